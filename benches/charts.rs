@@ -0,0 +1,104 @@
+//! Frame times of charts whose data stays the same between frames (and so
+//! draw from cached geometry) against charts whose data changes every frame,
+//! and of detecting changes by hashing against by version
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use egui::{CentralPanel, Context, RawInput, Rect, Ui, Vec2};
+use egui_charts::prelude::*;
+
+/// Run and tessellate one frame showing `add_contents`
+fn frame(ctx: &Context, mut add_contents: impl FnMut(&mut Ui)) {
+    let input = RawInput {
+        screen_rect: Some(Rect::from_min_size(egui::Pos2::ZERO, Vec2::new(1200.0, 800.0))),
+        ..Default::default()
+    };
+    let output = ctx.run(input, |ctx| {
+        CentralPanel::default().show(ctx, |ui| add_contents(ui));
+    });
+    std::hint::black_box(ctx.tessellate(output.shapes, output.pixels_per_point));
+}
+
+/// Two slightly different versions of a series
+fn series(len: usize) -> [Vec<f64>; 2] {
+    let a: Vec<f64> = (0..len).map(|i| 50.0 + 40.0 * (i as f64 / 300.0).sin()).collect();
+    let b: Vec<f64> = a.iter().map(|v| v + 1.0).collect();
+    [a, b]
+}
+
+fn line_10k(c: &mut Criterion) {
+    let data = series(10_000);
+    let show = |ui: &mut Ui, data: &[f64]| {
+        LineChart::new()
+            .id("line")
+            .data(data.to_vec())
+            .fill(true)
+            .animate(Animation::none())
+            .size([1000.0, 400.0])
+            .show(ui);
+    };
+
+    let mut group = c.benchmark_group("line_10k");
+    let ctx = Context::default();
+    group.bench_function("unchanged", |b| b.iter(|| frame(&ctx, |ui| show(ui, &data[0]))));
+    let ctx = Context::default();
+    let mut flip = 0;
+    group.bench_function("changing", |b| {
+        b.iter(|| {
+            flip ^= 1;
+            frame(&ctx, |ui| show(ui, &data[flip]))
+        })
+    });
+    group.finish();
+}
+
+fn line_1m(c: &mut Criterion) {
+    let data = &series(1_000_000)[0];
+    let show = |ui: &mut Ui, chart: LineChart<'_>| {
+        chart.id("line").animate(Animation::none()).size([1000.0, 400.0]).show(ui);
+    };
+
+    let mut group = c.benchmark_group("line_1m");
+    let ctx = Context::default();
+    group.bench_function("copied", |b| {
+        b.iter(|| frame(&ctx, |ui| show(ui, LineChart::new().data(data.clone()))))
+    });
+    let ctx = Context::default();
+    group.bench_function("hashed", |b| {
+        b.iter(|| frame(&ctx, |ui| show(ui, LineChart::new().data_source(&data[..]))))
+    });
+    let ctx = Context::default();
+    group.bench_function("versioned", |b| {
+        b.iter(|| frame(&ctx, |ui| show(ui, LineChart::new().data_source(Versioned::new(&data[..], 1)))))
+    });
+    group.finish();
+}
+
+fn bar_1k(c: &mut Criterion) {
+    let data = series(1_000);
+    let labels: Vec<String> = (0..1_000).map(|i| format!("Item {i}")).collect();
+    let show = |ui: &mut Ui, data: &[f64]| {
+        BarChart::new()
+            .id("bar")
+            .data(data.to_vec())
+            .labels(labels.clone())
+            .animate(Animation::none())
+            .size([1000.0, 400.0])
+            .show(ui);
+    };
+
+    let mut group = c.benchmark_group("bar_1k");
+    let ctx = Context::default();
+    group.bench_function("unchanged", |b| b.iter(|| frame(&ctx, |ui| show(ui, &data[0]))));
+    let ctx = Context::default();
+    let mut flip = 0;
+    group.bench_function("changing", |b| {
+        b.iter(|| {
+            flip ^= 1;
+            frame(&ctx, |ui| show(ui, &data[flip]))
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, line_10k, line_1m, bar_1k);
+criterion_main!(benches);
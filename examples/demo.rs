@@ -19,6 +19,8 @@ enum ChartType {
     Bar,
     Line,
     Pie,
+    Gauge,
+    Linked,
 }
 
 struct DemoApp {
@@ -31,6 +33,7 @@ struct DemoApp {
     // Common options
     theme: ThemePreset,
     animation_duration: f32,
+    easing: Easing,
     show_tooltip: bool,
     show_legend: bool,
     // Bar chart options
@@ -45,10 +48,26 @@ struct DemoApp {
     line_show_grid: bool,
     line_show_values: bool,
     line_width: f32,
+    line_crosshair: bool,
+    line_large: bool,
+    line_large_data: Vec<f64>,
+    line_live: bool,
+    line_gaps: bool,
+    line_monotone: bool,
+    line_stepped: bool,
+    line_stream: DataStream,
+    line_next_sample: f64,
     // Pie chart options
     donut_ratio: f32,
     pie_show_labels: bool,
     pie_show_percentages: bool,
+    pie_circumference: f32,
+    pie_nested: bool,
+    pie_explode: f32,
+    pie_spacing: f32,
+    pie_border_radius: f32,
+    pie_selected: Vec<usize>,
+    gauge_value: f64,
 }
 
 impl Default for DemoApp {
@@ -63,6 +82,7 @@ impl Default for DemoApp {
             // Common options
             theme: ThemePreset::Light,
             animation_duration: 0.8,
+            easing: Easing::EaseOutQuart,
             show_tooltip: true,
             show_legend: true,
             // Bar chart options
@@ -77,10 +97,31 @@ impl Default for DemoApp {
             line_show_grid: true,
             line_show_values: false,
             line_width: 3.0,
+            line_crosshair: true,
+            line_large: false,
+            line_large_data: (0..100_000)
+                .map(|i| {
+                    let t = i as f64 / 1000.0;
+                    50.0 + 30.0 * t.sin() + 10.0 * (t * 7.3).sin() + 5.0 * (i as f64 * 0.37).sin()
+                })
+                .collect(),
+            line_live: false,
+            line_gaps: false,
+            line_monotone: false,
+            line_stepped: false,
+            line_stream: DataStream::with_capacity(60),
+            line_next_sample: 0.0,
             // Pie chart options
             donut_ratio: 0.5,
             pie_show_labels: false,
             pie_show_percentages: false,
+            pie_circumference: 360.0,
+            pie_nested: false,
+            pie_explode: 0.0,
+            pie_spacing: 0.0,
+            pie_border_radius: 0.0,
+            pie_selected: Vec::new(),
+            gauge_value: 72.0,
         }
     }
 }
@@ -97,6 +138,8 @@ impl eframe::App for DemoApp {
                 ui.selectable_value(&mut self.chart_type, ChartType::Bar, "Bar");
                 ui.selectable_value(&mut self.chart_type, ChartType::Line, "Line");
                 ui.selectable_value(&mut self.chart_type, ChartType::Pie, "Pie");
+                ui.selectable_value(&mut self.chart_type, ChartType::Gauge, "Gauge");
+                ui.selectable_value(&mut self.chart_type, ChartType::Linked, "Linked");
 
                 ui.separator();
 
@@ -112,8 +155,29 @@ impl eframe::App for DemoApp {
                         ChartType::Pie => {
                             self.pie_data = (0..5).map(|_| rng.gen_range(10.0..50.0)).collect();
                         }
+                        ChartType::Gauge => {
+                            self.gauge_value = rng.gen_range(0.0..100.0);
+                        }
+                        ChartType::Linked => {
+                            self.bar_data = (0..7).map(|_| rng.gen_range(20.0..100.0)).collect();
+                            self.line_data = (0..7).map(|_| rng.gen_range(10.0..100.0)).collect();
+                        }
                     }
                 }
+
+                ui.separator();
+                let chart_id = match self.chart_type {
+                    ChartType::Bar => egui::Id::new("bar_chart"),
+                    ChartType::Line => egui::Id::new("line_chart"),
+                    ChartType::Pie => egui::Id::new("pie_chart"),
+                    ChartType::Gauge => egui::Id::new("gauge_chart"),
+                    ChartType::Linked => egui::Id::new(("linked", 0)),
+                };
+                if ui.button("Reset Zoom").clicked() {
+                    let mut state = ChartState::load(ctx, chart_id).unwrap_or_default();
+                    state.reset_zoom();
+                    state.store(ctx, chart_id);
+                }
             });
         });
 
@@ -144,6 +208,23 @@ impl eframe::App for DemoApp {
                     .text("Animation")
                     .suffix("s"),
             );
+            egui::ComboBox::from_id_salt("easing")
+                .selected_text(format!("{:?}", self.easing))
+                .show_ui(ui, |ui| {
+                    for easing in Easing::ALL {
+                        ui.selectable_value(&mut self.easing, easing, format!("{:?}", easing));
+                    }
+                    ui.selectable_value(
+                        &mut self.easing,
+                        Easing::CubicBezier(0.68, -0.55, 0.27, 1.55),
+                        "CubicBezier",
+                    );
+                    ui.selectable_value(
+                        &mut self.easing,
+                        Easing::Spring { stiffness: 100.0, damping: 8.0 },
+                        "Spring",
+                    );
+                });
 
             ui.add_space(16.0);
 
@@ -171,23 +252,55 @@ impl eframe::App for DemoApp {
                     ui.checkbox(&mut self.line_show_values, "Show Values");
                     ui.checkbox(&mut self.line_fill, "Area Fill");
                     ui.checkbox(&mut self.line_curved, "Curved Lines");
+                    ui.checkbox(&mut self.line_monotone, "Monotone Curves");
+                    ui.checkbox(&mut self.line_stepped, "Stepped");
                     ui.checkbox(&mut self.line_show_points, "Show Points");
+                    ui.checkbox(&mut self.line_crosshair, "Crosshair");
+                    ui.checkbox(&mut self.line_large, "100k Points (decimated)");
+                    ui.checkbox(&mut self.line_live, "Live Stream");
+                    ui.checkbox(&mut self.line_gaps, "Missing Reading");
                     ui.add_space(8.0);
                     ui.add(
                         egui::Slider::new(&mut self.line_width, 1.0..=6.0)
                             .text("Line Width"),
                     );
                 }
+                ChartType::Linked => {
+                    ui.heading("Linked Charts");
+                    ui.separator();
+                    ui.label("Hover, zoom or pan one chart and the others follow.");
+                }
                 ChartType::Pie => {
                     ui.heading("Pie Options");
                     ui.separator();
                     ui.checkbox(&mut self.pie_show_labels, "Show Labels");
                     ui.checkbox(&mut self.pie_show_percentages, "Show Percentages");
+                    ui.checkbox(&mut self.pie_nested, "Last Year Ring");
                     ui.add_space(8.0);
                     ui.add(
                         egui::Slider::new(&mut self.donut_ratio, 0.0..=0.8)
                             .text("Donut Hole"),
                     );
+                    ui.add(
+                        egui::Slider::new(&mut self.pie_circumference, 90.0..=360.0)
+                            .text("Circumference"),
+                    );
+                    ui.add(egui::Slider::new(&mut self.pie_explode, 0.0..=20.0).text("Explode"));
+                    ui.add(egui::Slider::new(&mut self.pie_spacing, 0.0..=12.0).text("Spacing"));
+                    ui.add(egui::Slider::new(&mut self.pie_border_radius, 0.0..=16.0).text("Corner Radius"));
+                    ui.add_space(8.0);
+                    ui.label("Click segments to select them.");
+                    let selected: Vec<&str> = self
+                        .pie_selected
+                        .iter()
+                        .filter_map(|&i| self.pie_labels.get(i).map(String::as_str))
+                        .collect();
+                    ui.label(format!("Selected: {}", if selected.is_empty() { "none".into() } else { selected.join(", ") }));
+                }
+                ChartType::Gauge => {
+                    ui.heading("Gauge Options");
+                    ui.separator();
+                    ui.add(egui::Slider::new(&mut self.gauge_value, 0.0..=100.0).text("Value"));
                 }
             }
         });
@@ -215,7 +328,9 @@ impl eframe::App for DemoApp {
                             "#36a2eb", "#ff6384", "#ffce56", "#4bc0c0", "#9966ff", "#ff9f40", "#c9cbcf",
                         ];
 
+                        let chart_id = egui::Id::new("bar_chart");
                         BarChart::new()
+                            .id(chart_id)
                             .data(self.bar_data.clone())
                             .labels(self.labels.clone())
                             .colors(colors.clone())
@@ -223,67 +338,180 @@ impl eframe::App for DemoApp {
                             .border_radius(self.bar_border_radius)
                             .grid(self.bar_show_grid)
                             .show_values(self.bar_show_values)
-                            .animate(Animation::custom(Easing::EaseOutQuart, self.animation_duration))
+                            .animate(Animation::custom(self.easing, self.animation_duration))
                             .tooltip(self.show_tooltip)
                             .theme_preset(self.theme)
+                            .zoom(ZoomConfig::new().axis(ZoomAxis::X))
+                            .annotation(
+                                Annotation::horizontal_line(
+                                    self.bar_data.iter().sum::<f64>() / self.bar_data.len().max(1) as f64,
+                                )
+                                .label("Average")
+                                .dashed(true)
+                                .color(egui::Color32::from_gray(120)),
+                            )
                             .size([600.0, 350.0])
                             .show(ui);
 
                         if self.show_legend {
                             ui.add_space(15.0);
-                            draw_legend(ui, &self.labels, &colors, self.theme);
+                            draw_legend(ui, chart_id, &self.labels, &colors, self.theme);
                         }
                     }
                     ChartType::Line => {
                         ui.heading("Weekly Temperature");
                         ui.add_space(10.0);
 
+                        let chart_id = egui::Id::new("line_chart");
                         let color = "#36a2eb";
 
-                        LineChart::new()
-                            .data(self.line_data.clone())
-                            .labels(self.labels.clone())
+                        let mut chart = LineChart::new();
+                        if self.line_crosshair {
+                            chart = chart.crosshair(Crosshair::new().snap_to_index(true));
+                        }
+                        if self.line_monotone {
+                            chart = chart.cubic_interpolation(CubicInterpolation::Monotone);
+                        }
+                        if self.line_stepped {
+                            chart = chart.stepped(Stepped::Before);
+                        }
+                        chart = if self.line_live {
+                            // A new sample every 100ms
+                            let now = ui.input(|i| i.time);
+                            while self.line_next_sample <= now {
+                                let t = self.line_next_sample;
+                                self.line_stream.push(t, 50.0 + 25.0 * (t * 0.8).sin() + 8.0 * (t * 3.1).sin());
+                                self.line_next_sample = t.max(now - 1.0) + 0.1;
+                            }
+                            ui.ctx().request_repaint_after(std::time::Duration::from_millis(100));
+                            chart.stream(&self.line_stream)
+                        } else if self.line_large {
+                            chart.data_source(Versioned::new(&self.line_large_data[..], 0)).decimation(Decimation::min_max())
+                        } else if self.line_gaps {
+                            // No reading on Thursday: the line breaks there
+                            let readings: Vec<Option<f64>> =
+                                self.line_data.iter().enumerate().map(|(i, v)| (i != 3).then_some(*v)).collect();
+                            chart.data_source(readings).labels(self.labels.clone())
+                        } else {
+                            chart.data(self.line_data.clone()).labels(self.labels.clone())
+                        };
+                        chart
+                            .id(chart_id)
                             .color(color)
                             .fill(self.line_fill)
                             .curved(self.line_curved)
-                            .show_points(self.line_show_points)
+                            .show_points(self.line_show_points && !self.line_large && !self.line_live)
                             .show_values(self.line_show_values)
                             .line_width(self.line_width)
                             .point_radius(5.0)
                             .grid(self.line_show_grid)
-                            .animate(Animation::custom(Easing::EaseOutQuart, self.animation_duration))
+                            .animate(Animation::custom(self.easing, self.animation_duration))
                             .tooltip(self.show_tooltip)
                             .theme_preset(self.theme)
+                            .zoom(
+                                ZoomConfig::new()
+                                    .drag_select(true)
+                                    .pan_modifier(egui::Modifiers::SHIFT),
+                            )
+                            .annotation(
+                                Annotation::x_range(4.5, 6.0)
+                                    .label("Weekend")
+                                    .color(egui::Color32::from_rgb(75, 192, 192))
+                                    .behind(),
+                            )
+                            .annotation(Annotation::horizontal_line(80.0).label("Heat warning").interactive(true))
                             .size([600.0, 350.0])
                             .show(ui);
 
                         if self.show_legend {
                             ui.add_space(15.0);
-                            draw_legend(ui, &["Temperature".to_string()], &[color], self.theme);
+                            draw_legend(ui, chart_id, &["Temperature".to_string()], &[color], self.theme);
                         }
                     }
+                    ChartType::Linked => {
+                        ui.heading("Linked Time Series");
+                        ui.add_space(10.0);
+
+                        let group = LinkGroup::new("linked_demo");
+                        let series = [
+                            ("Temperature", &self.line_data, "#36a2eb"),
+                            ("Sales", &self.bar_data, "#ff6384"),
+                        ];
+                        for (i, (label, data, color)) in series.into_iter().enumerate() {
+                            LineChart::new()
+                                .id(egui::Id::new(("linked", i)))
+                                .data(data.clone())
+                                .label(label)
+                                .title(label)
+                                .labels(self.labels.clone())
+                                .color(color)
+                                .curved(self.line_curved)
+                                .animate(Animation::custom(self.easing, self.animation_duration))
+                                .tooltip(self.show_tooltip)
+                                .theme_preset(self.theme)
+                                .zoom(ZoomConfig::new().axis(ZoomAxis::X))
+                                .crosshair(Crosshair::new().horizontal(false))
+                                .link(group)
+                                .size([600.0, 180.0])
+                                .show(ui);
+                        }
+                    }
+                    ChartType::Gauge => {
+                        ui.heading("Server Load");
+                        ui.add_space(10.0);
+
+                        GaugeChart::new()
+                            .id("gauge_chart")
+                            .value(self.gauge_value)
+                            .range(0.0, 100.0)
+                            .band(60.0, "#4bc0c0")
+                            .band(85.0, "#ffce56")
+                            .band(100.0, "#ff6384")
+                            .label("%")
+                            .animate(Animation::custom(self.easing, self.animation_duration))
+                            .tooltip(self.show_tooltip)
+                            .theme_preset(self.theme)
+                            .size([400.0, 260.0])
+                            .show(ui);
+                    }
                     ChartType::Pie => {
                         ui.heading("Browser Market Share");
                         ui.add_space(10.0);
 
                         let colors = vec!["#36a2eb", "#ff6384", "#ffce56", "#4bc0c0", "#9966ff"];
 
-                        PieChart::new()
+                        let chart_id = egui::Id::new("pie_chart");
+                        let mut chart = PieChart::new();
+                        if self.pie_nested {
+                            let last_year = self.pie_data.iter().rev().copied().collect::<Vec<_>>();
+                            chart = chart.label("This year").dataset(PieDataset::new("Last year", last_year).weight(0.6));
+                        }
+                        let response = chart
+                            .id(chart_id)
                             .data(self.pie_data.clone())
                             .labels(self.pie_labels.clone())
                             .colors(colors.clone())
                             .donut(self.donut_ratio)
+                            .center(DonutCenter::default().hovered_details(true))
+                            .circumference(self.pie_circumference)
+                            .rotation(-self.pie_circumference / 2.0)
+                            .offset(self.pie_explode)
+                            .spacing(self.pie_spacing)
+                            .border_radius(self.pie_border_radius)
+                            .selectable(true)
+                            .data_labels(DataLabels::new().anchor(LabelAnchor::Center))
                             .show_labels(self.pie_show_labels)
                             .show_percentages(self.pie_show_percentages)
-                            .animate(Animation::custom(Easing::EaseOutQuart, self.animation_duration))
+                            .animate(Animation::custom(self.easing, self.animation_duration))
                             .tooltip(self.show_tooltip)
                             .theme_preset(self.theme)
                             .size([350.0, 350.0])
                             .show(ui);
+                        self.pie_selected = response.selected;
 
                         if self.show_legend {
                             ui.add_space(15.0);
-                            draw_legend(ui, &self.pie_labels, &colors, self.theme);
+                            draw_legend(ui, chart_id, &self.pie_labels, &colors, self.theme);
                         }
                     }
                 }
@@ -292,12 +520,21 @@ impl eframe::App for DemoApp {
     }
 }
 
-/// Draw a simple legend below the chart
-fn draw_legend<S: AsRef<str>>(ui: &mut egui::Ui, labels: &[S], colors: &[&str], theme: ThemePreset) {
+/// Draw a simple legend below the chart; clicking an item hides or shows it
+fn draw_legend<S: AsRef<str>>(
+    ui: &mut egui::Ui,
+    chart_id: egui::Id,
+    labels: &[S],
+    colors: &[&str],
+    theme: ThemePreset,
+) {
     let text_color = match theme {
         ThemePreset::Dark => egui::Color32::from_gray(220),
         _ => egui::Color32::from_gray(60),
     };
+    let mut state = ChartState::load(ui.ctx(), chart_id).unwrap_or_default();
+    // A single legend entry stands for the whole dataset
+    let single = labels.len() == 1;
 
     ui.horizontal_wrapped(|ui| {
         ui.spacing_mut().item_spacing.x = 16.0;
@@ -306,13 +543,31 @@ fn draw_legend<S: AsRef<str>>(ui: &mut egui::Ui, labels: &[S], colors: &[&str],
             let color_str = colors.get(i % colors.len()).unwrap_or(&"#888888");
             let color = parse_color(color_str);
 
-            ui.horizontal(|ui| {
-                let (rect, _) = ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
-                ui.painter().rect_filled(rect, 2.0, color);
-                ui.label(egui::RichText::new(label.as_ref()).color(text_color).size(12.0));
-            });
+            let visible = if single { state.is_visible(0) } else { state.is_data_visible(i) };
+            let mut text = egui::RichText::new(label.as_ref()).color(text_color).size(12.0);
+            if !visible {
+                text = text.strikethrough();
+            }
+
+            let clicked = ui
+                .horizontal(|ui| {
+                    let (rect, _) = ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+                    ui.painter().rect_filled(rect, 2.0, color);
+                    ui.add(egui::Label::new(text).sense(egui::Sense::click())).clicked()
+                })
+                .inner;
+
+            if clicked {
+                if single {
+                    state.toggle_visible(0);
+                } else {
+                    state.toggle_data_visibility(i);
+                }
+            }
         }
     });
+
+    state.store(ui.ctx(), chart_id);
 }
 
 /// Parse hex color string to Color32
@@ -1,29 +1,116 @@
-use egui::Context;
-use std::time::Instant;
+use egui::{Color32, Context, Id, Ui};
+use std::f32::consts::PI;
+
+use crate::helpers::color::lerp_color;
 
 /// Easing functions matching Chart.js
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+///
+/// Includes the full Chart.js set plus CSS-style `CubicBezier`, a physical
+/// `Spring` and user supplied `Custom` curves.
+#[derive(Clone, Copy, Debug, Default)]
 pub enum Easing {
     Linear,
+    EaseInQuad,
+    EaseOutQuad,
+    EaseInOutQuad,
+    EaseInCubic,
+    EaseOutCubic,
+    EaseInOutCubic,
+    EaseInQuart,
     #[default]
     EaseOutQuart,
-    EaseInQuart,
     EaseInOutQuart,
-    EaseOutBounce,
+    EaseInQuint,
+    EaseOutQuint,
+    EaseInOutQuint,
+    EaseInSine,
+    EaseOutSine,
+    EaseInOutSine,
+    EaseInExpo,
+    EaseOutExpo,
+    EaseInOutExpo,
+    EaseInCirc,
+    EaseOutCirc,
+    EaseInOutCirc,
+    EaseInElastic,
     EaseOutElastic,
-    EaseOutCubic,
-    EaseInOutCubic,
+    EaseInOutElastic,
+    EaseInBack,
+    EaseOutBack,
+    EaseInOutBack,
+    EaseInBounce,
+    EaseOutBounce,
+    EaseInOutBounce,
+    /// CSS `cubic-bezier(x1, y1, x2, y2)`; x control points are clamped to [0, 1]
+    CubicBezier(f32, f32, f32, f32),
+    /// Damped spring (unit mass), time-scaled so it settles at t = 1
+    Spring { stiffness: f32, damping: f32 },
+    /// User supplied easing curve, receives t in [0, 1]
+    Custom(fn(f32) -> f32),
 }
 
 impl Easing {
+    /// All named (non-parameterized) easings, in Chart.js order
+    pub const ALL: [Easing; 31] = [
+        Easing::Linear,
+        Easing::EaseInQuad,
+        Easing::EaseOutQuad,
+        Easing::EaseInOutQuad,
+        Easing::EaseInCubic,
+        Easing::EaseOutCubic,
+        Easing::EaseInOutCubic,
+        Easing::EaseInQuart,
+        Easing::EaseOutQuart,
+        Easing::EaseInOutQuart,
+        Easing::EaseInQuint,
+        Easing::EaseOutQuint,
+        Easing::EaseInOutQuint,
+        Easing::EaseInSine,
+        Easing::EaseOutSine,
+        Easing::EaseInOutSine,
+        Easing::EaseInExpo,
+        Easing::EaseOutExpo,
+        Easing::EaseInOutExpo,
+        Easing::EaseInCirc,
+        Easing::EaseOutCirc,
+        Easing::EaseInOutCirc,
+        Easing::EaseInElastic,
+        Easing::EaseOutElastic,
+        Easing::EaseInOutElastic,
+        Easing::EaseInBack,
+        Easing::EaseOutBack,
+        Easing::EaseInOutBack,
+        Easing::EaseInBounce,
+        Easing::EaseOutBounce,
+        Easing::EaseInOutBounce,
+    ];
+
     /// Apply easing to normalized time t in [0, 1]
     pub fn apply(&self, t: f32) -> f32 {
         let t = t.clamp(0.0, 1.0);
         match self {
             Easing::Linear => t,
+            Easing::EaseInQuad => t * t,
+            Easing::EaseOutQuad => 1.0 - (1.0 - t).powi(2),
+            Easing::EaseInOutQuad => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::EaseInCubic => t.powi(3),
+            Easing::EaseOutCubic => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOutCubic => {
+                if t < 0.5 {
+                    4.0 * t.powi(3)
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::EaseInQuart => t.powi(4),
             // Chart.js: 1 - (1 - t)^4
             Easing::EaseOutQuart => 1.0 - (1.0 - t).powi(4),
-            Easing::EaseInQuart => t.powi(4),
             Easing::EaseInOutQuart => {
                 if t < 0.5 {
                     8.0 * t.powi(4)
@@ -31,16 +118,81 @@ impl Easing {
                     1.0 - (-2.0 * t + 2.0).powi(4) / 2.0
                 }
             }
-            Easing::EaseOutCubic => 1.0 - (1.0 - t).powi(3),
-            Easing::EaseInOutCubic => {
+            Easing::EaseInQuint => t.powi(5),
+            Easing::EaseOutQuint => 1.0 - (1.0 - t).powi(5),
+            Easing::EaseInOutQuint => {
                 if t < 0.5 {
-                    4.0 * t.powi(3)
+                    16.0 * t.powi(5)
                 } else {
-                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                    1.0 - (-2.0 * t + 2.0).powi(5) / 2.0
                 }
             }
-            Easing::EaseOutBounce => Self::bounce_out(t),
+            Easing::EaseInSine => 1.0 - (t * PI / 2.0).cos(),
+            Easing::EaseOutSine => (t * PI / 2.0).sin(),
+            Easing::EaseInOutSine => -((PI * t).cos() - 1.0) / 2.0,
+            Easing::EaseInExpo => {
+                if t == 0.0 {
+                    0.0
+                } else {
+                    2.0_f32.powf(10.0 * t - 10.0)
+                }
+            }
+            Easing::EaseOutExpo => {
+                if t == 1.0 {
+                    1.0
+                } else {
+                    1.0 - 2.0_f32.powf(-10.0 * t)
+                }
+            }
+            Easing::EaseInOutExpo => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else if t < 0.5 {
+                    2.0_f32.powf(20.0 * t - 10.0) / 2.0
+                } else {
+                    (2.0 - 2.0_f32.powf(-20.0 * t + 10.0)) / 2.0
+                }
+            }
+            Easing::EaseInCirc => 1.0 - (1.0 - t * t).sqrt(),
+            Easing::EaseOutCirc => (1.0 - (t - 1.0).powi(2)).sqrt(),
+            Easing::EaseInOutCirc => {
+                if t < 0.5 {
+                    (1.0 - (1.0 - (2.0 * t).powi(2)).sqrt()) / 2.0
+                } else {
+                    ((1.0 - (-2.0 * t + 2.0).powi(2)).sqrt() + 1.0) / 2.0
+                }
+            }
+            Easing::EaseInElastic => Self::elastic_in(t),
             Easing::EaseOutElastic => Self::elastic_out(t),
+            Easing::EaseInOutElastic => Self::elastic_in_out(t),
+            Easing::EaseInBack => {
+                const C1: f32 = 1.70158;
+                (C1 + 1.0) * t.powi(3) - C1 * t * t
+            }
+            Easing::EaseOutBack => {
+                const C1: f32 = 1.70158;
+                1.0 + (C1 + 1.0) * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
+            }
+            Easing::EaseInOutBack => {
+                const C2: f32 = 1.70158 * 1.525;
+                if t < 0.5 {
+                    (2.0 * t).powi(2) * ((C2 + 1.0) * 2.0 * t - C2) / 2.0
+                } else {
+                    ((2.0 * t - 2.0).powi(2) * ((C2 + 1.0) * (t * 2.0 - 2.0) + C2) + 2.0) / 2.0
+                }
+            }
+            Easing::EaseInBounce => 1.0 - Self::bounce_out(1.0 - t),
+            Easing::EaseOutBounce => Self::bounce_out(t),
+            Easing::EaseInOutBounce => {
+                if t < 0.5 {
+                    (1.0 - Self::bounce_out(1.0 - 2.0 * t)) / 2.0
+                } else {
+                    (1.0 + Self::bounce_out(2.0 * t - 1.0)) / 2.0
+                }
+            }
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier(*x1, *y1, *x2, *y2, t),
+            Easing::Spring { stiffness, damping } => spring(*stiffness, *damping, t),
+            Easing::Custom(f) => f(t),
         }
     }
 
@@ -61,14 +213,207 @@ impl Easing {
         }
     }
 
+    fn elastic_in(t: f32) -> f32 {
+        if t == 0.0 || t == 1.0 {
+            t
+        } else {
+            let c4 = (2.0 * PI) / 3.0;
+            -(2.0_f32.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * c4).sin()
+        }
+    }
+
     fn elastic_out(t: f32) -> f32 {
         if t == 0.0 || t == 1.0 {
             t
         } else {
-            let c4 = (2.0 * std::f32::consts::PI) / 3.0;
+            let c4 = (2.0 * PI) / 3.0;
             2.0_f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * c4).sin() + 1.0
         }
     }
+
+    fn elastic_in_out(t: f32) -> f32 {
+        if t == 0.0 || t == 1.0 {
+            return t;
+        }
+        let c5 = (2.0 * PI) / 4.5;
+        let s = ((20.0 * t - 11.125) * c5).sin();
+        if t < 0.5 {
+            -(2.0_f32.powf(20.0 * t - 10.0) * s) / 2.0
+        } else {
+            2.0_f32.powf(-20.0 * t + 10.0) * s / 2.0 + 1.0
+        }
+    }
+}
+
+impl PartialEq for Easing {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Easing::CubicBezier(a1, b1, c1, d1), Easing::CubicBezier(a2, b2, c2, d2)) => {
+                (a1, b1, c1, d1) == (a2, b2, c2, d2)
+            }
+            (
+                Easing::Spring { stiffness: s1, damping: d1 },
+                Easing::Spring { stiffness: s2, damping: d2 },
+            ) => s1 == s2 && d1 == d2,
+            // Function identity is best-effort: the same fn may have several addresses
+            (Easing::Custom(f1), Easing::Custom(f2)) => std::ptr::fn_addr_eq(*f1, *f2),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+/// Evaluate a CSS-style cubic bezier timing function at time `x`
+///
+/// Solves bezier_x(s) = x for the curve parameter with Newton's method,
+/// falling back to bisection, then returns bezier_y(s). Same approach as
+/// browser implementations of `cubic-bezier()`.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    if x <= 0.0 || x >= 1.0 {
+        return x;
+    }
+    let (x1, x2) = (x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0));
+
+    // Polynomial coefficients of B(s) = ((a*s + b)*s + c)*s
+    let cx = 3.0 * x1;
+    let bx = 3.0 * (x2 - x1) - cx;
+    let ax = 1.0 - cx - bx;
+    let cy = 3.0 * y1;
+    let by = 3.0 * (y2 - y1) - cy;
+    let ay = 1.0 - cy - by;
+
+    let sample_x = |s: f32| ((ax * s + bx) * s + cx) * s;
+    let sample_dx = |s: f32| (3.0 * ax * s + 2.0 * bx) * s + cx;
+    let sample_y = |s: f32| ((ay * s + by) * s + cy) * s;
+
+    const EPSILON: f32 = 1e-6;
+
+    // Newton-Raphson from the linear guess
+    let mut s = x;
+    for _ in 0..8 {
+        let err = sample_x(s) - x;
+        if err.abs() < EPSILON {
+            return sample_y(s);
+        }
+        let d = sample_dx(s);
+        if d.abs() < EPSILON {
+            break;
+        }
+        s -= err / d;
+    }
+
+    // Bisection fallback for flat regions
+    let (mut lo, mut hi) = (0.0_f32, 1.0_f32);
+    s = x;
+    for _ in 0..32 {
+        let sx = sample_x(s);
+        if (sx - x).abs() < EPSILON {
+            break;
+        }
+        if sx < x {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) / 2.0;
+    }
+    sample_y(s)
+}
+
+/// Damped harmonic oscillator from 0 to 1 (unit mass)
+///
+/// Normalized time is stretched to the spring's settle time (when the decay
+/// envelope falls below 0.1%), so the spring lands on 1.0 at t = 1.
+fn spring(stiffness: f32, damping: f32, t: f32) -> f32 {
+    if t >= 1.0 {
+        return 1.0;
+    }
+    let omega0 = stiffness.max(0.001).sqrt();
+    let zeta = damping.max(0.0) / (2.0 * omega0);
+    // Treat near-critical damping as critical to avoid dividing by a tiny omega_d
+    let critical = (zeta - 1.0).abs() < 0.03;
+
+    // Slowest decay rate determines how long the spring takes to settle
+    let decay = if critical {
+        omega0 / 1.5
+    } else if zeta < 1.0 {
+        zeta * omega0
+    } else {
+        omega0 * (zeta - (zeta * zeta - 1.0).sqrt())
+    };
+    let settle = if decay > 0.0 { 1000.0_f32.ln() / decay } else { 10.0 };
+    let tau = t * settle;
+
+    if critical {
+        1.0 - (-omega0 * tau).exp() * (1.0 + omega0 * tau)
+    } else if zeta < 1.0 {
+        let omega_d = omega0 * (1.0 - zeta * zeta).sqrt();
+        let envelope = (-zeta * omega0 * tau).exp();
+        1.0 - envelope * ((omega_d * tau).cos() + (zeta * omega0 / omega_d) * (omega_d * tau).sin())
+    } else {
+        let root = (zeta * zeta - 1.0).sqrt();
+        let r1 = -omega0 * (zeta - root);
+        let r2 = -omega0 * (zeta + root);
+        1.0 - (r2 * (r1 * tau).exp() - r1 * (r2 * tau).exp()) / (r2 - r1)
+    }
+}
+
+/// Element property that can be animated independently
+///
+/// Mirrors the property names used by Chart.js `animations` config.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnimatedProperty {
+    /// Horizontal position / width (bars grow sideways, lines reveal left to right)
+    X,
+    /// Vertical position / height (bars and points rise from the baseline)
+    Y,
+    /// Point radius and arc radius (pie/donut scale in from the center)
+    Radius,
+    /// Fill and border colors
+    Colors,
+    /// Arc sweep angle
+    Angle,
+}
+
+/// Starting value for a property animation (Chart.js `from`)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnimationFrom {
+    /// Fraction of the final value to start from (0.0 = grow from nothing)
+    Fraction(f32),
+    /// Color to fade from (only meaningful for [`AnimatedProperty::Colors`])
+    Color(Color32),
+}
+
+/// Animation settings for a single property
+#[derive(Clone, Debug)]
+pub struct PropertyAnimation {
+    pub easing: Easing,
+    pub duration_secs: f32,
+    /// Fixed delay before this property starts, added to the per-index delay
+    pub delay_secs: f32,
+    pub from: Option<AnimationFrom>,
+}
+
+impl PropertyAnimation {
+    pub fn new(easing: Easing, duration_secs: f32) -> Self {
+        Self {
+            easing,
+            duration_secs,
+            delay_secs: 0.0,
+            from: None,
+        }
+    }
+
+    /// Delay this property by a fixed amount of seconds
+    pub fn delay(mut self, delay_secs: f32) -> Self {
+        self.delay_secs = delay_secs.max(0.0);
+        self
+    }
+
+    /// Set the value this property animates from
+    pub fn from(mut self, from: AnimationFrom) -> Self {
+        self.from = Some(from);
+        self
+    }
 }
 
 /// Animation configuration
@@ -77,6 +422,12 @@ pub struct AnimationConfig {
     pub easing: Easing,
     pub duration_secs: f32,
     pub enabled: bool,
+    /// Delay in seconds by data index, for staggered animations (Chart.js `delay`)
+    pub delay: Option<fn(usize) -> f32>,
+    /// Replay the animation endlessly, reversing direction every cycle (Chart.js `loop`)
+    pub looping: bool,
+    /// Per-property overrides; properties not listed use `easing`/`duration_secs`
+    pub properties: Vec<(AnimatedProperty, PropertyAnimation)>,
 }
 
 impl Default for AnimationConfig {
@@ -85,62 +436,261 @@ impl Default for AnimationConfig {
             easing: Easing::EaseOutQuart,
             duration_secs: 0.8,
             enabled: true,
+            delay: None,
+            looping: false,
+            properties: Vec::new(),
         }
     }
 }
 
-/// Runtime animation state (stored in egui memory)
-#[derive(Clone, Debug)]
-pub struct AnimationState {
-    start_time: Option<Instant>,
-    config: AnimationConfig,
-}
+impl AnimationConfig {
+    /// Set a delay callback by data index
+    ///
+    /// ```rust,ignore
+    /// // Bars rise one after another, 50ms apart
+    /// Animation::ease_out_quart(0.6).delay(|i| i as f32 * 0.05)
+    /// ```
+    pub fn delay(mut self, delay: fn(usize) -> f32) -> Self {
+        self.delay = Some(delay);
+        self
+    }
 
-impl Default for AnimationState {
-    fn default() -> Self {
-        Self {
-            start_time: None,
-            config: AnimationConfig::default(),
+    /// Loop the animation endlessly
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Configure a single property (replaces any previous config for it)
+    pub fn property(mut self, property: AnimatedProperty, animation: PropertyAnimation) -> Self {
+        self.properties.retain(|(p, _)| *p != property);
+        self.properties.push((property, animation));
+        self
+    }
+
+    /// Get the override for a property, if one is configured
+    pub fn get_property(&self, property: AnimatedProperty) -> Option<&PropertyAnimation> {
+        self.properties
+            .iter()
+            .find(|(p, _)| *p == property)
+            .map(|(_, a)| a)
+    }
+
+    /// Whether the property has an explicit animation configured
+    pub fn animates(&self, property: AnimatedProperty) -> bool {
+        self.enabled && self.get_property(property).is_some()
+    }
+
+    /// Timing for a property: (easing, duration, fixed delay, from)
+    fn timing(&self, property: AnimatedProperty) -> (Easing, f32, f32, Option<AnimationFrom>) {
+        match self.get_property(property) {
+            Some(anim) => (anim.easing, anim.duration_secs, anim.delay_secs, anim.from),
+            None => (self.easing, self.duration_secs, 0.0, None),
+        }
+    }
+
+    /// Duration for state transitions such as hiding a dataset or rescaling
+    /// an axis; zero when animations are disabled or egui requests reduced motion
+    pub(crate) fn transition_secs(&self, ui: &Ui) -> f32 {
+        if self.enabled && ui.style().animation_time > 0.0 {
+            self.duration_secs
+        } else {
+            0.0
         }
     }
+
+    fn index_delay(&self, index: usize) -> f32 {
+        self.delay.map(|f| f(index).max(0.0)).unwrap_or(0.0)
+    }
+}
+
+/// Runtime animation state (stored in egui memory)
+///
+/// Time is driven by egui's input clock (`ctx.input(|i| i.time)`) rather than
+/// the system clock, so animations are deterministic, work on wasm and can be
+/// paused, seeked and scrubbed. Each chart stores its state under its own
+/// [`Id`]; use [`AnimationState::load`] and [`AnimationState::store`] with the
+/// id passed to the chart's `.id()` to control it from outside:
+///
+/// ```rust,ignore
+/// if let Some(mut animation) = AnimationState::load(ui.ctx(), chart_id) {
+///     animation.seek(slider_value); // scrub to 0.0..=1.0 of the timeline
+///     animation.store(ui.ctx(), chart_id);
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AnimationState {
+    /// Input time the animation started at (`None` when disabled)
+    start_time: Option<f64>,
+    /// Input time of the most recent frame
+    now: f64,
+    /// Elapsed seconds frozen while paused
+    paused_elapsed: Option<f32>,
+    config: AnimationConfig,
+    element_count: usize,
 }
 
 impl AnimationState {
-    pub fn new(config: AnimationConfig) -> Self {
+    /// Start a new animation at input time `now`
+    pub fn new(config: AnimationConfig, now: f64) -> Self {
         Self {
-            start_time: if config.enabled {
-                Some(Instant::now())
-            } else {
-                None
-            },
+            start_time: if config.enabled { Some(now) } else { None },
+            now,
+            paused_elapsed: None,
             config,
+            element_count: 0,
         }
     }
 
+    /// Load the animation state of the chart with the given id
+    pub fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.data(|d| d.get_temp(id))
+    }
+
+    /// Store the animation state for the chart with the given id
+    pub fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|d| d.insert_temp(id, self));
+    }
+
+    /// Advance the clock to input time `now`
+    pub fn tick(&mut self, now: f64) {
+        self.now = now;
+    }
+
     /// Restart animation from beginning
     pub fn restart(&mut self) {
         if self.config.enabled {
-            self.start_time = Some(Instant::now());
+            self.start_time = Some(self.now);
+            if self.paused_elapsed.is_some() {
+                self.paused_elapsed = Some(0.0);
+            }
         }
     }
 
+    /// Freeze the animation at its current position
+    pub fn pause(&mut self) {
+        if self.paused_elapsed.is_none() {
+            self.paused_elapsed = Some(self.elapsed());
+        }
+    }
+
+    /// Continue a paused animation from where it was frozen
+    pub fn resume(&mut self) {
+        if let Some(elapsed) = self.paused_elapsed.take() {
+            if self.start_time.is_some() {
+                self.start_time = Some(self.now - elapsed as f64);
+            }
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_elapsed.is_some()
+    }
+
+    /// Jump to a position on the timeline, 0.0 = start, 1.0 = every element finished
+    ///
+    /// Keeps the paused state, so pausing then seeking every frame scrubs.
+    pub fn seek(&mut self, position: f32) {
+        self.seek_secs(position.clamp(0.0, 1.0) * self.total_duration());
+    }
+
+    /// Jump to `secs` seconds after the animation start
+    pub fn seek_secs(&mut self, secs: f32) {
+        let Some(_) = self.start_time else {
+            return;
+        };
+        let secs = secs.max(0.0);
+        if self.paused_elapsed.is_some() {
+            self.paused_elapsed = Some(secs);
+        } else {
+            self.start_time = Some(self.now - secs as f64);
+        }
+    }
+
+    /// Current position on the timeline, 0.0 = start, 1.0 = finished
+    pub fn position(&self) -> f32 {
+        match self.start_time {
+            None => 1.0,
+            Some(_) => {
+                let total = self.total_duration();
+                if total <= 0.0 {
+                    1.0
+                } else {
+                    (self.elapsed() / total).min(1.0)
+                }
+            }
+        }
+    }
+
+    /// Set the number of animated elements, used to account for per-index delays
+    pub fn set_element_count(&mut self, count: usize) {
+        self.element_count = count;
+    }
+
     /// Get current progress [0.0, 1.0] with easing applied
     pub fn progress(&self) -> f32 {
         match self.start_time {
             None => 1.0, // Animation disabled, show final state
-            Some(start) => {
-                let elapsed = start.elapsed().as_secs_f32();
-                let t = (elapsed / self.config.duration_secs).min(1.0);
+            Some(_) => {
+                let t = self.cycle_t(self.elapsed(), self.config.duration_secs);
                 self.config.easing.apply(t)
             }
         }
     }
 
-    /// Check if animation is still running
+    /// Progress of one property for the element at `index`
+    ///
+    /// Applies the property's own easing, duration and `from`, plus the
+    /// per-index delay. Returns the fraction of the final value to display.
+    pub fn property_progress(&self, property: AnimatedProperty, index: usize) -> f32 {
+        if self.start_time.is_none() {
+            return 1.0;
+        }
+        let (easing, duration, delay, from) = self.config.timing(property);
+        let elapsed = self.elapsed() - delay - self.config.index_delay(index);
+        let eased = easing.apply(self.cycle_t(elapsed.max(0.0), duration));
+
+        match from {
+            Some(AnimationFrom::Fraction(f)) => f + (1.0 - f) * eased,
+            _ => eased,
+        }
+    }
+
+    /// Animate a color towards `target` for the element at `index`
+    ///
+    /// Colors only animate when [`AnimatedProperty::Colors`] is configured.
+    /// Without an explicit `from` color the element fades in from transparent.
+    pub fn animate_color(&self, target: Color32, index: usize) -> Color32 {
+        if !self.config.animates(AnimatedProperty::Colors) {
+            return target;
+        }
+        let from = match self.config.timing(AnimatedProperty::Colors).3 {
+            Some(AnimationFrom::Color(c)) => c,
+            _ => {
+                let [r, g, b, _] = target.to_srgba_unmultiplied();
+                Color32::from_rgba_unmultiplied(r, g, b, 0)
+            }
+        };
+        let t = self.property_progress(AnimatedProperty::Colors, index);
+        lerp_color(from, target, t)
+    }
+
+    /// Check if animation is still running (paused animations are not)
     pub fn is_animating(&self) -> bool {
         match self.start_time {
             None => false,
-            Some(start) => start.elapsed().as_secs_f32() < self.config.duration_secs,
+            Some(_) if self.is_paused() => false,
+            Some(_) if self.config.looping => true,
+            Some(_) => self.elapsed() < self.total_duration(),
+        }
+    }
+
+    /// Check if every element has reached its final state (unlike
+    /// [`Self::is_animating`], false while paused part way)
+    pub fn is_finished(&self) -> bool {
+        match self.start_time {
+            None => true,
+            Some(_) => !self.config.looping && self.elapsed() >= self.total_duration(),
         }
     }
 
@@ -155,6 +705,78 @@ impl AnimationState {
     pub fn set_config(&mut self, config: AnimationConfig) {
         self.config = config;
     }
+
+    /// Seconds since the animation started, as of the last tick
+    fn elapsed(&self) -> f32 {
+        match (self.paused_elapsed, self.start_time) {
+            (Some(elapsed), _) => elapsed,
+            (None, Some(start)) => (self.now - start).max(0.0) as f32,
+            (None, None) => 0.0,
+        }
+    }
+
+    /// Time until every property of every element has finished
+    fn total_duration(&self) -> f32 {
+        let max_index_delay = (0..self.element_count)
+            .map(|i| self.config.index_delay(i))
+            .fold(0.0, f32::max);
+        let longest_property = self
+            .config
+            .properties
+            .iter()
+            .map(|(_, a)| a.delay_secs + a.duration_secs)
+            .fold(self.config.duration_secs, f32::max);
+        max_index_delay + longest_property
+    }
+
+    /// Normalized time within the current cycle, ping-ponging when looping
+    fn cycle_t(&self, elapsed: f32, duration: f32) -> f32 {
+        if duration <= 0.0 {
+            return 1.0;
+        }
+        let t = elapsed / duration;
+        if !self.config.looping {
+            return t.min(1.0);
+        }
+        let cycle = t.floor();
+        let frac = t - cycle;
+        if (cycle as u64).is_multiple_of(2) {
+            frac
+        } else {
+            1.0 - frac
+        }
+    }
+}
+
+/// Load a chart's animation state and advance it to the current frame
+///
+/// Restarts the animation when `data_changed`, and disables it when egui's
+/// `animation_time` is zero (the conventional reduced-motion setting).
+pub(crate) fn update_chart_animation(
+    ui: &Ui,
+    id: Id,
+    config: &AnimationConfig,
+    data_changed: bool,
+    element_count: usize,
+) -> AnimationState {
+    let now = ui.input(|i| i.time);
+    let mut config = config.clone();
+    if ui.style().animation_time <= 0.0 {
+        config.enabled = false;
+    }
+
+    let mut state = match AnimationState::load(ui.ctx(), id) {
+        Some(state) if !data_changed => state,
+        _ => AnimationState::new(config.clone(), now),
+    };
+    if !config.enabled {
+        state.start_time = None;
+    }
+    state.set_config(config);
+    state.tick(now);
+    state.set_element_count(element_count);
+    state.request_repaint_if_animating(ui.ctx());
+    state
 }
 
 /// Builder helper for fluent API
@@ -168,6 +790,7 @@ impl Animation {
             easing: Easing::EaseOutQuart,
             duration_secs,
             enabled: true,
+            ..Default::default()
         }
     }
 
@@ -177,6 +800,7 @@ impl Animation {
             easing: Easing::Linear,
             duration_secs,
             enabled: true,
+            ..Default::default()
         }
     }
 
@@ -186,6 +810,7 @@ impl Animation {
             easing: Easing::EaseOutBounce,
             duration_secs,
             enabled: true,
+            ..Default::default()
         }
     }
 
@@ -195,6 +820,27 @@ impl Animation {
             easing: Easing::EaseOutElastic,
             duration_secs,
             enabled: true,
+            ..Default::default()
+        }
+    }
+
+    /// CSS-style `cubic-bezier(x1, y1, x2, y2)` timing
+    pub fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, duration_secs: f32) -> AnimationConfig {
+        AnimationConfig {
+            easing: Easing::CubicBezier(x1, y1, x2, y2),
+            duration_secs,
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    /// Physical spring (e.g. stiffness 170, damping 26 for a gentle settle)
+    pub fn spring(stiffness: f32, damping: f32, duration_secs: f32) -> AnimationConfig {
+        AnimationConfig {
+            easing: Easing::Spring { stiffness, damping },
+            duration_secs,
+            enabled: true,
+            ..Default::default()
         }
     }
 
@@ -204,6 +850,7 @@ impl Animation {
             easing,
             duration_secs,
             enabled: true,
+            ..Default::default()
         }
     }
 
@@ -244,7 +891,7 @@ mod tests {
             // Mid-values should be in reasonable range
             let mid = easing.apply(0.5);
             assert!(
-                mid >= 0.0 && mid <= 1.0,
+                (0.0..=1.0).contains(&mid),
                 "{:?} mid value {} out of range",
                 easing,
                 mid
@@ -252,6 +899,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_all_easings_endpoints() {
+        for easing in Easing::ALL {
+            assert!(easing.apply(0.0).abs() < 0.001, "{:?} failed at t=0", easing);
+            assert!((easing.apply(1.0) - 1.0).abs() < 0.001, "{:?} failed at t=1", easing);
+        }
+    }
+
+    #[test]
+    fn test_in_out_symmetry() {
+        // In-out variants pass through the midpoint
+        for easing in [
+            Easing::EaseInOutQuad,
+            Easing::EaseInOutCubic,
+            Easing::EaseInOutQuint,
+            Easing::EaseInOutSine,
+            Easing::EaseInOutExpo,
+            Easing::EaseInOutCirc,
+            Easing::EaseInOutBounce,
+        ] {
+            assert!((easing.apply(0.5) - 0.5).abs() < 0.001, "{:?} not symmetric", easing);
+        }
+    }
+
+    #[test]
+    fn test_back_overshoots() {
+        assert!(Easing::EaseInBack.apply(0.2) < 0.0);
+        assert!(Easing::EaseOutBack.apply(0.8) > 1.0);
+    }
+
+    #[test]
+    fn test_cubic_bezier_matches_presets() {
+        // CSS `linear` equivalent
+        let linear = Easing::CubicBezier(0.0, 0.0, 1.0, 1.0);
+        for t in [0.1, 0.25, 0.5, 0.9] {
+            assert!((linear.apply(t) - t).abs() < 0.001);
+        }
+
+        // CSS `ease`: known value at x = 0.5 is ~0.8024
+        let ease = Easing::CubicBezier(0.25, 0.1, 0.25, 1.0);
+        assert!((ease.apply(0.5) - 0.8024).abs() < 0.002);
+
+        // Monotonic for valid control points
+        let mut prev = 0.0;
+        for i in 1..=20 {
+            let v = ease.apply(i as f32 / 20.0);
+            assert!(v >= prev);
+            prev = v;
+        }
+    }
+
+    #[test]
+    fn test_spring() {
+        for (stiffness, damping) in [(170.0, 26.0), (100.0, 5.0), (100.0, 20.0), (100.0, 60.0)] {
+            let spring = Easing::Spring { stiffness, damping };
+            assert!(spring.apply(0.0).abs() < 0.001);
+            assert!((spring.apply(1.0) - 1.0).abs() < 0.001);
+            // Settled within 1% well before the end
+            assert!((spring.apply(0.9) - 1.0).abs() < 0.01);
+        }
+
+        // Underdamped springs overshoot
+        let bouncy = Easing::Spring { stiffness: 100.0, damping: 5.0 };
+        assert!((0..100).any(|i| bouncy.apply(i as f32 / 100.0) > 1.0));
+    }
+
+    #[test]
+    fn test_custom_easing() {
+        fn square(t: f32) -> f32 {
+            t * t
+        }
+        assert!((Easing::Custom(square).apply(0.5) - 0.25).abs() < 0.0001);
+    }
+
     #[test]
     fn test_ease_out_quart_formula() {
         // Chart.js formula: 1 - (1 - t)^4
@@ -278,21 +999,30 @@ mod tests {
             easing: Easing::Linear,
             duration_secs: 1.0,
             enabled: true,
+            ..Default::default()
         };
-        let state = AnimationState::new(config);
+        let mut state = AnimationState::new(config, 10.0);
 
-        // Progress should start near 0
+        // Progress should start at 0
         let initial_progress = state.progress();
-        assert!(initial_progress < 0.1);
+        assert!(initial_progress < 0.001);
 
         // Should be animating
         assert!(state.is_animating());
+
+        // Deterministic: driven only by the ticked input time
+        state.tick(10.5);
+        assert!((state.progress() - 0.5).abs() < 0.001);
+        state.tick(11.5);
+        assert!((state.progress() - 1.0).abs() < 0.001);
+        assert!(!state.is_animating());
+        assert!(state.is_finished());
     }
 
     #[test]
     fn test_animation_disabled() {
         let config = Animation::none();
-        let state = AnimationState::new(config);
+        let state = AnimationState::new(config, 0.0);
 
         // Should immediately be at full progress
         assert!((state.progress() - 1.0).abs() < 0.001);
@@ -305,4 +1035,118 @@ mod tests {
         assert!((Easing::EaseOutBounce.apply(0.0)).abs() < 0.001);
         assert!((Easing::EaseOutBounce.apply(1.0) - 1.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_property_override() {
+        let config = Animation::linear(1.0)
+            .property(AnimatedProperty::Y, PropertyAnimation::new(Easing::EaseOutBounce, 2.0))
+            .property(AnimatedProperty::Y, PropertyAnimation::new(Easing::EaseInQuart, 0.5));
+
+        // Later config for the same property replaces the earlier one
+        assert_eq!(config.properties.len(), 1);
+        assert_eq!(config.get_property(AnimatedProperty::Y).unwrap().easing, Easing::EaseInQuart);
+        assert!(config.animates(AnimatedProperty::Y));
+        assert!(!config.animates(AnimatedProperty::X));
+    }
+
+    #[test]
+    fn test_property_from_fraction() {
+        let config = Animation::linear(10.0).property(
+            AnimatedProperty::Radius,
+            PropertyAnimation::new(Easing::Linear, 10.0).from(AnimationFrom::Fraction(0.5)),
+        );
+        let state = AnimationState::new(config, 0.0);
+
+        // Just started: radius begins at half its final size
+        let p = state.property_progress(AnimatedProperty::Radius, 0);
+        assert!((0.5..0.55).contains(&p));
+    }
+
+    #[test]
+    fn test_staggered_delay() {
+        let mut state = AnimationState::new(Animation::linear(1.0).delay(|i| i as f32 * 10.0), 0.0);
+        state.set_element_count(3);
+
+        // Later elements have not started yet
+        assert!(state.property_progress(AnimatedProperty::Y, 2) < 0.001);
+        assert!((state.total_duration() - 21.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_loop_reverses() {
+        let state = AnimationState::new(Animation::linear(1.0).looping(true), 0.0);
+        assert!((state.cycle_t(0.25, 1.0) - 0.25).abs() < 0.001);
+        assert!((state.cycle_t(1.25, 1.0) - 0.75).abs() < 0.001);
+        assert!((state.cycle_t(2.25, 1.0) - 0.25).abs() < 0.001);
+        assert!(state.is_animating());
+    }
+
+    #[test]
+    fn test_animate_color_only_when_configured() {
+        let target = Color32::from_rgb(255, 99, 132);
+        let plain = AnimationState::new(Animation::linear(10.0), 0.0);
+        assert_eq!(plain.animate_color(target, 0), target);
+
+        let fading = AnimationState::new(
+            Animation::linear(10.0)
+                .property(AnimatedProperty::Colors, PropertyAnimation::new(Easing::Linear, 10.0)),
+            0.0,
+        );
+        // Fades in from transparent
+        assert!(fading.animate_color(target, 0).a() < 20);
+    }
+
+    #[test]
+    fn test_pause_resume() {
+        let mut state = AnimationState::new(Animation::linear(2.0), 0.0);
+        state.tick(0.5);
+        state.pause();
+        assert!(state.is_paused());
+        assert!(!state.is_animating());
+        assert!(!state.is_finished());
+
+        // Time passes while paused, progress stays frozen
+        state.tick(5.0);
+        assert!((state.progress() - 0.25).abs() < 0.001);
+
+        // Resuming continues from the frozen position
+        state.resume();
+        state.tick(5.5);
+        assert!((state.progress() - 0.5).abs() < 0.001);
+        assert!(state.is_animating());
+    }
+
+    #[test]
+    fn test_seek_and_scrub() {
+        let mut state = AnimationState::new(Animation::linear(4.0), 100.0);
+        state.seek(0.75);
+        assert!((state.progress() - 0.75).abs() < 0.001);
+        assert!((state.position() - 0.75).abs() < 0.001);
+
+        // Scrubbing while paused
+        state.pause();
+        state.seek(0.25);
+        state.tick(200.0);
+        assert!((state.progress() - 0.25).abs() < 0.001);
+
+        state.seek_secs(3.0);
+        assert!((state.progress() - 0.75).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_reduced_motion_disables_animation() {
+        let ctx = Context::default();
+        ctx.style_mut(|style| style.animation_time = 0.0);
+
+        let mut progress = 0.0;
+        let _ = ctx.run(Default::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let state =
+                    update_chart_animation(ui, Id::new("chart"), &Animation::linear(1.0), true, 3);
+                progress = state.progress();
+            });
+        });
+
+        assert!((progress - 1.0).abs() < 0.001);
+    }
 }
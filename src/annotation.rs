@@ -0,0 +1,410 @@
+use egui::{Align2, Color32, FontId, Painter, Pos2, Rect, Shape, Stroke, StrokeKind, Vec2};
+
+use crate::scale::LinearScale;
+
+/// Padding around annotation labels
+const LABEL_PADDING: Vec2 = Vec2::new(5.0, 3.0);
+/// Pointer distance, in pixels, that counts as touching a line
+const HIT_DISTANCE: f32 = 4.0;
+
+/// Shape of an annotation, in data coordinates
+///
+/// Bar charts place category `i` at `x = i..i + 1`; line charts place point `i` at `x = i`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AnnotationKind {
+    /// Line across the chart at a y value
+    HorizontalLine { y: f64 },
+    /// Line across the chart at an x value
+    VerticalLine { x: f64 },
+    /// Shaded box; open sides extend to the chart edge
+    Box {
+        x_min: Option<f64>,
+        x_max: Option<f64>,
+        y_min: Option<f64>,
+        y_max: Option<f64>,
+    },
+    /// Standalone text label
+    Text { x: f64, y: f64, text: String },
+    /// Arrow pointing from one data position to another
+    Arrow { from: (f64, f64), to: (f64, f64) },
+    /// Point marker
+    Point { x: f64, y: f64, radius: f32 },
+}
+
+/// Whether an annotation is drawn behind or in front of the datasets
+/// (Chart.js annotation `drawTime`)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AnnotationLayer {
+    Behind,
+    #[default]
+    Front,
+}
+
+/// A line, box, label, arrow or point marker drawn over a cartesian chart
+///
+/// ```rust,ignore
+/// LineChart::new()
+///     .data(latency)
+///     .annotation(Annotation::horizontal_line(250.0).label("SLO").dashed(true))
+///     .annotation(Annotation::x_range(3.0, 5.0).label("Maintenance").behind())
+///     .show(ui);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Annotation {
+    pub kind: AnnotationKind,
+    /// Text drawn next to the annotation
+    pub label: Option<String>,
+    /// Line, arrow and marker color
+    pub color: Color32,
+    /// Box and point fill
+    pub fill: Color32,
+    pub width: f32,
+    pub dashed: bool,
+    pub label_color: Color32,
+    pub label_background: Color32,
+    pub layer: AnnotationLayer,
+    /// Report hover and clicks in the chart response
+    pub interactive: bool,
+}
+
+impl Annotation {
+    pub fn new(kind: AnnotationKind) -> Self {
+        let color = Color32::from_rgb(255, 99, 132);
+        Self {
+            kind,
+            label: None,
+            color,
+            fill: color.gamma_multiply(0.15),
+            width: 2.0,
+            dashed: false,
+            label_color: Color32::WHITE,
+            label_background: Color32::from_rgba_unmultiplied(0, 0, 0, 200),
+            layer: AnnotationLayer::Front,
+            interactive: false,
+        }
+    }
+
+    /// Horizontal line at `y`, e.g. a threshold
+    pub fn horizontal_line(y: f64) -> Self {
+        Self::new(AnnotationKind::HorizontalLine { y })
+    }
+
+    /// Vertical line at `x`, e.g. a deployment marker
+    pub fn vertical_line(x: f64) -> Self {
+        Self::new(AnnotationKind::VerticalLine { x })
+    }
+
+    /// Full-height box between two x values
+    pub fn x_range(min: f64, max: f64) -> Self {
+        Self::new(AnnotationKind::Box {
+            x_min: Some(min),
+            x_max: Some(max),
+            y_min: None,
+            y_max: None,
+        })
+    }
+
+    /// Full-width box between two y values
+    pub fn y_range(min: f64, max: f64) -> Self {
+        Self::new(AnnotationKind::Box {
+            x_min: None,
+            x_max: None,
+            y_min: Some(min),
+            y_max: Some(max),
+        })
+    }
+
+    /// Box between two corners
+    pub fn boxed(x: (f64, f64), y: (f64, f64)) -> Self {
+        Self::new(AnnotationKind::Box {
+            x_min: Some(x.0),
+            x_max: Some(x.1),
+            y_min: Some(y.0),
+            y_max: Some(y.1),
+        })
+    }
+
+    /// Text label at a data position
+    pub fn text(x: f64, y: f64, text: impl Into<String>) -> Self {
+        Self::new(AnnotationKind::Text { x, y, text: text.into() })
+    }
+
+    /// Arrow between two data positions
+    pub fn arrow(from: (f64, f64), to: (f64, f64)) -> Self {
+        Self::new(AnnotationKind::Arrow { from, to })
+    }
+
+    /// Point marker at a data position
+    pub fn point(x: f64, y: f64) -> Self {
+        Self::new(AnnotationKind::Point { x, y, radius: 6.0 })
+    }
+
+    /// Set label text
+    pub fn label(mut self, text: impl Into<String>) -> Self {
+        self.label = Some(text.into());
+        self
+    }
+
+    /// Set line color; box and point fills follow with transparency
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = color;
+        self.fill = color.gamma_multiply(0.15);
+        self
+    }
+
+    /// Set box and point fill
+    pub fn fill(mut self, color: Color32) -> Self {
+        self.fill = color;
+        self
+    }
+
+    /// Set line width
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Draw lines dashed
+    pub fn dashed(mut self, dashed: bool) -> Self {
+        self.dashed = dashed;
+        self
+    }
+
+    /// Set label text and background colors
+    pub fn label_colors(mut self, text: Color32, background: Color32) -> Self {
+        self.label_color = text;
+        self.label_background = background;
+        self
+    }
+
+    /// Draw behind the datasets
+    pub fn behind(mut self) -> Self {
+        self.layer = AnnotationLayer::Behind;
+        self
+    }
+
+    /// Choose the drawing layer
+    pub fn layer(mut self, layer: AnnotationLayer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Report hover and clicks in the chart response
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Draw the annotation, thicker when hovered
+    fn draw(&self, painter: &Painter, chart_rect: Rect, x_scale: &LinearScale, y_scale: &LinearScale, hovered: bool) {
+        let width = if hovered { self.width + 1.0 } else { self.width };
+        let stroke = Stroke::new(width, self.color);
+        let font_id = FontId::proportional(11.0);
+
+        match &self.kind {
+            AnnotationKind::HorizontalLine { y } => {
+                let y = y_scale.value_to_pixel(*y);
+                self.draw_line(painter, Pos2::new(chart_rect.min.x, y), Pos2::new(chart_rect.max.x, y), stroke);
+                self.draw_label(painter, Pos2::new(chart_rect.max.x - 4.0, y - 4.0), Align2::RIGHT_BOTTOM, &font_id);
+            }
+            AnnotationKind::VerticalLine { x } => {
+                let x = x_scale.value_to_pixel(*x);
+                self.draw_line(painter, Pos2::new(x, chart_rect.min.y), Pos2::new(x, chart_rect.max.y), stroke);
+                self.draw_label(painter, Pos2::new(x, chart_rect.min.y + 4.0), Align2::CENTER_TOP, &font_id);
+            }
+            AnnotationKind::Box { .. } => {
+                let rect = self.box_rect(chart_rect, x_scale, y_scale);
+                let border = Stroke::new(width.min(if hovered { 2.0 } else { 1.0 }), self.color);
+                painter.rect(rect, 0.0, self.fill, border, StrokeKind::Inside);
+                self.draw_label(painter, rect.left_top() + Vec2::splat(4.0), Align2::LEFT_TOP, &font_id);
+            }
+            AnnotationKind::Text { x, y, text } => {
+                let pos = Pos2::new(x_scale.value_to_pixel(*x), y_scale.value_to_pixel(*y));
+                draw_text_box(painter, pos, Align2::CENTER_CENTER, text, &font_id, self.label_color, self.label_background);
+            }
+            AnnotationKind::Arrow { from, to } => {
+                let from = Pos2::new(x_scale.value_to_pixel(from.0), y_scale.value_to_pixel(from.1));
+                let to = Pos2::new(x_scale.value_to_pixel(to.0), y_scale.value_to_pixel(to.1));
+                painter.arrow(from, to - from, stroke);
+                self.draw_label(painter, from, Align2::CENTER_BOTTOM, &font_id);
+            }
+            AnnotationKind::Point { x, y, radius } => {
+                let center = Pos2::new(x_scale.value_to_pixel(*x), y_scale.value_to_pixel(*y));
+                let radius = if hovered { radius * 1.3 } else { *radius };
+                painter.circle(center, radius, self.fill, stroke);
+                self.draw_label(painter, center - Vec2::new(0.0, radius + 4.0), Align2::CENTER_BOTTOM, &font_id);
+            }
+        }
+    }
+
+    /// Whether `pos` touches the annotation
+    fn contains(&self, pos: Pos2, chart_rect: Rect, x_scale: &LinearScale, y_scale: &LinearScale) -> bool {
+        match &self.kind {
+            AnnotationKind::HorizontalLine { y } => (pos.y - y_scale.value_to_pixel(*y)).abs() <= HIT_DISTANCE,
+            AnnotationKind::VerticalLine { x } => (pos.x - x_scale.value_to_pixel(*x)).abs() <= HIT_DISTANCE,
+            AnnotationKind::Box { .. } => self.box_rect(chart_rect, x_scale, y_scale).contains(pos),
+            AnnotationKind::Text { x, y, text } => {
+                // Approximate the label size without laying out text
+                let center = Pos2::new(x_scale.value_to_pixel(*x), y_scale.value_to_pixel(*y));
+                let half_size = Vec2::new(text.chars().count() as f32 * 3.5, 7.0) + LABEL_PADDING;
+                Rect::from_center_size(center, half_size * 2.0).contains(pos)
+            }
+            AnnotationKind::Arrow { from, to } => {
+                let from = Pos2::new(x_scale.value_to_pixel(from.0), y_scale.value_to_pixel(from.1));
+                let to = Pos2::new(x_scale.value_to_pixel(to.0), y_scale.value_to_pixel(to.1));
+                distance_to_segment(pos, from, to) <= HIT_DISTANCE
+            }
+            AnnotationKind::Point { x, y, radius } => {
+                let center = Pos2::new(x_scale.value_to_pixel(*x), y_scale.value_to_pixel(*y));
+                pos.distance(center) <= radius + HIT_DISTANCE
+            }
+        }
+    }
+
+    /// Pixel rect of a box annotation, open sides clamped to the chart area
+    fn box_rect(&self, chart_rect: Rect, x_scale: &LinearScale, y_scale: &LinearScale) -> Rect {
+        let AnnotationKind::Box { x_min, x_max, y_min, y_max } = &self.kind else {
+            return Rect::NOTHING;
+        };
+        let left = x_min.map_or(chart_rect.min.x, |x| x_scale.value_to_pixel(x));
+        let right = x_max.map_or(chart_rect.max.x, |x| x_scale.value_to_pixel(x));
+        let bottom = y_min.map_or(chart_rect.max.y, |y| y_scale.value_to_pixel(y));
+        let top = y_max.map_or(chart_rect.min.y, |y| y_scale.value_to_pixel(y));
+        Rect::from_two_pos(Pos2::new(left, top), Pos2::new(right, bottom))
+    }
+
+    fn draw_line(&self, painter: &Painter, from: Pos2, to: Pos2, stroke: Stroke) {
+        if self.dashed {
+            painter.extend(Shape::dashed_line(&[from, to], stroke, 6.0, 4.0));
+        } else {
+            painter.line_segment([from, to], stroke);
+        }
+    }
+
+    fn draw_label(&self, painter: &Painter, anchor: Pos2, align: Align2, font_id: &FontId) {
+        if let Some(label) = &self.label {
+            draw_text_box(painter, anchor, align, label, font_id, self.label_color, self.label_background);
+        }
+    }
+}
+
+/// Text on a rounded background
+fn draw_text_box(
+    painter: &Painter,
+    anchor: Pos2,
+    align: Align2,
+    text: &str,
+    font_id: &FontId,
+    color: Color32,
+    background: Color32,
+) {
+    let galley = painter.layout_no_wrap(text.to_string(), font_id.clone(), color);
+    let rect = align.anchor_size(anchor, galley.size() + LABEL_PADDING * 2.0);
+    painter.rect(rect, 3.0, background, Stroke::NONE, StrokeKind::Inside);
+    painter.galley(rect.min + LABEL_PADDING, galley, color);
+}
+
+fn distance_to_segment(p: Pos2, a: Pos2, b: Pos2) -> f32 {
+    let ab = b - a;
+    let length_sq = ab.length_sq();
+    if length_sq <= f32::EPSILON {
+        return p.distance(a);
+    }
+    let t = ((p - a).dot(ab) / length_sq).clamp(0.0, 1.0);
+    p.distance(a + ab * t)
+}
+
+/// Draw the annotations of one layer
+pub(crate) fn draw_annotations(
+    painter: &Painter,
+    annotations: &[Annotation],
+    layer: AnnotationLayer,
+    chart_rect: Rect,
+    scales: (&LinearScale, &LinearScale),
+    hovered: Option<usize>,
+) {
+    for (i, annotation) in annotations.iter().enumerate() {
+        if annotation.layer == layer {
+            annotation.draw(painter, chart_rect, scales.0, scales.1, hovered == Some(i));
+        }
+    }
+}
+
+/// Topmost interactive annotation under `pos`
+pub(crate) fn annotation_at(
+    annotations: &[Annotation],
+    pos: Pos2,
+    chart_rect: Rect,
+    scales: (&LinearScale, &LinearScale),
+) -> Option<usize> {
+    if !chart_rect.contains(pos) {
+        return None;
+    }
+    // Front layer first, then reverse drawing order
+    let front = |a: &Annotation| a.layer == AnnotationLayer::Front;
+    let hit = |i: &usize| {
+        let a = &annotations[*i];
+        a.interactive && a.contains(pos, chart_rect, scales.0, scales.1)
+    };
+    let order = (0..annotations.len()).rev();
+    order
+        .clone()
+        .filter(|i| front(&annotations[*i]))
+        .find(hit)
+        .or_else(|| order.filter(|i| !front(&annotations[*i])).find(hit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scales() -> (Rect, LinearScale, LinearScale) {
+        let rect = Rect::from_min_max(Pos2::new(0.0, 0.0), Pos2::new(100.0, 100.0));
+        (
+            rect,
+            LinearScale::new(0.0, 10.0, 0.0, 100.0),
+            LinearScale::new(0.0, 100.0, 100.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn test_box_rect_open_sides() {
+        let (rect, x, y) = scales();
+        let band = Annotation::x_range(2.0, 4.0);
+        assert_eq!(
+            band.box_rect(rect, &x, &y),
+            Rect::from_min_max(Pos2::new(20.0, 0.0), Pos2::new(40.0, 100.0))
+        );
+        let band = Annotation::y_range(25.0, 50.0);
+        assert_eq!(
+            band.box_rect(rect, &x, &y),
+            Rect::from_min_max(Pos2::new(0.0, 50.0), Pos2::new(100.0, 75.0))
+        );
+    }
+
+    #[test]
+    fn test_hit_testing() {
+        let (rect, x, y) = scales();
+        let annotations = vec![
+            Annotation::x_range(0.0, 10.0).behind().interactive(true),
+            Annotation::horizontal_line(50.0).interactive(true),
+            Annotation::point(2.0, 80.0),
+        ];
+
+        // The front line wins over the box behind it
+        assert_eq!(annotation_at(&annotations, Pos2::new(30.0, 52.0), rect, (&x, &y)), Some(1));
+        assert_eq!(annotation_at(&annotations, Pos2::new(30.0, 70.0), rect, (&x, &y)), Some(0));
+        // Non-interactive point is ignored
+        assert_eq!(annotation_at(&annotations, Pos2::new(20.0, 20.0), rect, (&x, &y)), Some(0));
+        // Outside the chart area
+        assert_eq!(annotation_at(&annotations, Pos2::new(150.0, 50.0), rect, (&x, &y)), None);
+    }
+
+    #[test]
+    fn test_distance_to_segment() {
+        let d = distance_to_segment(Pos2::new(5.0, 3.0), Pos2::new(0.0, 0.0), Pos2::new(10.0, 0.0));
+        assert!((d - 3.0).abs() < 1e-5);
+        let d = distance_to_segment(Pos2::new(13.0, 4.0), Pos2::new(0.0, 0.0), Pos2::new(10.0, 0.0));
+        assert!((d - 5.0).abs() < 1e-5);
+    }
+}
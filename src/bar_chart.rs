@@ -1,19 +1,39 @@
-use egui::{Color32, CornerRadius, Id, Painter, Pos2, Rect, Response, Sense, Stroke, Ui, Vec2, Widget};
+use std::sync::Arc;
 
-use crate::animation::{AnimationConfig, AnimationState};
+use egui::{Align2, Color32, CornerRadius, FontId, Id, Mesh, Painter, Pos2, Rect, Response, Sense, Shape, Stroke, Ui, Vec2, Widget};
+
+use crate::animation::{update_chart_animation, AnimatedProperty, AnimationConfig};
+use crate::cache::{atlas_key, cache_key, cached, range_bits, rect_bits, tessellate, Cached, PlacedGalleys};
+use crate::annotation::{annotation_at, draw_annotations, Annotation, AnnotationLayer};
+use crate::crosshair::{draw_crosshair, Crosshair, CrosshairTarget};
+use crate::data_labels::{draw_data_labels, DataLabels, LabelContext, LabelRequest, LabelTarget};
 use crate::elements::{BarElement, BarStyle};
-use crate::helpers::color::{lighten, ChartColor};
-use crate::helpers::math::{compute_data_hash, nice_ticks};
-use crate::interaction::evaluate_interaction;
+use crate::helpers::color::ChartColor;
+use crate::data::{data_key, is_missing, values, ChartData, Values};
+use crate::helpers::math::compute_labels_hash;
+use crate::interaction::{evaluate_interaction, HoverStyle, HoverTransitions};
+use crate::link::{draw_cursor_line, LinkGroup};
+use crate::scale::{value_axis_range, LinearScale};
+use crate::state::ChartState;
+use crate::plugin::{chart_plugins, ChartElements, ChartEvent, ChartLayout, ChartPlugin, ChartTooltip, PluginContext};
 use crate::theme::{ChartTheme, ThemePreset};
-use crate::tooltip::{calculate_tooltip_position, draw_tooltip, measure_tooltip_size, TooltipContent};
+use crate::title::Title;
+use crate::tooltip::{TooltipContent, TooltipPlugin};
+use crate::zoom::{draw_selection, handle_zoom_input, VisibleRange, ZoomConfig};
 
 /// Memory stored in egui context between frames
 #[derive(Clone, Default)]
 struct BarChartMemory {
-    animation: AnimationState,
     data_hash: u64,
     hovered_index: Option<usize>,
+    hover: HoverTransitions,
+    /// Bar elements, rebuilt when the data, chart size, visible range or
+    /// style change
+    bars: Option<Cached<Vec<BarElement>>>,
+    /// Tessellated bars once animations finish
+    bars_mesh: Option<Cached<Mesh>>,
+    /// Laid out axis labels
+    axis_labels: Option<Cached<PlacedGalleys>>,
 }
 
 /// Response returned after showing the chart
@@ -25,13 +45,19 @@ pub struct BarChartResponse {
     pub hovered: Option<usize>,
     /// Index of clicked bar (if any this frame)
     pub clicked: Option<usize>,
+    /// Interactive annotation under the pointer
+    pub hovered_annotation: Option<usize>,
+    /// Interactive annotation clicked this frame
+    pub clicked_annotation: Option<usize>,
+    /// Data range on screen; bar `i` spans `x = i..i + 1`
+    pub visible_range: VisibleRange,
 }
 
 /// Bar chart widget with Chart.js-inspired API
 #[derive(Clone)]
-pub struct BarChart {
+pub struct BarChart<'a> {
     id: Option<Id>,
-    data: Vec<f64>,
+    data: Values<'a>,
     labels: Vec<String>,
     colors: Vec<ChartColor>,
     animation: AnimationConfig,
@@ -41,15 +67,20 @@ pub struct BarChart {
     min_size: Vec2,
     show_grid: bool,
     show_axes: bool,
-    show_values: bool,
+    data_labels: Option<DataLabels>,
     bar_style: Option<BarStyle>,
+    zoom: ZoomConfig,
+    link: Option<LinkGroup>,
+    crosshair: Option<Crosshair>,
+    annotations: Vec<Annotation>,
+    plugins: Vec<Arc<dyn ChartPlugin>>,
 }
 
-impl Default for BarChart {
+impl Default for BarChart<'_> {
     fn default() -> Self {
         Self {
             id: None,
-            data: Vec::new(),
+            data: Values::default(),
             labels: Vec::new(),
             colors: Vec::new(),
             animation: AnimationConfig::default(),
@@ -59,13 +90,18 @@ impl Default for BarChart {
             min_size: Vec2::new(100.0, 80.0),
             show_grid: true,
             show_axes: true,
-            show_values: false,
+            data_labels: None,
             bar_style: None,
+            zoom: ZoomConfig::default(),
+            link: None,
+            crosshair: None,
+            annotations: Vec::new(),
+            plugins: Vec::new(),
         }
     }
 }
 
-impl BarChart {
+impl<'a> BarChart<'a> {
     /// Create a new bar chart
     pub fn new() -> Self {
         Self::default()
@@ -80,7 +116,14 @@ impl BarChart {
 
     /// Set chart data values
     pub fn data(mut self, data: impl IntoIterator<Item = impl Into<f64>>) -> Self {
-        self.data = data.into_iter().map(|v| v.into()).collect();
+        self.data = Values::Owned(data.into_iter().map(|v| v.into()).collect());
+        self
+    }
+
+    /// Read the chart data in place, from `&values[..]` or a reference to
+    /// your own [`ChartData`] type, instead of copying it into the chart
+    pub fn data_source(mut self, data: impl ChartData + 'a) -> Self {
+        self.data = Values::Source(Arc::new(data));
         self
     }
 
@@ -146,7 +189,13 @@ impl BarChart {
 
     /// Show/hide value labels on bars
     pub fn show_values(mut self, show: bool) -> Self {
-        self.show_values = show;
+        self.data_labels = show.then(DataLabels::default);
+        self
+    }
+
+    /// Configure value labels on bars
+    pub fn data_labels(mut self, labels: DataLabels) -> Self {
+        self.data_labels = Some(labels);
         self
     }
 
@@ -177,6 +226,13 @@ impl BarChart {
         self
     }
 
+    /// Set hover appearance and transition
+    pub fn hover_style(mut self, hover: HoverStyle) -> Self {
+        let style = self.bar_style.get_or_insert_with(BarStyle::default);
+        style.hover = hover;
+        self
+    }
+
     /// Set bar border color
     pub fn border_color(mut self, color: impl Into<ChartColor>) -> Self {
         let style = self.bar_style.get_or_insert_with(BarStyle::default);
@@ -184,6 +240,47 @@ impl BarChart {
         self
     }
 
+    /// Enable zoom and pan
+    pub fn zoom(mut self, config: ZoomConfig) -> Self {
+        self.zoom = config;
+        self
+    }
+
+    /// Synchronize cursor, zoom and visibility with other charts in the group
+    pub fn link(mut self, group: LinkGroup) -> Self {
+        self.link = Some(group);
+        self
+    }
+
+    /// Draw crosshair lines following the cursor
+    pub fn crosshair(mut self, crosshair: Crosshair) -> Self {
+        self.crosshair = Some(crosshair);
+        self
+    }
+
+    /// Add an annotation (threshold line, range box, label, arrow or marker)
+    pub fn annotation(mut self, annotation: Annotation) -> Self {
+        self.annotations.push(annotation);
+        self
+    }
+
+    /// Add several annotations
+    pub fn annotations(mut self, annotations: impl IntoIterator<Item = Annotation>) -> Self {
+        self.annotations.extend(annotations);
+        self
+    }
+
+    /// Add a plugin hooking into this chart's drawing
+    pub fn plugin(mut self, plugin: impl ChartPlugin + 'static) -> Self {
+        self.plugins.push(Arc::new(plugin));
+        self
+    }
+
+    /// Add a title above the chart
+    pub fn title(self, text: impl Into<String>) -> Self {
+        self.plugin(Title::new(text))
+    }
+
     /// Show the chart and return response
     pub fn show(self, ui: &mut Ui) -> BarChartResponse {
         // Determine size
@@ -206,74 +303,226 @@ impl BarChart {
         let mut memory = ui
             .ctx()
             .data_mut(|d| d.get_temp_mut_or_insert_with::<BarChartMemory>(id, Default::default).clone());
-
-        // Check for data changes
-        let new_data_hash = compute_data_hash(&self.data);
-        if memory.data_hash != new_data_hash {
-            memory.animation = AnimationState::new(self.animation.clone());
-            memory.data_hash = new_data_hash;
+        let mut state = ChartState::load(ui.ctx(), id).unwrap_or_default();
+        if let Some(link) = &self.link {
+            link.pull(ui.ctx(), id, &mut state);
         }
 
-        // Get animation progress
-        let progress = memory.animation.progress();
-
-        // Request repaint if still animating
-        memory.animation.request_repaint_if_animating(ui.ctx());
+        // Check for data changes, by version for sources that have one
+        let new_data_hash = data_key(&self.data);
+        let data_changed = memory.data_hash != new_data_hash;
+        memory.data_hash = new_data_hash;
+        let animation =
+            update_chart_animation(ui, id, &self.animation, data_changed, self.data.len());
 
-        // Calculate layout regions
-        let y_axis_width = 45.0;
-        let x_axis_height = 30.0;
-        let top_padding = 15.0;
-        let right_padding = 15.0;
-
-        let chart_rect = Rect::from_min_max(
-            Pos2::new(rect.min.x + y_axis_width, rect.min.y + top_padding),
-            Pos2::new(rect.max.x - right_padding, rect.max.y - x_axis_height),
-        );
+        // Get animation progress
+        let progress = animation.progress();
+        let y_progress: Vec<f32> = (0..self.data.len())
+            .map(|i| animation.property_progress(AnimatedProperty::Y, i))
+            .collect();
+
+        // Animated visibility: the whole dataset (index 0) and each bar
+        let transition_secs = self.animation.transition_secs(ui);
+        let dataset_amount = state.dataset_amount(ui.ctx(), id, 0, transition_secs);
+        let visibility: Vec<f32> = (0..self.data.len())
+            .map(|i| dataset_amount * state.data_amount(ui.ctx(), id, i, transition_secs))
+            .collect();
+        // Missing values have no bar to show
+        let shown: Vec<bool> = (0..self.data.len())
+            .map(|i| state.is_visible(0) && state.is_data_visible(i) && !is_missing(self.data.y(i)))
+            .collect();
+
+        // Calculate layout regions, letting plugins reserve space
+        let plugins = chart_plugins(ui.ctx(), &self.plugins);
+        let mut layout = ChartLayout {
+            left: 45.0,
+            right: 15.0,
+            top: 15.0,
+            bottom: 30.0,
+        };
+        for plugin in &plugins {
+            plugin.before_layout(&mut layout);
+        }
+        let chart_rect = layout.chart_area(rect);
 
         // Draw background
         if self.theme.background_color != Color32::TRANSPARENT {
             painter.rect_filled(rect, CornerRadius::ZERO, self.theme.background_color);
         }
 
-        // Build bar elements
-        let bars = self.build_bar_elements(chart_rect);
+        // Y scale covers visible bars, rescaling smoothly when that changes
+        let (target_min, target_max) = value_axis_range(
+            values(&self.data)
+                .enumerate()
+                .filter(|(i, _)| shown[*i])
+                .map(|(_, v)| v),
+        );
+        let y_min = ui.ctx().animate_value_with_time(id.with("y_min"), target_min as f32, transition_secs);
+        let y_max = ui.ctx().animate_value_with_time(id.with("y_max"), target_max as f32, transition_secs);
+
+        // Zoom and pan within the full data range
+        let full_range = VisibleRange::new((0.0, self.data.len().max(1) as f64), (y_min as f64, y_max as f64));
+        let scales = |range: VisibleRange| {
+            (
+                LinearScale::new(range.x_min, range.x_max, chart_rect.min.x, chart_rect.max.x),
+                LinearScale::new(range.y_min, range.y_max, chart_rect.max.y, chart_rect.min.y),
+            )
+        };
+        let (x_scale, y_scale) = scales(state.visible_range(full_range));
+        let selection = handle_zoom_input(ui, &response, &self.zoom, &x_scale, &y_scale, &mut state);
+        let visible_range = state.visible_range(full_range);
+        let (x_scale, y_scale) = scales(visible_range);
+
+        // Publish the hovered x position to the link group and follow the group's
+        // cursor. The group places category `i` at `x = i`, like a line chart's
+        // points, which here is the center of its bar.
+        let pointer_x = response
+            .hover_pos()
+            .filter(|p| chart_rect.contains(*p))
+            .map(|p| x_scale.pixel_to_value(p.x) - 0.5);
+        let linked_cursor = self.link.and_then(|link| {
+            link.set_cursor(ui.ctx(), id, pointer_x);
+            link.cursor(ui.ctx()).map(|mut cursor| {
+                cursor.x += 0.5;
+                cursor
+            })
+        });
+
+        // Keep zoomed-in bars inside the plot area
+        let clip_rect = if state.is_zoomed() { chart_rect } else { rect };
+        let plot_painter = painter.with_clip_rect(clip_rect);
+
+        // Annotations under the pointer
+        let annotation_under = |pos: Pos2| annotation_at(&self.annotations, pos, chart_rect, (&x_scale, &y_scale));
+        let hovered_annotation = response.hover_pos().and_then(annotation_under);
+        let clicked_annotation = if response.clicked() {
+            response.interact_pointer_pos().and_then(annotation_under)
+        } else {
+            None
+        };
+        let annotation_painter = painter.with_clip_rect(chart_rect);
+
+        // Build bar elements, reusing last frame's when nothing they depend on changed
+        let style = self.style();
+        let bars_key = cache_key((
+            new_data_hash,
+            self.data.len(),
+            rect_bits(chart_rect),
+            range_bits(visible_range),
+            self.style_key(&style),
+        ));
+        let bars = cached(&mut memory.bars, bars_key, || self.build_bar_elements(&x_scale, &y_scale));
+
+        let plugin_ctx = PluginContext {
+            painter: &painter,
+            chart_id: id,
+            rect,
+            chart_area: chart_rect,
+            x_scale: Some(&x_scale),
+            y_scale: Some(&y_scale),
+            elements: ChartElements::None,
+            theme: &self.theme,
+            response: &response,
+            hovered: memory.hovered_index.map(|i| (0, i)),
+            tooltip: None,
+        };
+        for plugin in &plugins {
+            plugin.before_draw(&plugin_ctx);
+        }
 
         // Draw grid
         if self.show_grid {
-            self.draw_grid(&painter, chart_rect);
+            self.draw_grid(&painter, chart_rect, &y_scale);
         }
 
-        // Draw bars with animation
-        for (i, bar) in bars.iter().enumerate() {
-            let mut bar = bar.clone();
-
-            // Apply hover effect
-            if memory.hovered_index == Some(i) {
-                bar.fill_color = lighten(bar.fill_color, 0.15);
-            }
+        // Advance hover transitions
+        memory.hover.update(ui.ctx(), id, memory.hovered_index, &style.hover);
 
-            bar.draw(&painter, progress);
-        }
+        draw_annotations(
+            &annotation_painter,
+            &self.annotations,
+            AnnotationLayer::Behind,
+            chart_rect,
+            (&x_scale, &y_scale),
+            hovered_annotation,
+        );
 
-        // Draw value labels on bars
-        if self.show_values && progress > 0.5 {
+        // Draw bars with animation, tessellated again only while animating
+        // or when the bars, their visibility or hover change
+        let bar_shapes = || {
+            let mut shapes = Vec::new();
             for (i, bar) in bars.iter().enumerate() {
-                if let Some(&value) = self.data.get(i) {
-                    let animated_rect = bar.animated_rect(progress);
-                    let label_pos = Pos2::new(
-                        animated_rect.center().x,
-                        animated_rect.min.y - 5.0,
-                    );
-                    painter.text(
-                        label_pos,
-                        egui::Align2::CENTER_BOTTOM,
-                        format_value(value),
-                        egui::FontId::proportional(10.0),
-                        self.theme.text_color,
-                    );
+                let mut bar = bar.clone();
+                bar.fill_color = animation.animate_color(bar.fill_color, i);
+                bar.border_color = animation.animate_color(bar.border_color, i);
+                if self.animation.animates(AnimatedProperty::X) {
+                    bar.width *= animation.property_progress(AnimatedProperty::X, i);
                 }
+
+                // Apply hover effect
+                let hover = memory.hover.amount(i);
+                if hover > 0.0 {
+                    bar.fill_color = style.hover.fill_color(bar.fill_color, hover);
+                    bar.border_color = style.hover.border_color(bar.border_color, hover);
+                    bar.border_width = style.hover.border_width(bar.border_width, hover);
+                }
+
+                shapes.extend(bar.shapes(y_progress[i] * visibility[i]));
             }
+            shapes
+        };
+        let atlas = atlas_key(ui.ctx());
+        if animation.is_finished() {
+            let visibility_bits: Vec<u32> = visibility.iter().map(|v| v.to_bits()).collect();
+            let key = cache_key((bars_key, visibility_bits, memory.hover.key(), atlas));
+            let mesh = cached(&mut memory.bars_mesh, key, || tessellate(ui.ctx(), bar_shapes()));
+            plot_painter.add(Shape::Mesh(mesh));
+        } else {
+            plot_painter.extend(bar_shapes());
+        }
+
+        draw_annotations(
+            &annotation_painter,
+            &self.annotations,
+            AnnotationLayer::Front,
+            chart_rect,
+            (&x_scale, &y_scale),
+            hovered_annotation,
+        );
+
+        // Draw value labels on bars
+        if let Some(data_labels) = self.data_labels.as_ref().filter(|_| progress > 0.5) {
+            let requests: Vec<LabelRequest> = bars
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| visibility[*i] > 0.0)
+                .filter_map(|(i, bar)| {
+                    let context = LabelContext {
+                        dataset: 0,
+                        index: i,
+                        value: self.value(i)?,
+                        fraction: None,
+                    };
+                    Some(LabelRequest {
+                        target: LabelTarget::Bar {
+                            rect: bar.animated_rect(y_progress[i] * visibility[i]),
+                            upward: bar.y <= bar.base,
+                        },
+                        text: data_labels.text(&context, format_value)?,
+                        color: bar.fill_color,
+                        opacity: visibility[i],
+                    })
+                })
+                .collect();
+            draw_data_labels(&plot_painter, data_labels, &requests, chart_rect, &self.theme);
+        }
+
+        let plugin_ctx = PluginContext {
+            elements: ChartElements::Bars(&bars),
+            ..plugin_ctx
+        };
+        for plugin in &plugins {
+            plugin.after_datasets_draw(&plugin_ctx);
         }
 
         // Draw axes (on top of bars)
@@ -281,101 +530,140 @@ impl BarChart {
             self.draw_axes(&painter, chart_rect);
         }
 
-        // Draw labels
-        self.draw_labels(&painter, chart_rect, &bars);
-
-        // Handle interaction
-        let interaction = evaluate_interaction(&bars, &response);
-        memory.hovered_index = interaction.hovered_index;
-
-        // Draw tooltip if hovering
-        if self.tooltip_enabled {
-            if let Some(idx) = memory.hovered_index {
-                if idx < self.data.len() {
-                    let bar = &bars[idx];
-                    let content = TooltipContent {
-                        title: None,
-                        label: self
-                            .labels
-                            .get(idx)
-                            .cloned()
-                            .unwrap_or_else(|| format!("Item {}", idx + 1)),
-                        value: format_value(self.data[idx]),
-                        color: bar.fill_color,
-                    };
+        // Draw axis labels, laid out again only when they change
+        let labels_key = cache_key((
+            compute_labels_hash(&self.labels),
+            bars_key,
+            self.show_axes,
+            self.theme.text_color,
+            atlas,
+        ));
+        let labels = cached(&mut memory.axis_labels, labels_key, || {
+            self.layout_axis_labels(&painter, chart_rect, &y_scale, &bars)
+        });
+        for (pos, galley) in labels.iter() {
+            painter.galley(*pos, galley.clone(), self.theme.text_color);
+        }
+
+        if let Some(selection) = selection {
+            draw_selection(&painter, selection, self.theme.axis_color);
+        }
 
-                    let tooltip_size = measure_tooltip_size(&painter, &content, &self.theme.tooltip);
-                    let anchor = Pos2::new(bar.x, bar.y.min(bar.base));
-                    let tooltip_pos = calculate_tooltip_position(anchor, tooltip_size, rect);
+        // Handle interaction (hidden or scrolled-away bars can't be hovered or clicked)
+        let mut interaction = evaluate_interaction(&bars, &response);
+        let in_view = response.hover_pos().is_some_and(|p| clip_rect.contains(p));
+        interaction.hovered_index = interaction.hovered_index.filter(|i| in_view && shown[*i]);
+        interaction.clicked_index = interaction.clicked_index.filter(|i| in_view && shown[*i]);
 
-                    draw_tooltip(&painter, &content, tooltip_pos, &self.theme.tooltip);
-                }
+        // Follow a cursor hovering another chart of the link group
+        let linked_hover = linked_cursor.filter(|c| c.source != id).and_then(|c| {
+            let i = c.x.floor();
+            (i >= 0.0 && i < self.data.len() as f64 && shown[i as usize]).then_some(i as usize)
+        });
+        memory.hovered_index = interaction.hovered_index.or(linked_hover);
+
+        if let Some(crosshair) = &self.crosshair {
+            let pointer = response.hover_pos().filter(|p| chart_rect.contains(*p));
+            let target = self.crosshair_target(crosshair, pointer, &x_scale, &y_scale, &shown).or_else(|| {
+                // Only the vertical line follows another chart's cursor
+                let cursor = linked_cursor.filter(|c| c.source != id)?;
+                let index = category_index(cursor.x, self.data.len())?;
+                let x = if crosshair.snap_to_index { index as f64 + 0.5 } else { cursor.x };
+                Some(CrosshairTarget {
+                    x: Some((x, self.category_label(index))),
+                    y: None,
+                })
+            });
+            if let Some(target) = target {
+                draw_crosshair(&painter, chart_rect, &x_scale, &y_scale, crosshair, &target, &self.theme);
+            }
+        } else if let Some(cursor) = linked_cursor {
+            draw_cursor_line(&painter, chart_rect, x_scale.value_to_pixel(cursor.x), self.theme.axis_color);
+        }
+
+        let plugin_ctx = PluginContext {
+            hovered: memory.hovered_index.map(|i| (0, i)),
+            ..plugin_ctx
+        };
+        for event in ChartEvent::from_response(&response) {
+            for plugin in &plugins {
+                plugin.on_event(&plugin_ctx, &event);
             }
         }
 
+        // Tooltip for the hovered bar
+        let tooltip = memory.hovered_index.filter(|_| self.tooltip_enabled).and_then(|idx| {
+            let (bar, value) = (bars.get(idx)?, self.value(idx)?);
+            Some(ChartTooltip {
+                content: TooltipContent {
+                    title: None,
+                    label: self
+                        .labels
+                        .get(idx)
+                        .cloned()
+                        .unwrap_or_else(|| format!("Item {}", idx + 1)),
+                    value: format_value(value),
+                    color: bar.fill_color,
+                },
+                anchor: Pos2::new(bar.x, bar.y.min(bar.base)),
+            })
+        });
+
+        let plugin_ctx = PluginContext {
+            tooltip: tooltip.as_ref(),
+            ..plugin_ctx
+        };
+        for plugin in &plugins {
+            plugin.after_draw(&plugin_ctx);
+        }
+        TooltipPlugin.after_draw(&plugin_ctx);
+
         // Store updated memory
         ui.ctx().data_mut(|d| {
             d.insert_temp(id, memory.clone());
         });
+        animation.store(ui.ctx(), id);
+        if let Some(link) = &self.link {
+            link.push(ui.ctx(), id, &state);
+        }
+        state.store(ui.ctx(), id);
 
         BarChartResponse {
             response,
-            hovered: memory.hovered_index,
+            hovered: interaction.hovered_index,
             clicked: interaction.clicked_index,
+            hovered_annotation,
+            clicked_annotation,
+            visible_range,
         }
     }
 
     /// Build bar elements from data
-    fn build_bar_elements(&self, chart_rect: Rect) -> Vec<BarElement> {
+    fn build_bar_elements(&self, x_scale: &LinearScale, y_scale: &LinearScale) -> Vec<BarElement> {
         if self.data.is_empty() {
             return Vec::new();
         }
 
-        let style = self.bar_style.clone().unwrap_or(self.theme.bar_style.clone());
+        let style = self.style();
         let colors: Vec<Color32> = if self.colors.is_empty() {
             style.fill_colors.clone()
         } else {
             self.colors.iter().map(|c| c.to_color32()).collect()
         };
 
-        let n = self.data.len();
-        let total_width = chart_rect.width();
-        let category_width = total_width / n as f32 * style.category_percentage;
+        let category_width =
+            (x_scale.value_to_pixel(1.0) - x_scale.value_to_pixel(0.0)).abs() * style.category_percentage;
         let bar_width = category_width * style.bar_percentage;
 
-        // Calculate y scale
-        let max_val = self
-            .data
-            .iter()
-            .cloned()
-            .fold(f64::NEG_INFINITY, f64::max)
-            .max(0.0);
-        let min_val = self
-            .data
-            .iter()
-            .cloned()
-            .fold(f64::INFINITY, f64::min)
-            .min(0.0);
-
-        // Add some padding to max value for visual breathing room
-        let padded_max = if max_val > 0.0 { max_val * 1.1 } else { max_val };
-
-        let y_range = padded_max - min_val;
-        let y_scale = if y_range > 0.0 {
-            chart_rect.height() as f64 / y_range
-        } else {
-            1.0
-        };
-
-        let baseline_y = chart_rect.max.y - (-min_val * y_scale) as f32;
+        // Bars grow from zero, or from the nearest scale edge when zero is off-scale
+        let baseline_y = y_scale.value_to_pixel(0.0_f64.clamp(y_scale.min, y_scale.max.max(y_scale.min)));
 
-        self.data
-            .iter()
+        values(&self.data)
             .enumerate()
-            .map(|(i, &val)| {
-                let x = chart_rect.min.x + (i as f32 + 0.5) * total_width / n as f32;
-                let height = (val * y_scale) as f32;
-                let y = baseline_y - height;
+            .map(|(i, val)| {
+                let x = x_scale.value_to_pixel(i as f64 + 0.5);
+                // Missing values get an empty bar, so indices still match the data
+                let y = if is_missing(val) { baseline_y } else { y_scale.value_to_pixel(val) };
 
                 let color = colors.get(i % colors.len()).cloned().unwrap_or(Color32::GRAY);
 
@@ -389,26 +677,70 @@ impl BarChart {
             .collect()
     }
 
-    /// Draw grid lines
-    fn draw_grid(&self, painter: &Painter, chart_rect: Rect) {
-        // Calculate nice tick values
-        let max_val = self.data.iter().cloned().fold(0.0_f64, f64::max) * 1.1;
-        let ticks = nice_ticks(0.0, max_val, 5);
-
-        let y_scale = if max_val > 0.0 {
-            chart_rect.height() as f64 / max_val
+    /// Crosshair position under the pointer, snapped to the bar's center and value if configured
+    fn crosshair_target(
+        &self,
+        crosshair: &Crosshair,
+        pointer: Option<Pos2>,
+        x_scale: &LinearScale,
+        y_scale: &LinearScale,
+        shown: &[bool],
+    ) -> Option<CrosshairTarget> {
+        let pos = pointer?;
+        let x = x_scale.pixel_to_value(pos.x);
+        let index = category_index(x, self.data.len())?;
+
+        let (x, y) = if crosshair.snap_to_index {
+            let y = Some(self.data.y(index))
+                .filter(|_| shown[index])
+                .unwrap_or_else(|| y_scale.pixel_to_value(pos.y));
+            (index as f64 + 0.5, y)
         } else {
-            1.0
+            (x, y_scale.pixel_to_value(pos.y))
         };
 
-        for tick in &ticks {
-            let y = chart_rect.max.y - (*tick * y_scale) as f32;
-            if y >= chart_rect.min.y && y <= chart_rect.max.y {
-                painter.line_segment(
-                    [Pos2::new(chart_rect.min.x, y), Pos2::new(chart_rect.max.x, y)],
-                    Stroke::new(1.0, self.theme.grid_color),
-                );
-            }
+        Some(CrosshairTarget {
+            x: Some((x, self.category_label(index))),
+            y: Some((y, format_value(y))),
+        })
+    }
+
+    /// Value of the bar at `index`, unless it is missing
+    fn value(&self, index: usize) -> Option<f64> {
+        (index < self.data.len()).then(|| self.data.y(index)).filter(|v| !is_missing(*v))
+    }
+
+    /// Label of the category at `index`
+    fn category_label(&self, index: usize) -> String {
+        self.labels.get(index).cloned().unwrap_or_else(|| format!("{}", index + 1))
+    }
+
+    /// Hash of the style settings and colors bars are built from
+    fn style_key(&self, style: &BarStyle) -> u64 {
+        let hover = &style.hover;
+        cache_key((
+            &style.fill_colors,
+            self.colors.iter().map(|c| c.to_color32()).collect::<Vec<_>>(),
+            (style.border_color, style.border_radius),
+            [style.border_width, style.bar_percentage, style.category_percentage].map(f32::to_bits),
+            (hover.background_color, hover.border_color),
+            hover.border_width.map(f32::to_bits),
+        ))
+    }
+
+    /// Explicit bar style, or the theme's
+    fn style(&self) -> BarStyle {
+        self.bar_style.clone().unwrap_or_else(|| self.theme.bar_style.clone())
+    }
+
+    /// Draw grid lines
+    fn draw_grid(&self, painter: &Painter, chart_rect: Rect, y_scale: &LinearScale) {
+        for tick in y_scale.ticks(5) {
+            let y = y_scale.value_to_pixel(tick);
+            painter.line_segment(
+                [Pos2::new(chart_rect.min.x, y), Pos2::new(chart_rect.max.x, y)],
+                Stroke::new(1.0, self.theme.grid_color),
+            );
         }
     }
 
@@ -421,55 +753,54 @@ impl BarChart {
 
         // X axis
         painter.line_segment([chart_rect.left_bottom(), chart_rect.right_bottom()], stroke);
+    }
 
-        // Y axis labels
-        let max_val = self.data.iter().cloned().fold(0.0_f64, f64::max) * 1.1;
-        let ticks = nice_ticks(0.0, max_val, 5);
-
-        let y_scale = if max_val > 0.0 {
-            chart_rect.height() as f64 / max_val
-        } else {
-            1.0
+    /// Lay out the y axis labels (with axes shown) and the labels of the
+    /// categories inside the visible x range. Returns each galley with its
+    /// top left corner.
+    fn layout_axis_labels(
+        &self,
+        painter: &Painter,
+        chart_rect: Rect,
+        y_scale: &LinearScale,
+        bars: &[BarElement],
+    ) -> PlacedGalleys {
+        let font = FontId::proportional(11.0);
+        let color = self.theme.text_color;
+        let mut labels = Vec::new();
+        let mut place = |text: String, anchor: Pos2, align: Align2| {
+            let galley = painter.layout_no_wrap(text, font.clone(), color);
+            labels.push((align.anchor_size(anchor, galley.size()).min, galley));
         };
 
-        for tick in &ticks {
-            let y = chart_rect.max.y - (*tick * y_scale) as f32;
-            if y >= chart_rect.min.y && y <= chart_rect.max.y {
-                let text = format_axis_value(*tick);
-
-                painter.text(
-                    Pos2::new(chart_rect.min.x - 8.0, y),
-                    egui::Align2::RIGHT_CENTER,
-                    text,
-                    egui::FontId::proportional(11.0),
-                    self.theme.text_color,
-                );
+        if self.show_axes {
+            for tick in y_scale.ticks(5) {
+                let anchor = Pos2::new(chart_rect.min.x - 8.0, y_scale.value_to_pixel(tick));
+                place(format_axis_value(tick), anchor, Align2::RIGHT_CENTER);
             }
         }
-    }
 
-    /// Draw category labels
-    fn draw_labels(&self, painter: &Painter, chart_rect: Rect, bars: &[BarElement]) {
         for (i, bar) in bars.iter().enumerate() {
-            let label = self.labels.get(i).cloned().unwrap_or_else(|| format!("{}", i + 1));
-
-            painter.text(
-                Pos2::new(bar.x, chart_rect.max.y + 12.0),
-                egui::Align2::CENTER_TOP,
-                label,
-                egui::FontId::proportional(11.0),
-                self.theme.text_color,
-            );
+            if bar.x < chart_rect.min.x || bar.x > chart_rect.max.x {
+                continue;
+            }
+            place(self.category_label(i), Pos2::new(bar.x, chart_rect.max.y + 12.0), Align2::CENTER_TOP);
         }
+        labels
     }
 }
 
-impl Widget for BarChart {
+impl Widget for BarChart<'_> {
     fn ui(self, ui: &mut Ui) -> Response {
         self.show(ui).response
     }
 }
 
+/// Category whose band `i..i + 1` contains `x`, clamped to the data
+fn category_index(x: f64, n: usize) -> Option<usize> {
+    (n > 0).then(|| x.floor().clamp(0.0, (n - 1) as f64) as usize)
+}
+
 /// Format a value for display in tooltip
 fn format_value(value: f64) -> String {
     if value.abs() >= 1_000_000.0 {
@@ -0,0 +1,102 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use egui::epaint::Tessellator;
+use egui::{Context, Galley, Mesh, Pos2, Rect, Shape};
+
+use crate::zoom::VisibleRange;
+
+/// A value kept in chart memory between frames, along with the key of what
+/// it was built from
+#[derive(Clone, Debug)]
+pub(crate) struct Cached<T> {
+    key: u64,
+    value: Arc<T>,
+}
+
+/// Laid out texts, each with the position of its top left corner
+pub(crate) type PlacedGalleys = Vec<(Pos2, Arc<Galley>)>;
+
+/// The value in `slot` if it was built from `key`, otherwise a new one from
+/// `build`, stored for the next frame
+pub(crate) fn cached<T>(slot: &mut Option<Cached<T>>, key: u64, build: impl FnOnce() -> T) -> Arc<T> {
+    match slot {
+        Some(cached) if cached.key == key => cached.value.clone(),
+        _ => {
+            let value = Arc::new(build());
+            *slot = Some(Cached {
+                key,
+                value: value.clone(),
+            });
+            value
+        }
+    }
+}
+
+/// Hash of everything a cached value depends on
+pub(crate) fn cache_key(parts: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    parts.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Tessellate shapes into one mesh that can be cached and drawn again as is
+///
+/// Small circles use discs prepared in the font atlas, so the mesh should be
+/// keyed on [`atlas_key`] too.
+pub(crate) fn tessellate(ctx: &Context, shapes: impl IntoIterator<Item = Shape>) -> Mesh {
+    let options = ctx.tessellation_options(|options| *options);
+    let (font_size, discs) = ctx.fonts(|fonts| (fonts.font_image_size(), fonts.texture_atlas().lock().prepared_discs()));
+    let mut tessellator = Tessellator::new(ctx.pixels_per_point(), options, font_size, discs);
+    let mut mesh = Mesh::default();
+    for shape in shapes {
+        tessellator.tessellate_shape(shape, &mut mesh);
+    }
+    mesh
+}
+
+/// What tessellated meshes depend on besides their shapes: the scale and
+/// the font atlas their discs were taken from
+pub(crate) fn atlas_key(ctx: &Context) -> (u32, [usize; 2]) {
+    (ctx.pixels_per_point().to_bits(), ctx.fonts(|fonts| fonts.font_image_size()))
+}
+
+/// Hashable bits of a rect
+pub(crate) fn rect_bits(rect: Rect) -> [u32; 4] {
+    [rect.min.x, rect.min.y, rect.max.x, rect.max.y].map(f32::to_bits)
+}
+
+/// Hashable bits of a visible data range
+pub(crate) fn range_bits(range: VisibleRange) -> [u64; 4] {
+    [range.x_min, range.x_max, range.y_min, range.y_max].map(f64::to_bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebuilds_only_when_key_changes() {
+        let mut slot = None;
+        let mut builds = 0;
+        let mut get = |key: u64| {
+            *cached(&mut slot, key, || {
+                builds += 1;
+                key * 10
+            })
+        };
+        assert_eq!(get(1), 10);
+        assert_eq!(get(1), 10);
+        assert_eq!(get(2), 20);
+        assert_eq!(builds, 2);
+    }
+
+    #[test]
+    fn test_cache_key() {
+        assert_eq!(cache_key((1, "a")), cache_key((1, "a")));
+        assert_ne!(cache_key((1, "a")), cache_key((2, "a")));
+        let rect = Rect::from_min_max(egui::Pos2::ZERO, egui::Pos2::new(10.0, 5.0));
+        assert_ne!(rect_bits(rect), rect_bits(rect.translate(egui::Vec2::X)));
+    }
+}
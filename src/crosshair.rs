@@ -0,0 +1,137 @@
+use egui::{Align2, Color32, FontId, Painter, Pos2, Rect, Stroke, StrokeKind, Vec2};
+
+use crate::scale::LinearScale;
+use crate::theme::ChartTheme;
+use crate::tooltip::TooltipConfig;
+
+/// Padding around crosshair axis labels
+const LABEL_PADDING: Vec2 = Vec2::new(4.0, 2.0);
+
+/// Crosshair lines following the cursor over cartesian charts
+///
+/// ```rust,ignore
+/// LineChart::new()
+///     .data(data)
+///     .crosshair(Crosshair::new().horizontal(false).snap_to_index(true))
+///     .show(ui);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Crosshair {
+    /// Draw the vertical line
+    pub vertical: bool,
+    /// Draw the horizontal line
+    pub horizontal: bool,
+    /// Snap to the nearest data index (and its value) instead of following the pointer
+    pub snap_to_index: bool,
+    /// Show the value under the cursor at the axis edges
+    pub labels: bool,
+    /// Line color, defaults to the theme's axis color
+    pub color: Option<Color32>,
+    pub width: f32,
+}
+
+impl Default for Crosshair {
+    fn default() -> Self {
+        Self {
+            vertical: true,
+            horizontal: true,
+            snap_to_index: false,
+            labels: true,
+            color: None,
+            width: 1.0,
+        }
+    }
+}
+
+impl Crosshair {
+    /// Vertical and horizontal lines with axis labels
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show/hide the vertical line
+    pub fn vertical(mut self, show: bool) -> Self {
+        self.vertical = show;
+        self
+    }
+
+    /// Show/hide the horizontal line
+    pub fn horizontal(mut self, show: bool) -> Self {
+        self.horizontal = show;
+        self
+    }
+
+    /// Snap to the nearest data index
+    pub fn snap_to_index(mut self, snap: bool) -> Self {
+        self.snap_to_index = snap;
+        self
+    }
+
+    /// Show/hide axis-edge value labels
+    pub fn labels(mut self, show: bool) -> Self {
+        self.labels = show;
+        self
+    }
+
+    /// Set line color
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set line width
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+}
+
+/// Where to draw the crosshair lines, in data units, with their axis labels
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct CrosshairTarget {
+    pub x: Option<(f64, String)>,
+    pub y: Option<(f64, String)>,
+}
+
+/// Draw the crosshair lines and axis-edge labels
+pub(crate) fn draw_crosshair(
+    painter: &Painter,
+    chart_rect: Rect,
+    x_scale: &LinearScale,
+    y_scale: &LinearScale,
+    crosshair: &Crosshair,
+    target: &CrosshairTarget,
+    theme: &ChartTheme,
+) {
+    let stroke = Stroke::new(crosshair.width, crosshair.color.unwrap_or(theme.axis_color));
+    let tooltip = &theme.tooltip;
+    let font_id = FontId::proportional(11.0);
+
+    if let Some((value, label)) = target.x.as_ref().filter(|_| crosshair.vertical) {
+        let x = x_scale.value_to_pixel(*value);
+        if x >= chart_rect.min.x && x <= chart_rect.max.x {
+            painter.line_segment([Pos2::new(x, chart_rect.min.y), Pos2::new(x, chart_rect.max.y)], stroke);
+            if crosshair.labels {
+                draw_label(painter, Pos2::new(x, chart_rect.max.y + 2.0), Align2::CENTER_TOP, label, &font_id, tooltip);
+            }
+        }
+    }
+
+    if let Some((value, label)) = target.y.as_ref().filter(|_| crosshair.horizontal) {
+        let y = y_scale.value_to_pixel(*value);
+        if y >= chart_rect.min.y && y <= chart_rect.max.y {
+            painter.line_segment([Pos2::new(chart_rect.min.x, y), Pos2::new(chart_rect.max.x, y)], stroke);
+            if crosshair.labels {
+                draw_label(painter, Pos2::new(chart_rect.min.x - 2.0, y), Align2::RIGHT_CENTER, label, &font_id, tooltip);
+            }
+        }
+    }
+}
+
+/// Axis label on a tooltip-colored background, covering the tick labels beneath it
+fn draw_label(painter: &Painter, anchor: Pos2, align: Align2, text: &str, font_id: &FontId, tooltip: &TooltipConfig) {
+    let galley = painter.layout_no_wrap(text.to_string(), font_id.clone(), tooltip.text_color);
+    let rect = align.anchor_size(anchor, galley.size() + LABEL_PADDING * 2.0);
+    painter.rect(rect, 2.0, tooltip.background_color, Stroke::NONE, StrokeKind::Inside);
+    painter.galley(rect.min + LABEL_PADDING, galley, tooltip.text_color);
+}
@@ -0,0 +1,402 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::cache::cache_key;
+
+/// Values a chart reads in place, without copying them every frame
+///
+/// Implemented for slices, vectors, arrays and deques of values or
+/// `(x, y)` pairs, and for [`DataStream`](crate::DataStream). Implement it
+/// for your own columns and structs to pass them by reference with
+/// `.data_source()`.
+///
+/// A `NaN` value marks a missing point, as does `None` in slices and
+/// vectors of `Option<f64>`: lines break there and bars are left out.
+///
+/// Charts detect changes by hashing every value each frame unless
+/// [`ChartData::version`] reports a counter that changes with the data.
+///
+/// ```rust,ignore
+/// struct Samples {
+///     readings: Vec<Reading>,
+///     version: u64,
+/// }
+///
+/// impl ChartData for Samples {
+///     fn len(&self) -> usize {
+///         self.readings.len()
+///     }
+///     fn y(&self, index: usize) -> f64 {
+///         self.readings[index].celsius
+///     }
+///     fn version(&self) -> Option<u64> {
+///         Some(self.version)
+///     }
+/// }
+///
+/// LineChart::new().data_source(&samples).show(ui);
+/// ```
+pub trait ChartData {
+    /// Number of points
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Value of the point at `index`, `NaN` when it is missing
+    fn y(&self, index: usize) -> f64;
+
+    /// Position of the point at `index` along the x axis (default: the index)
+    ///
+    /// Line charts expect positions not to decrease; bar charts always place
+    /// categories by index.
+    fn x(&self, index: usize) -> f64 {
+        index as f64
+    }
+
+    /// A number that changes whenever the data does, or `None` to detect
+    /// changes by hashing the values
+    fn version(&self) -> Option<u64> {
+        None
+    }
+}
+
+impl<T: ChartData + ?Sized> ChartData for &T {
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+    fn y(&self, index: usize) -> f64 {
+        (**self).y(index)
+    }
+    fn x(&self, index: usize) -> f64 {
+        (**self).x(index)
+    }
+    fn version(&self) -> Option<u64> {
+        (**self).version()
+    }
+}
+
+impl ChartData for [f64] {
+    fn len(&self) -> usize {
+        <[f64]>::len(self)
+    }
+    fn y(&self, index: usize) -> f64 {
+        self[index]
+    }
+}
+
+impl ChartData for [(f64, f64)] {
+    fn len(&self) -> usize {
+        <[(f64, f64)]>::len(self)
+    }
+    fn y(&self, index: usize) -> f64 {
+        self[index].1
+    }
+    fn x(&self, index: usize) -> f64 {
+        self[index].0
+    }
+}
+
+impl ChartData for [Option<f64>] {
+    fn len(&self) -> usize {
+        <[Option<f64>]>::len(self)
+    }
+    fn y(&self, index: usize) -> f64 {
+        self[index].unwrap_or(f64::NAN)
+    }
+}
+
+impl ChartData for [(f64, Option<f64>)] {
+    fn len(&self) -> usize {
+        <[(f64, Option<f64>)]>::len(self)
+    }
+    fn y(&self, index: usize) -> f64 {
+        self[index].1.unwrap_or(f64::NAN)
+    }
+    fn x(&self, index: usize) -> f64 {
+        self[index].0
+    }
+}
+
+/// Implement [`ChartData`] by delegating to a slice
+macro_rules! impl_via_slice {
+    ($($ty:ty),* $(,)?) => {$(
+        impl ChartData for $ty {
+            fn len(&self) -> usize {
+                self.as_slice().len()
+            }
+            fn y(&self, index: usize) -> f64 {
+                ChartData::y(self.as_slice(), index)
+            }
+            fn x(&self, index: usize) -> f64 {
+                ChartData::x(self.as_slice(), index)
+            }
+        }
+    )*};
+}
+
+impl_via_slice!(Vec<f64>, Vec<(f64, f64)>, Vec<Option<f64>>, Vec<(f64, Option<f64>)>);
+
+impl<const N: usize> ChartData for [f64; N] {
+    fn len(&self) -> usize {
+        N
+    }
+    fn y(&self, index: usize) -> f64 {
+        self[index]
+    }
+}
+
+impl ChartData for VecDeque<f64> {
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+    fn y(&self, index: usize) -> f64 {
+        self[index]
+    }
+}
+
+/// Data together with a version number that changes with it, for sources
+/// that don't track one themselves
+///
+/// ```rust,ignore
+/// LineChart::new()
+///     .data_source(Versioned::new(&column[..], column_version))
+///     .show(ui);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Versioned<T> {
+    pub data: T,
+    pub version: u64,
+}
+
+impl<T: ChartData> Versioned<T> {
+    pub fn new(data: T, version: u64) -> Self {
+        Self { data, version }
+    }
+}
+
+impl<T: ChartData> ChartData for Versioned<T> {
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+    fn y(&self, index: usize) -> f64 {
+        self.data.y(index)
+    }
+    fn x(&self, index: usize) -> f64 {
+        self.data.x(index)
+    }
+    fn version(&self) -> Option<u64> {
+        Some(self.version)
+    }
+}
+
+/// Data of a chart: values given to `.data()`, or a source read in place
+#[derive(Clone)]
+pub(crate) enum Values<'a> {
+    Owned(Vec<f64>),
+    Source(Arc<dyn ChartData + 'a>),
+}
+
+impl Default for Values<'_> {
+    fn default() -> Self {
+        Self::Owned(Vec::new())
+    }
+}
+
+impl fmt::Debug for Values<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Owned(values) => values.fmt(f),
+            Self::Source(data) => f.debug_struct("ChartData").field("len", &data.len()).finish(),
+        }
+    }
+}
+
+impl ChartData for Values<'_> {
+    fn len(&self) -> usize {
+        match self {
+            Self::Owned(values) => values.len(),
+            Self::Source(data) => data.len(),
+        }
+    }
+    fn y(&self, index: usize) -> f64 {
+        match self {
+            Self::Owned(values) => values[index],
+            Self::Source(data) => data.y(index),
+        }
+    }
+    fn x(&self, index: usize) -> f64 {
+        match self {
+            Self::Owned(_) => index as f64,
+            Self::Source(data) => data.x(index),
+        }
+    }
+    fn version(&self) -> Option<u64> {
+        match self {
+            Self::Owned(_) => None,
+            Self::Source(data) => data.version(),
+        }
+    }
+}
+
+/// Values of `data`, in order
+pub(crate) fn values(data: &dyn ChartData) -> impl Iterator<Item = f64> + '_ {
+    (0..data.len()).map(|i| data.y(i))
+}
+
+/// Whether `value` marks a missing point: `NaN` (or `None`), or infinite
+pub(crate) fn is_missing(value: f64) -> bool {
+    !value.is_finite()
+}
+
+/// Indices into `kept`, the data indices drawn of a line, where the line
+/// breaks and starts a new segment: where points are missing in between.
+/// With `max_gap` it connects across a run of missing points unless the
+/// points either side of it are farther apart along x.
+pub(crate) fn line_breaks(data: &dyn ChartData, kept: &[usize], max_gap: Option<f64>) -> Vec<usize> {
+    (1..kept.len()).filter(|&k| breaks_between(data, kept[k - 1], kept[k], max_gap)).collect()
+}
+
+/// Whether the line from point `a` to point `b`, both present, crosses a gap
+/// it can't span. Decimation may have left out many points in between.
+fn breaks_between(data: &dyn ChartData, a: usize, b: usize, max_gap: Option<f64>) -> bool {
+    let mut before = a;
+    for i in a + 1..=b {
+        if is_missing(data.y(i)) {
+            continue;
+        }
+        if i > before + 1 && max_gap.is_none_or(|max_gap| data.x(i) - data.x(before) > max_gap) {
+            return true;
+        }
+        before = i;
+    }
+    false
+}
+
+/// Key that changes with `data`: its version when it has one (O(1)),
+/// otherwise a hash of every point
+pub(crate) fn data_key(data: &dyn ChartData) -> u64 {
+    match data.version() {
+        Some(version) => cache_key((data.len(), version)),
+        None => {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            data.len().hash(&mut hasher);
+            for i in 0..data.len() {
+                (data.x(i).to_bits(), data.y(i).to_bits()).hash(&mut hasher);
+            }
+            hasher.finish()
+        }
+    }
+}
+
+/// Index of the point whose x is nearest to `x`, for points sorted by x
+pub(crate) fn nearest_point(data: &dyn ChartData, x: f64) -> Option<usize> {
+    let len = data.len();
+    if len == 0 || x.is_nan() {
+        return None;
+    }
+    let after = partition_point(len, |i| data.x(i) < x);
+    if after == 0 {
+        return Some(0);
+    }
+    if after == len {
+        return Some(len - 1);
+    }
+    // Halfway between two points rounds up, like `x.round()` for indices
+    let before = after - 1;
+    Some(if x - data.x(before) < data.x(after) - x { before } else { after })
+}
+
+/// First index in `0..len` where `pred` turns false, for `pred` true on a prefix
+pub(crate) fn partition_point(len: usize, pred: impl Fn(usize) -> bool) -> usize {
+    let (mut lo, mut hi) = (0, len);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sources() {
+        let values = vec![3.0, 1.0, 2.0];
+        let pairs = [(0.5, 3.0), (1.5, 1.0)];
+        assert_eq!(ChartData::len(&values), 3);
+        assert_eq!(values.y(1), 1.0);
+        assert_eq!(values.x(2), 2.0);
+        assert_eq!(pairs[..].x(1), 1.5);
+        assert_eq!(Values::Source(Arc::new(&pairs[..])).y(0), 3.0);
+        assert_eq!([1.0, 2.0].version(), None);
+        assert_eq!(Versioned::new(&values[..], 7).version(), Some(7));
+
+        // `None` is missing, like `NaN`
+        let gaps = vec![Some(1.0), None];
+        assert!(is_missing(gaps.y(1)));
+        assert!(!is_missing(gaps.y(0)));
+        assert!(is_missing([(0.0, None)][..].y(0)));
+    }
+
+    #[test]
+    fn test_line_breaks() {
+        let data = vec![1.0, f64::NAN, 2.0, 3.0, 4.0, f64::NAN, f64::NAN, 5.0];
+        let kept: Vec<usize> = (0..data.len()).filter(|&i| !is_missing(data.y(i))).collect();
+        assert_eq!(kept, vec![0, 2, 3, 4, 7]);
+        // Breaks before points 2 and 7
+        assert_eq!(line_breaks(&data, &kept, None), vec![1, 4]);
+        // Spanning gaps of up to two steps
+        assert_eq!(line_breaks(&data, &kept, Some(2.0)), vec![4]);
+        assert!(line_breaks(&data, &kept, Some(f64::INFINITY)).is_empty());
+
+        // Decimated points far apart only break at gaps too long to span
+        let mut data: Vec<f64> = (0..1000).map(f64::from).collect();
+        data[500..502].fill(f64::NAN);
+        data[700..710].fill(f64::NAN);
+        let kept: Vec<usize> = (0..1000).step_by(50).filter(|&i| !is_missing(data.y(i))).collect();
+        assert_eq!(line_breaks(&data, &kept, Some(5.0)), vec![kept.iter().position(|&i| i == 750).unwrap()]);
+        assert_eq!(line_breaks(&data, &kept, None).len(), 2);
+        data[700..710].fill(0.0);
+        assert!(line_breaks(&data, &kept, Some(3.0)).is_empty());
+    }
+
+    #[test]
+    fn test_data_key() {
+        let a = vec![1.0, 2.0];
+        let b = vec![1.0, 3.0];
+        assert_eq!(data_key(&a), data_key(&a.clone()));
+        assert_ne!(data_key(&a), data_key(&b));
+
+        // Versioned data is only compared by version and length
+        assert_eq!(data_key(&Versioned::new(&a, 1)), data_key(&Versioned::new(&b, 1)));
+        assert_ne!(data_key(&Versioned::new(&a, 1)), data_key(&Versioned::new(&a, 2)));
+    }
+
+    #[test]
+    fn test_nearest_point() {
+        let pairs = vec![(0.0, 0.0), (10.0, 0.0), (11.0, 0.0)];
+        assert_eq!(nearest_point(&pairs, -5.0), Some(0));
+        assert_eq!(nearest_point(&pairs, 4.0), Some(0));
+        assert_eq!(nearest_point(&pairs, 6.0), Some(1));
+        assert_eq!(nearest_point(&pairs, 10.5), Some(2));
+        assert_eq!(nearest_point(&pairs, 99.0), Some(2));
+        assert_eq!(nearest_point(&Vec::<f64>::new(), 1.0), None);
+
+        // Points at their indices round like `x.round()`
+        let values = [0.0; 5];
+        assert_eq!(nearest_point(&values, 2.4), Some(2));
+        assert_eq!(nearest_point(&values, 2.5), Some(3));
+        assert_eq!(nearest_point(&values, 2.6), Some(3));
+        assert_eq!(nearest_point(&values, -3.0), Some(0));
+        assert_eq!(nearest_point(&values, 9.0), Some(4));
+    }
+}
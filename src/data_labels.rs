@@ -0,0 +1,578 @@
+use egui::epaint::TextShape;
+use egui::emath::Rot2;
+use egui::{Color32, FontId, Painter, Pos2, Rect, Shape, Stroke, Vec2};
+
+use crate::elements::ArcElement;
+use crate::helpers::color::contrast_text_color;
+use crate::theme::ChartTheme;
+
+/// Length of the leader line from a slice to a label placed outside it
+const LEADER_LENGTH: f32 = 12.0;
+
+/// Radial position of inside-slice labels for each anchor, as a fraction
+/// of the ring thickness
+const ARC_ANCHOR_RADIUS: [f32; 3] = [0.25, 0.5, 0.75];
+
+/// Point on the element a data label is attached to (Chart.js `anchor`)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LabelAnchor {
+    /// Base of a bar, bottom of a point, inner part of a slice
+    Start,
+    /// Middle of the element
+    Center,
+    /// Value end of a bar, top of a point, outer part of a slice
+    #[default]
+    End,
+}
+
+/// Side of the anchor the label is placed on (Chart.js `align`)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LabelAlign {
+    /// Centered on the anchor
+    Center,
+    /// Back towards the element's base
+    Start,
+    /// Away from the element's base (above a positive bar)
+    #[default]
+    End,
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// What to do with labels that overlap an earlier one
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LabelOverlap {
+    /// Draw every label
+    #[default]
+    Show,
+    /// Skip labels that overlap an already drawn one
+    Hide,
+    /// Move overlapping labels up until they fit, hiding them if they can't
+    Shift,
+}
+
+/// Data passed to a [`DataLabels`] formatter
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LabelContext {
+    pub dataset: usize,
+    pub index: usize,
+    pub value: f64,
+    /// Share of the total, for pie slices
+    pub fraction: Option<f64>,
+}
+
+/// Value labels drawn on bars, points and pie slices
+///
+/// Pie slices get their label inside when it fits and outside with a leader
+/// line when it doesn't; `align` only applies to bars and points.
+///
+/// ```rust,ignore
+/// BarChart::new()
+///     .data(data)
+///     .data_labels(
+///         DataLabels::new()
+///             .anchor(LabelAnchor::Center)
+///             .align(LabelAlign::Center)
+///             .background(Color32::from_black_alpha(160))
+///             .formatter(|c| (c.value > 0.0).then(|| format!("${:.0}", c.value))),
+///     )
+///     .show(ui);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DataLabels {
+    pub anchor: LabelAnchor,
+    pub align: LabelAlign,
+    /// Distance from the anchor in pixels
+    pub offset: f32,
+    /// Text rotation in radians, clockwise
+    pub rotation: f32,
+    /// Keep labels inside the chart area
+    pub clamp: bool,
+    pub font_size: f32,
+    /// Text color; defaults to the theme's text color, or a contrasting
+    /// color for labels drawn on top of their element
+    pub color: Option<Color32>,
+    /// Background box behind the text
+    pub background: Option<Color32>,
+    /// Space between the text and the edge of its box
+    pub padding: Vec2,
+    pub corner_radius: f32,
+    /// Leader line color for outside slice labels, defaults to the slice color
+    pub leader_color: Option<Color32>,
+    pub overlap: LabelOverlap,
+    /// Custom text; returning `None` hides the label
+    pub formatter: Option<fn(&LabelContext) -> Option<String>>,
+}
+
+impl Default for DataLabels {
+    fn default() -> Self {
+        Self {
+            anchor: LabelAnchor::End,
+            align: LabelAlign::End,
+            offset: 4.0,
+            rotation: 0.0,
+            clamp: false,
+            font_size: 10.0,
+            color: None,
+            background: None,
+            padding: Vec2::new(3.0, 1.0),
+            corner_radius: 2.0,
+            leader_color: None,
+            overlap: LabelOverlap::Show,
+            formatter: None,
+        }
+    }
+}
+
+impl DataLabels {
+    /// Labels just past the value end of each element, drawing all of them
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the anchor point on the element
+    pub fn anchor(mut self, anchor: LabelAnchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Set the side of the anchor the label sits on
+    pub fn align(mut self, align: LabelAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Set the distance from the anchor
+    pub fn offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Rotate the text, in degrees clockwise
+    pub fn rotation(mut self, degrees: f32) -> Self {
+        self.rotation = degrees.to_radians();
+        self
+    }
+
+    /// Keep labels inside the chart area
+    pub fn clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+
+    /// Set font size
+    pub fn font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    /// Set text color
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Draw a box behind the text
+    pub fn background(mut self, color: Color32) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Set leader line color
+    pub fn leader_color(mut self, color: Color32) -> Self {
+        self.leader_color = Some(color);
+        self
+    }
+
+    /// Set how overlapping labels are handled
+    pub fn overlap(mut self, overlap: LabelOverlap) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    /// Format label text from its value
+    pub fn formatter(mut self, formatter: fn(&LabelContext) -> Option<String>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
+    /// Label text, using `default` when there is no formatter
+    pub(crate) fn text(&self, context: &LabelContext, default: fn(f64) -> String) -> Option<String> {
+        match self.formatter {
+            Some(formatter) => formatter(context),
+            None => Some(default(context.value)),
+        }
+        .filter(|text| !text.is_empty())
+    }
+}
+
+/// Element a label is attached to, in screen space
+#[derive(Clone, Debug)]
+pub(crate) enum LabelTarget {
+    /// Bar rect as drawn; `upward` for bars growing up from their base
+    Bar { rect: Rect, upward: bool },
+    Point { pos: Pos2, radius: f32 },
+    Arc(ArcElement),
+}
+
+/// One label to draw
+#[derive(Clone, Debug)]
+pub(crate) struct LabelRequest {
+    pub target: LabelTarget,
+    pub text: String,
+    /// Fill color of the element
+    pub color: Color32,
+    pub opacity: f32,
+}
+
+/// Where a label ended up
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct PlacedLabel {
+    /// Center of the (unrotated) label box
+    pub center: Pos2,
+    /// Label box size including padding
+    pub size: Vec2,
+    /// Whether the label sits on top of its element
+    pub inside: bool,
+    /// Line from the element to the label
+    pub leader: Option<[Pos2; 2]>,
+}
+
+impl PlacedLabel {
+    /// Screen bounds of the rotated label box
+    fn bounds(&self, rotation: f32) -> Rect {
+        Rect::from_center_size(self.center, rotated_size(self.size, rotation))
+    }
+
+    fn translate(&mut self, delta: Vec2) {
+        self.center += delta;
+        if let Some(leader) = &mut self.leader {
+            leader[1] += delta;
+        }
+    }
+}
+
+/// Size of the bounding box of a `size` box rotated by `rotation`
+fn rotated_size(size: Vec2, rotation: f32) -> Vec2 {
+    let (sin, cos) = (rotation.sin().abs(), rotation.cos().abs());
+    Vec2::new(size.x * cos + size.y * sin, size.x * sin + size.y * cos)
+}
+
+/// Corners of the rotated label box
+fn rotated_corners(center: Pos2, size: Vec2, rotation: f32) -> [Pos2; 4] {
+    let rot = Rot2::from_angle(rotation);
+    let half = size / 2.0;
+    [
+        center + rot * Vec2::new(-half.x, -half.y),
+        center + rot * Vec2::new(half.x, -half.y),
+        center + rot * Vec2::new(half.x, half.y),
+        center + rot * Vec2::new(-half.x, half.y),
+    ]
+}
+
+/// Center of a box of `extent` placed `offset` away from `anchor` in direction `dir`
+fn beside(anchor: Pos2, dir: Vec2, offset: f32, extent: Vec2) -> Pos2 {
+    if dir == Vec2::ZERO {
+        return anchor;
+    }
+    let reach = dir.x.abs() * extent.x / 2.0 + dir.y.abs() * extent.y / 2.0;
+    anchor + dir * (offset + reach)
+}
+
+/// Place one label next to its element
+fn place(target: &LabelTarget, size: Vec2, config: &DataLabels) -> PlacedLabel {
+    let extent = rotated_size(size, config.rotation);
+
+    let (anchor, forward) = match target {
+        LabelTarget::Bar { rect, upward } => {
+            let forward = if *upward { -Vec2::Y } else { Vec2::Y };
+            let (base, end) = if *upward {
+                (rect.center_bottom(), rect.center_top())
+            } else {
+                (rect.center_top(), rect.center_bottom())
+            };
+            let anchor = match config.anchor {
+                LabelAnchor::Start => base,
+                LabelAnchor::Center => rect.center(),
+                LabelAnchor::End => end,
+            };
+            (anchor, forward)
+        }
+        LabelTarget::Point { pos, radius } => {
+            let anchor = match config.anchor {
+                LabelAnchor::Start => *pos + Vec2::Y * *radius,
+                LabelAnchor::Center => *pos,
+                LabelAnchor::End => *pos - Vec2::Y * *radius,
+            };
+            (anchor, -Vec2::Y)
+        }
+        LabelTarget::Arc(arc) => return place_on_arc(arc, size, config),
+    };
+
+    let dir = match config.align {
+        LabelAlign::Center => Vec2::ZERO,
+        LabelAlign::Start => -forward,
+        LabelAlign::End => forward,
+        LabelAlign::Top => -Vec2::Y,
+        LabelAlign::Bottom => Vec2::Y,
+        LabelAlign::Left => -Vec2::X,
+        LabelAlign::Right => Vec2::X,
+    };
+    let center = beside(anchor, dir, config.offset, extent);
+    let inside = match target {
+        LabelTarget::Bar { rect, .. } => rect.contains(center),
+        _ => false,
+    };
+
+    PlacedLabel {
+        center,
+        size,
+        inside,
+        leader: None,
+    }
+}
+
+/// Inside the slice when the whole box fits, otherwise outside with a leader line
+fn place_on_arc(arc: &ArcElement, size: Vec2, config: &DataLabels) -> PlacedLabel {
+    let fraction = ARC_ANCHOR_RADIUS[config.anchor as usize];
+    let radius = arc.inner_radius + (arc.outer_radius - arc.inner_radius) * fraction;
+    let center = arc.mid_point(radius);
+    if rotated_corners(center, size, config.rotation).iter().all(|&c| arc.contains(c)) {
+        return PlacedLabel {
+            center,
+            size,
+            inside: true,
+            leader: None,
+        };
+    }
+
+    let angle = arc.mid_angle();
+    let dir = Vec2::new(angle.cos(), angle.sin());
+    let edge = arc.mid_point(arc.outer_radius);
+    let end = edge + dir * (config.offset.max(0.0) + LEADER_LENGTH);
+    PlacedLabel {
+        center: beside(end, dir, 0.0, rotated_size(size, config.rotation)),
+        size,
+        inside: false,
+        leader: Some([edge, end]),
+    }
+}
+
+/// Place every label, resolving overlaps in order (earlier labels win)
+pub(crate) fn layout_labels(
+    labels: &[(LabelTarget, Vec2)],
+    config: &DataLabels,
+    area: Rect,
+) -> Vec<Option<PlacedLabel>> {
+    let mut taken: Vec<Rect> = Vec::new();
+    labels
+        .iter()
+        .map(|(target, size)| {
+            let mut label = place(target, *size, config);
+            if config.clamp {
+                let bounds = label.bounds(config.rotation);
+                label.translate(clamp_delta(bounds, area));
+            }
+
+            match config.overlap {
+                LabelOverlap::Show => {}
+                LabelOverlap::Hide => {
+                    if overlapping(label.bounds(config.rotation), &taken).is_some() {
+                        return None;
+                    }
+                }
+                LabelOverlap::Shift => {
+                    // Each shift clears one label, so this settles within `taken.len()` steps
+                    for _ in 0..=taken.len() {
+                        let bounds = label.bounds(config.rotation);
+                        let Some(other) = overlapping(bounds, &taken) else { break };
+                        label.translate(Vec2::Y * (other.min.y - 1.0 - bounds.max.y));
+                    }
+                    let bounds = label.bounds(config.rotation);
+                    if overlapping(bounds, &taken).is_some() || (config.clamp && bounds.min.y < area.min.y) {
+                        return None;
+                    }
+                }
+            }
+
+            taken.push(label.bounds(config.rotation));
+            Some(label)
+        })
+        .collect()
+}
+
+/// First rect in `taken` that `bounds` overlaps
+fn overlapping(bounds: Rect, taken: &[Rect]) -> Option<Rect> {
+    // Shrink slightly so labels that only touch don't count
+    let bounds = bounds.shrink(0.5);
+    taken.iter().copied().find(|other| other.intersects(bounds))
+}
+
+/// Translation moving `bounds` inside `area` (towards its top-left if it doesn't fit)
+fn clamp_delta(bounds: Rect, area: Rect) -> Vec2 {
+    let axis = |min: f32, max: f32, area_min: f32, area_max: f32| {
+        if min < area_min {
+            area_min - min
+        } else if max > area_max {
+            (area_max - max).max(area_min - min)
+        } else {
+            0.0
+        }
+    };
+    Vec2::new(
+        axis(bounds.min.x, bounds.max.x, area.min.x, area.max.x),
+        axis(bounds.min.y, bounds.max.y, area.min.y, area.max.y),
+    )
+}
+
+/// Lay out and draw data labels
+pub(crate) fn draw_data_labels(
+    painter: &Painter,
+    config: &DataLabels,
+    requests: &[LabelRequest],
+    area: Rect,
+    theme: &ChartTheme,
+) {
+    let font_id = FontId::proportional(config.font_size);
+    let galleys: Vec<_> = requests
+        .iter()
+        .map(|request| painter.layout_no_wrap(request.text.clone(), font_id.clone(), Color32::PLACEHOLDER))
+        .collect();
+    let sizes: Vec<_> = requests
+        .iter()
+        .zip(&galleys)
+        .map(|(request, galley)| (request.target.clone(), galley.size() + config.padding * 2.0))
+        .collect();
+
+    let placed = layout_labels(&sizes, config, area);
+    for ((request, galley), label) in requests.iter().zip(galleys).zip(placed) {
+        let Some(label) = label else { continue };
+
+        if let Some(leader) = label.leader {
+            let color = config.leader_color.unwrap_or(request.color);
+            painter.line_segment(leader, Stroke::new(1.0, color.gamma_multiply(request.opacity)));
+        }
+
+        if let Some(background) = config.background {
+            let corners = rotated_corners(label.center, label.size, config.rotation);
+            if config.rotation == 0.0 {
+                let rect = Rect::from_two_pos(corners[0], corners[2]);
+                painter.rect_filled(rect, config.corner_radius, background.gamma_multiply(request.opacity));
+            } else {
+                painter.add(Shape::convex_polygon(
+                    corners.to_vec(),
+                    background.gamma_multiply(request.opacity),
+                    Stroke::NONE,
+                ));
+            }
+        }
+
+        let color = config.color.unwrap_or_else(|| {
+            if label.inside && config.background.is_none() {
+                contrast_text_color(request.color)
+            } else {
+                theme.text_color
+            }
+        });
+        // Text is positioned by its top-left corner and rotated around it
+        let top_left = rotated_corners(label.center, label.size, config.rotation)[0];
+        let pos = top_left + Rot2::from_angle(config.rotation) * config.padding;
+        painter.add(
+            TextShape::new(pos, galley, color.gamma_multiply(request.opacity)).with_angle(config.rotation),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    fn bar(top: f32) -> LabelTarget {
+        LabelTarget::Bar {
+            rect: Rect::from_min_max(Pos2::new(0.0, top), Pos2::new(20.0, 100.0)),
+            upward: true,
+        }
+    }
+
+    #[test]
+    fn test_bar_end_label_sits_above() {
+        let config = DataLabels::new();
+        let placed = layout_labels(&[(bar(50.0), Vec2::new(16.0, 10.0))], &config, Rect::EVERYTHING);
+        let label = placed[0].unwrap();
+        assert_eq!(label.center, Pos2::new(10.0, 41.0));
+        assert!(!label.inside);
+
+        let config = config.anchor(LabelAnchor::Center).align(LabelAlign::Center);
+        let label = layout_labels(&[(bar(50.0), Vec2::new(16.0, 10.0))], &config, Rect::EVERYTHING)[0].unwrap();
+        assert_eq!(label.center, Pos2::new(10.0, 75.0));
+        assert!(label.inside);
+    }
+
+    #[test]
+    fn test_clamp_keeps_label_in_area() {
+        let area = Rect::from_min_max(Pos2::new(0.0, 10.0), Pos2::new(100.0, 100.0));
+        let config = DataLabels::new().clamp(true);
+        let label = layout_labels(&[(bar(12.0), Vec2::new(16.0, 10.0))], &config, area)[0].unwrap();
+        assert_eq!(label.center.y, 15.0);
+    }
+
+    #[test]
+    fn test_overlap_hide_and_shift() {
+        let labels = [
+            (LabelTarget::Point { pos: Pos2::new(50.0, 50.0), radius: 3.0 }, Vec2::new(20.0, 10.0)),
+            (LabelTarget::Point { pos: Pos2::new(55.0, 52.0), radius: 3.0 }, Vec2::new(20.0, 10.0)),
+        ];
+
+        let hidden = layout_labels(&labels, &DataLabels::new().overlap(LabelOverlap::Hide), Rect::EVERYTHING);
+        assert!(hidden[0].is_some());
+        assert!(hidden[1].is_none());
+
+        let config = DataLabels::new().overlap(LabelOverlap::Shift);
+        let shifted = layout_labels(&labels, &config, Rect::EVERYTHING);
+        let (first, second) = (shifted[0].unwrap(), shifted[1].unwrap());
+        assert!(second.bounds(0.0).max.y <= first.bounds(0.0).min.y);
+
+        // All labels are drawn by default, as with `show_values`
+        let all = layout_labels(&labels, &DataLabels::new(), Rect::EVERYTHING);
+        assert!(all.iter().all(Option::is_some));
+    }
+
+    #[test]
+    fn test_arc_label_inside_when_it_fits() {
+        let wide = ArcElement::new(Pos2::ZERO, 0.0, 100.0, 0.0, PI / 2.0);
+        let label = place(&LabelTarget::Arc(wide), Vec2::new(20.0, 10.0), &DataLabels::new());
+        assert!(label.inside);
+        assert!(label.leader.is_none());
+
+        let thin = ArcElement::new(Pos2::ZERO, 0.0, 100.0, 0.0, 0.05);
+        let label = place(&LabelTarget::Arc(thin), Vec2::new(20.0, 10.0), &DataLabels::new());
+        assert!(!label.inside);
+        let [start, end] = label.leader.unwrap();
+        assert!((start.to_vec2().length() - 100.0).abs() < 0.01);
+        assert!(end.to_vec2().length() > 100.0);
+        assert!(label.center.x > end.x);
+    }
+
+    #[test]
+    fn test_rotated_bounds() {
+        let size = rotated_size(Vec2::new(20.0, 10.0), PI / 2.0);
+        assert!((size.x - 10.0).abs() < 0.01);
+        assert!((size.y - 20.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_formatter_hides_labels() {
+        let context = LabelContext {
+            dataset: 0,
+            index: 0,
+            value: 0.0,
+            fraction: None,
+        };
+        let config = DataLabels::new().formatter(|c| (c.value != 0.0).then(|| c.value.to_string()));
+        assert_eq!(config.text(&context, |v| v.to_string()), None);
+        assert_eq!(DataLabels::new().text(&context, |v| v.to_string()), Some("0".to_string()));
+    }
+}
@@ -0,0 +1,273 @@
+use std::ops::Range;
+
+use crate::data::{is_missing, partition_point, ChartData};
+
+/// How a [`Decimation`] picks the points to draw
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DecimationAlgorithm {
+    /// First, lowest, highest and last point of each pixel column; keeps every peak
+    #[default]
+    MinMax,
+    /// Largest-Triangle-Three-Buckets: a fixed number of points preserving the shape
+    Lttb,
+}
+
+/// Draw fewer points for large series
+///
+/// Decimation only changes what is drawn: hovered and clicked indices,
+/// tooltips and labels still refer to the original data. Missing points
+/// are never picked.
+///
+/// ```rust,ignore
+/// LineChart::new()
+///     .data(samples)
+///     .decimation(Decimation::lttb().samples(500))
+///     .show(ui);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Decimation {
+    pub enabled: bool,
+    pub algorithm: DecimationAlgorithm,
+    /// Decimate when more points than this are on screen (default: 4 per pixel of width)
+    pub threshold: Option<usize>,
+    /// Points kept by LTTB (default: 1 per pixel of width)
+    pub samples: Option<usize>,
+}
+
+impl Default for Decimation {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            algorithm: DecimationAlgorithm::default(),
+            threshold: None,
+            samples: None,
+        }
+    }
+}
+
+impl Decimation {
+    /// Min/max decimation above the default threshold
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Min/max per pixel column
+    pub fn min_max() -> Self {
+        Self::default()
+    }
+
+    /// Largest-Triangle-Three-Buckets
+    pub fn lttb() -> Self {
+        Self {
+            algorithm: DecimationAlgorithm::Lttb,
+            ..Default::default()
+        }
+    }
+
+    /// Always draw every point
+    pub fn off() -> Self {
+        Self {
+            enabled: false,
+            ..Default::default()
+        }
+    }
+
+    /// Decimate when more than `points` are on screen
+    pub fn threshold(mut self, points: usize) -> Self {
+        self.threshold = Some(points);
+        self
+    }
+
+    /// Number of points LTTB keeps
+    pub fn samples(mut self, samples: usize) -> Self {
+        self.samples = Some(samples.max(3));
+        self
+    }
+
+    /// Indices of `data` to draw: all of `visible` when there are few enough,
+    /// otherwise the decimated ones. `pixel_of` gives the x pixel of an index.
+    pub(crate) fn indices(
+        &self,
+        data: &dyn ChartData,
+        visible: Range<usize>,
+        plot_width: f32,
+        pixel_of: impl Fn(usize) -> f32,
+    ) -> Vec<usize> {
+        let width = plot_width.max(1.0) as usize;
+        let threshold = self.threshold.unwrap_or(4 * width);
+        if !self.enabled || visible.len() <= threshold {
+            return visible.collect();
+        }
+        match self.algorithm {
+            DecimationAlgorithm::MinMax => min_max(data, visible, pixel_of),
+            DecimationAlgorithm::Lttb => lttb(data, visible, self.samples.unwrap_or(width)),
+        }
+    }
+}
+
+/// Points kept past each edge of the view: the one a line runs off to, its
+/// neighbour that shapes the curve into it, and the one after that, which
+/// monotone curves look at when limiting that neighbour's tangent
+const EDGE_POINTS: usize = 3;
+
+/// Indices of the data points on screen, plus [`EDGE_POINTS`] on each side
+/// so lines run off the edges along the same curves as with all points.
+/// Expects x positions not to decrease.
+pub(crate) fn visible_indices(data: &dyn ChartData, x_min: f64, x_max: f64) -> Range<usize> {
+    let len = data.len();
+    let start = partition_point(len, |i| data.x(i) <= x_min).saturating_sub(EDGE_POINTS);
+    let end = (partition_point(len, |i| data.x(i) < x_max) + EDGE_POINTS).min(len);
+    start..end.max(start)
+}
+
+/// Largest-Triangle-Three-Buckets: keep the first and last points, and from
+/// each bucket in between the one forming the largest triangle with the point
+/// kept before it and the average of the next bucket
+pub(crate) fn lttb(data: &dyn ChartData, range: Range<usize>, samples: usize) -> Vec<usize> {
+    let len = range.len();
+    if samples < 3 || samples >= len {
+        return range.collect();
+    }
+
+    let every = (len - 2) as f64 / (samples - 2) as f64;
+    let bucket_start = |bucket: usize| range.start + 1 + (bucket as f64 * every) as usize;
+    let present = |k: &usize| !is_missing(data.y(*k));
+    let mut kept = Vec::with_capacity(samples);
+    kept.push(range.start);
+    // Triangles start at the last kept point that isn't missing
+    let mut a = range.clone().find(present).unwrap_or(range.start);
+
+    for bucket in 0..samples - 2 {
+        // Average of the next bucket (the last point for the last bucket)
+        let next = bucket_start(bucket + 1)..bucket_start(bucket + 2).min(range.end - 1);
+        let next = if next.is_empty() { range.end - 1..range.end } else { next };
+        let (count, sum_x, sum_y) = next
+            .clone()
+            .filter(present)
+            .fold((0.0, 0.0, 0.0), |(n, x, y), k| (n + 1.0, x + data.x(k), y + data.y(k)));
+        let (ax, ay) = (data.x(a), data.y(a));
+        let (avg_x, avg_y) = if count > 0.0 { (sum_x / count, sum_y / count) } else { (data.x(next.start), ay) };
+
+        let current = bucket_start(bucket)..bucket_start(bucket + 1).min(range.end - 1);
+        let best = current
+            .filter(present)
+            .max_by(|&i, &j| {
+                let area = |k: usize| ((ax - avg_x) * (data.y(k) - ay) - (ax - data.x(k)) * (avg_y - ay)).abs();
+                area(i).total_cmp(&area(j))
+            })
+            .unwrap_or(a);
+        if best != a {
+            kept.push(best);
+            a = best;
+        }
+    }
+    kept.push(range.end - 1);
+    kept
+}
+
+/// First, lowest, highest and last point of each pixel column, in order,
+/// leaving out missing points
+pub(crate) fn min_max(data: &dyn ChartData, range: Range<usize>, pixel_of: impl Fn(usize) -> f32) -> Vec<usize> {
+    let mut kept = Vec::new();
+    let mut column: Option<(i64, [usize; 4])> = None;
+    let flush = |kept: &mut Vec<usize>, [first, min, max, last]: [usize; 4]| {
+        let mut picks = [first, min, max, last];
+        picks.sort_unstable();
+        for i in picks {
+            if kept.last() != Some(&i) {
+                kept.push(i);
+            }
+        }
+    };
+
+    for i in range.filter(|&i| !is_missing(data.y(i))) {
+        let x = pixel_of(i).floor() as i64;
+        match &mut column {
+            Some((cx, [_, min, max, last])) if *cx == x => {
+                if data.y(i) < data.y(*min) {
+                    *min = i;
+                }
+                if data.y(i) > data.y(*max) {
+                    *max = i;
+                }
+                *last = i;
+            }
+            _ => {
+                if let Some((_, picks)) = column {
+                    flush(&mut kept, picks);
+                }
+                column = Some((x, [i; 4]));
+            }
+        }
+    }
+    if let Some((_, picks)) = column {
+        flush(&mut kept, picks);
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lttb_keeps_ends_and_peaks() {
+        let mut data: Vec<f64> = (0..1000).map(|i| (i as f64 / 50.0).sin()).collect();
+        data[500] = 10.0;
+        let kept = lttb(&data, 0..data.len(), 100);
+        assert!(kept.len() <= 100);
+        assert_eq!(kept.first(), Some(&0));
+        assert_eq!(kept.last(), Some(&999));
+        assert!(kept.contains(&500));
+        assert!(kept.windows(2).all(|w| w[0] < w[1]));
+
+        // Missing points are never picked, and don't spoil the triangles
+        let mut gaps = data.clone();
+        for v in &mut gaps[490..500] {
+            *v = f64::NAN;
+        }
+        let kept = lttb(&gaps, 0..gaps.len(), 100);
+        assert!(kept.contains(&500));
+        assert!(!kept.iter().any(|i| (490..500).contains(i)));
+
+        // Nothing to do for short series
+        assert_eq!(lttb(&data, 10..20, 100), (10..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_min_max_per_column() {
+        let data = [1.0, 5.0, -3.0, 2.0, 0.0, 7.0, 1.0, 1.0];
+        // Four points per pixel column
+        let kept = min_max(&data, 0..data.len(), |i| i as f32 / 4.0);
+        // Column 0: first 0, max 1, min 2, last 3; column 1: first 4, max 5, last 7
+        assert_eq!(kept, vec![0, 1, 2, 3, 4, 5, 7]);
+
+        // Missing points are skipped, even where they start a column
+        let data = [f64::NAN, 5.0, -3.0, 2.0, f64::NAN, f64::NAN, f64::NAN, f64::NAN];
+        assert_eq!(min_max(&data, 0..data.len(), |i| i as f32 / 4.0), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_threshold() {
+        let data = vec![0.0; 1000];
+        let pixel_of = |i: usize| i as f32 / 10.0;
+        // 100 pixels wide: up to 400 points are drawn as they are
+        assert_eq!(Decimation::new().indices(&data, 0..400, 100.0, pixel_of).len(), 400);
+        assert!(Decimation::new().indices(&data, 0..1000, 100.0, pixel_of).len() < 1000);
+        assert_eq!(Decimation::off().indices(&data, 0..1000, 100.0, pixel_of).len(), 1000);
+        assert_eq!(Decimation::lttb().threshold(10).samples(50).indices(&data, 0..1000, 100.0, pixel_of).len(), 50);
+    }
+
+    #[test]
+    fn test_visible_indices() {
+        let data: Vec<f64> = (0..100).map(f64::from).collect();
+        assert_eq!(visible_indices(&data, 0.0, 99.0), 0..100);
+        assert_eq!(visible_indices(&data, 10.5, 20.5), 8..24);
+        // Right of all points: the last few, so a line runs in from the left
+        assert_eq!(visible_indices(&data[..5].to_vec(), 10.0, 20.0), 2..5);
+
+        // Uneven x positions
+        let pairs: Vec<(f64, f64)> = (0..100).map(|i| ((i * i) as f64, 0.0)).collect();
+        assert_eq!(visible_indices(&pairs, 100.0, 400.0), 8..23);
+    }
+}
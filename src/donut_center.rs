@@ -0,0 +1,232 @@
+use egui::{Align2, Color32, FontId, Painter, Pos2, Vec2};
+
+/// Space between center lines
+const LINE_SPACING: f32 = 2.0;
+
+/// One line of text in the donut hole
+#[derive(Clone, Debug, PartialEq)]
+pub struct CenterLine {
+    /// Text; `{total}` is replaced with the formatted total of visible segments
+    pub text: String,
+    pub font_size: f32,
+    /// Text color, defaults to the theme's text color
+    pub color: Option<Color32>,
+}
+
+impl CenterLine {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            font_size: 14.0,
+            color: None,
+        }
+    }
+
+    /// Set font size
+    pub fn size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    /// Set text color
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// The hovered segment, as shown in the donut hole
+#[derive(Clone, Debug, PartialEq)]
+pub struct CenterSegment {
+    /// Ring of the segment (0 is the main ring)
+    pub ring: usize,
+    pub index: usize,
+    pub label: String,
+    pub value: f64,
+    /// Share of the ring, or of the parent slice in a sunburst
+    pub fraction: f64,
+    pub color: Color32,
+}
+
+/// What a center render callback gets to draw with
+#[derive(Clone, Debug)]
+pub struct CenterContext {
+    pub center: Pos2,
+    /// Radius of the hole
+    pub radius: f32,
+    /// Total of the visible segments
+    pub total: f64,
+    pub hovered: Option<CenterSegment>,
+    pub text_color: Color32,
+}
+
+/// Content of the donut hole
+///
+/// ```rust,ignore
+/// PieChart::new()
+///     .data(data)
+///     .donut(0.6)
+///     .center(
+///         DonutCenter::new()
+///             .line(CenterLine::new("{total}").size(22.0))
+///             .line(CenterLine::new("visitors").size(11.0))
+///             .hovered_details(true),
+///     )
+///     .show(ui);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DonutCenter {
+    /// Lines stacked around the center
+    pub lines: Vec<CenterLine>,
+    /// Replace the lines with the hovered segment's label, value and share
+    pub hovered_details: bool,
+    /// Draw the center yourself instead
+    pub render: Option<fn(&Painter, &CenterContext)>,
+}
+
+impl Default for DonutCenter {
+    fn default() -> Self {
+        Self {
+            lines: vec![CenterLine::new("{total}").size(16.0)],
+            hovered_details: false,
+            render: None,
+        }
+    }
+}
+
+impl DonutCenter {
+    /// No content; add lines with [`DonutCenter::line`]
+    pub fn new() -> Self {
+        Self {
+            lines: Vec::new(),
+            ..Default::default()
+        }
+    }
+
+    /// Nothing in the hole
+    pub fn none() -> Self {
+        Self::new()
+    }
+
+    /// Add a line of text
+    pub fn line(mut self, line: CenterLine) -> Self {
+        self.lines.push(line);
+        self
+    }
+
+    /// Show the hovered segment's details instead of the lines
+    pub fn hovered_details(mut self, show: bool) -> Self {
+        self.hovered_details = show;
+        self
+    }
+
+    /// Draw the center with a callback
+    pub fn render(mut self, render: fn(&Painter, &CenterContext)) -> Self {
+        self.render = Some(render);
+        self
+    }
+
+    /// Lines to show for this frame, with placeholders filled in
+    pub(crate) fn resolve(&self, context: &CenterContext, format: fn(f64) -> String) -> Vec<CenterLine> {
+        if let Some(segment) = context.hovered.as_ref().filter(|_| self.hovered_details) {
+            return vec![
+                CenterLine::new(segment.label.clone()).size(12.0),
+                CenterLine::new(format(segment.value)).size(20.0).color(segment.color),
+                CenterLine::new(format!("{:.1}%", segment.fraction * 100.0)).size(11.0),
+            ];
+        }
+        let total = format(context.total);
+        self.lines
+            .iter()
+            .map(|line| CenterLine {
+                text: line.text.replace("{total}", &total),
+                ..line.clone()
+            })
+            .collect()
+    }
+
+    /// Draw the center content
+    pub(crate) fn draw(&self, painter: &Painter, context: &CenterContext, format: fn(f64) -> String) {
+        if let Some(render) = self.render {
+            render(painter, context);
+            return;
+        }
+
+        let galleys: Vec<_> = self
+            .resolve(context, format)
+            .into_iter()
+            .map(|line| {
+                let color = line.color.unwrap_or(context.text_color);
+                painter.layout_no_wrap(line.text, FontId::proportional(line.font_size), color)
+            })
+            .collect();
+        let heights: Vec<f32> = galleys.iter().map(|g| g.size().y).collect();
+        for (galley, y) in galleys.into_iter().zip(stack_lines(&heights)) {
+            let rect = Align2::CENTER_CENTER.anchor_size(context.center + Vec2::new(0.0, y), galley.size());
+            painter.galley(rect.min, galley, context.text_color);
+        }
+    }
+}
+
+/// Vertical offsets from the center of lines of the given heights, stacked
+/// so the block is centered
+fn stack_lines(heights: &[f32]) -> Vec<f32> {
+    let total = heights.iter().sum::<f32>() + LINE_SPACING * heights.len().saturating_sub(1) as f32;
+    let mut top = -total / 2.0;
+    heights
+        .iter()
+        .map(|height| {
+            let y = top + height / 2.0;
+            top += height + LINE_SPACING;
+            y
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(hovered: Option<CenterSegment>) -> CenterContext {
+        CenterContext {
+            center: Pos2::ZERO,
+            radius: 50.0,
+            total: 1200.0,
+            hovered,
+            text_color: Color32::GRAY,
+        }
+    }
+
+    #[test]
+    fn test_stack_lines_centered() {
+        assert_eq!(stack_lines(&[10.0]), vec![0.0]);
+        assert_eq!(stack_lines(&[10.0, 10.0]), vec![-6.0, 6.0]);
+    }
+
+    #[test]
+    fn test_total_placeholder() {
+        let center = DonutCenter::new().line(CenterLine::new("{total} users"));
+        let lines = center.resolve(&context(None), |v| format!("{v:.0}"));
+        assert_eq!(lines[0].text, "1200 users");
+    }
+
+    #[test]
+    fn test_hovered_details_replace_lines() {
+        let segment = CenterSegment {
+            ring: 0,
+            index: 1,
+            label: "Safari".into(),
+            value: 300.0,
+            fraction: 0.25,
+            color: Color32::RED,
+        };
+        let center = DonutCenter::default().hovered_details(true);
+        let lines = center.resolve(&context(Some(segment.clone())), |v| format!("{v:.0}"));
+        let texts: Vec<&str> = lines.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(texts, vec!["Safari", "300", "25.0%"]);
+
+        // Without hovered details, the total stays
+        let lines = DonutCenter::default().resolve(&context(Some(segment)), |v| format!("{v:.0}"));
+        assert_eq!(lines[0].text, "1200");
+    }
+}
@@ -1,6 +1,8 @@
-use egui::{Color32, Painter, Pos2, Stroke};
+use egui::{Color32, Painter, Pos2, Stroke, Vec2};
 use std::f32::consts::PI;
 
+use crate::interaction::HoverStyle;
+
 /// Represents an arc segment for pie/donut charts
 #[derive(Clone, Debug)]
 pub struct ArcElement {
@@ -20,6 +22,12 @@ pub struct ArcElement {
     pub border_color: Color32,
     /// Border width
     pub border_width: f32,
+    /// Gap between this segment and its neighbours, in pixels
+    pub spacing: f32,
+    /// Corner radius, in pixels
+    pub border_radius: f32,
+    /// Stroke around the whole segment, drawn over the border
+    pub outline: Option<Stroke>,
 }
 
 impl ArcElement {
@@ -40,6 +48,9 @@ impl ArcElement {
             fill_color: Color32::from_rgb(54, 162, 235),
             border_color: Color32::WHITE,
             border_width: 2.0,
+            spacing: 0.0,
+            border_radius: 0.0,
+            outline: None,
         }
     }
 
@@ -54,22 +65,22 @@ impl ArcElement {
             return false;
         }
 
-        // Check angle bounds
-        let mut angle = dy.atan2(dx);
-        if angle < 0.0 {
-            angle += 2.0 * PI;
+        // Check angle bounds, measured from the start of the sweep so partial
+        // sweeps and sweeps crossing the 0/2PI boundary work alike
+        let sweep = self.end_angle - self.start_angle;
+        if sweep.abs() >= 2.0 * PI {
+            return true;
+        }
+        let start = self.start_angle.min(self.end_angle);
+        if normalize_angle(dy.atan2(dx) - start) > sweep.abs() {
+            return false;
         }
 
-        // Normalize angles to 0..2PI range
-        let start = normalize_angle(self.start_angle);
-        let end = normalize_angle(self.end_angle);
-
-        if start <= end {
-            angle >= start && angle <= end
-        } else {
-            // Arc crosses 0/2PI boundary
-            angle >= start || angle <= end
+        // Spacing and rounded corners cut into the plain segment
+        if self.spacing > 0.0 || self.border_radius > 0.0 {
+            return self.shape(self.start_angle, self.end_angle).contains(pos);
         }
+        true
     }
 
     /// Get the middle angle of the arc
@@ -102,84 +113,218 @@ impl ArcElement {
         if (end - start).abs() < 0.001 {
             return;
         }
+        let shape = self.shape(start, end);
+        if shape.outer.is_empty() {
+            return;
+        }
+
+        let feather = 1.0 / painter.ctx().pixels_per_point();
+        painter.add(egui::Shape::mesh(shape.mesh(self.fill_color, feather)));
+
+        if self.border_width > 0.0 {
+            let stroke = Stroke::new(self.border_width, self.border_color);
+            let [start_side, end_side] = shape.sides();
+            painter.add(egui::Shape::line(start_side, stroke));
+            painter.add(egui::Shape::line(end_side, stroke));
+        }
+        if let Some(stroke) = self.outline {
+            painter.add(egui::Shape::closed_line(shape.outline(), stroke));
+        }
+    }
+
+    /// Geometry of the segment between the given angles, with `spacing`
+    /// taken off both sides and corners rounded by `border_radius`
+    pub(crate) fn shape(&self, start: f32, end: f32) -> ArcShape {
+        let (start, end) = (start.min(end), start.max(end));
+        let sweep = end - start;
+        let full = sweep >= 2.0 * PI - 1e-4;
+        let gap = if full { 0.0 } else { self.spacing.max(0.0) / 2.0 };
+        let half = sweep / 2.0;
+        let outer_radius = self.outer_radius;
+
+        // Where the two sides meet, if they do before reaching the hole
+        let apex = if gap > 0.0 { gap / half.sin().max(1e-6) } else { 0.0 };
+        if apex >= outer_radius {
+            return ArcShape::default();
+        }
+        let has_hole = self.inner_radius > apex;
+        let inner_radius = self.inner_radius.max(apex);
+
+        // Largest corner radii that still fit the segment's sweep and thickness
+        let sin = if half >= PI / 2.0 { 1.0 } else { half.sin() };
+        let radius = if full { 0.0 } else { self.border_radius.clamp(0.0, (outer_radius - inner_radius) / 2.0) };
+        let outer_corner = radius.min(((outer_radius * sin - gap) / (1.0 + sin)).max(0.0));
+        let inner_corner = match has_hole {
+            true if sin < 1.0 => radius.min(((inner_radius * sin - gap) / (1.0 - sin)).max(0.0)),
+            true => radius,
+            false => 0.0,
+        };
+
+        let arc_steps = (sweep * 64.0 / PI).max(16.0) as usize;
+        let corner_steps = if radius > 0.0 { CORNER_STEPS } else { 0 };
+        let along = |from: f32, to: f32, steps: usize| {
+            (0..=steps).map(move |i| from + (to - from) * i as f32 / steps.max(1) as f32)
+        };
+        // Arcs leave out their ends, which are the corners' last points
+        let between = |from: f32, to: f32| (1..arc_steps).map(move |i| from + (to - from) * i as f32 / arc_steps as f32);
+        let at = |center: Pos2, angle: f32, radius: f32| center + Vec2::angled(angle) * radius;
+
+        // Outer edge: start corner, arc, end corner
+        let rho = outer_radius - outer_corner;
+        let theta = ((gap + outer_corner) / rho).min(1.0).asin();
+        let start_corner = at(self.center, start + theta, rho);
+        let end_corner = at(self.center, end - theta, rho);
+        let mut outer: Vec<Pos2> = along(start - PI / 2.0, start + theta, corner_steps)
+            .map(|a| at(start_corner, a, outer_corner))
+            .collect();
+        outer.extend(between(start + theta, end - theta).map(|a| at(self.center, a, outer_radius)));
+        outer.extend(along(end - theta, end + PI / 2.0, corner_steps).map(|a| at(end_corner, a, outer_corner)));
+
+        // Inner edge, point for point, or the apex
+        let inner = if has_hole {
+            let rho = inner_radius + inner_corner;
+            let theta = ((gap + inner_corner) / rho).min(1.0).asin();
+            let start_corner = at(self.center, start + theta, rho);
+            let end_corner = at(self.center, end - theta, rho);
+            let mut inner: Vec<Pos2> = along(start - PI / 2.0, start + theta - PI, corner_steps)
+                .map(|a| at(start_corner, a, inner_corner))
+                .collect();
+            inner.extend(between(start + theta, end - theta).map(|a| at(self.center, a, inner_radius)));
+            inner.extend(along(end - theta + PI, end + PI / 2.0, corner_steps).map(|a| at(end_corner, a, inner_corner)));
+            inner
+        } else {
+            vec![at(self.center, start + half, apex); outer.len()]
+        };
+
+        ArcShape {
+            outer,
+            inner,
+            corner_points: corner_steps + 1,
+        }
+    }
+}
+
+/// Steps used to round each corner
+const CORNER_STEPS: usize = 6;
+
+/// Outline of a drawn segment
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ArcShape {
+    /// Along the outer edge, from the start side to the end side
+    pub outer: Vec<Pos2>,
+    /// Along the inner edge, one point for each outer one; all the same point
+    /// when the sides meet before the hole (or at the center of a pie)
+    pub inner: Vec<Pos2>,
+    /// Points in each corner at the ends of `outer` and `inner`
+    pub corner_points: usize,
+}
+
+impl ArcShape {
+    /// Whether the sides meet in a single point instead of an inner edge
+    fn has_apex(&self) -> bool {
+        self.inner.windows(2).all(|w| w[0] == w[1])
+    }
 
-        // Build polygon points for the arc
-        // Use more segments for smoother curves (64 per full circle minimum)
-        let segments = ((end - start).abs() * 64.0 / PI).max(16.0) as usize;
-        let mut points = Vec::with_capacity(segments * 2 + 2);
-
-        // Outer arc (clockwise)
-        for i in 0..=segments {
-            let t = i as f32 / segments as f32;
-            let angle = start + (end - start) * t;
-            points.push(Pos2::new(
-                self.center.x + angle.cos() * self.outer_radius,
-                self.center.y + angle.sin() * self.outer_radius,
-            ));
+    /// Filled mesh: a triangle strip between the edges of a ring segment or a
+    /// fan around the apex of a pie slice, plus a band `feather` wide fading
+    /// out around the outline to anti-alias the edges
+    pub fn mesh(&self, color: Color32, feather: f32) -> egui::Mesh {
+        let mut mesh = egui::Mesh::default();
+        if self.outer.len() < 2 {
+            return mesh;
         }
 
-        // Inner arc (counter-clockwise) or center point
-        if self.inner_radius > 0.0 {
-            for i in (0..=segments).rev() {
-                let t = i as f32 / segments as f32;
-                let angle = start + (end - start) * t;
-                points.push(Pos2::new(
-                    self.center.x + angle.cos() * self.inner_radius,
-                    self.center.y + angle.sin() * self.inner_radius,
-                ));
+        if self.has_apex() {
+            mesh.colored_vertex(self.inner[0], color);
+            for (i, &point) in self.outer.iter().enumerate() {
+                mesh.colored_vertex(point, color);
+                if i > 0 {
+                    let v = i as u32 + 1;
+                    mesh.add_triangle(0, v - 1, v);
+                }
             }
         } else {
-            // For pie (no hole), add center point
-            points.push(self.center);
+            for (i, (&outer, &inner)) in self.outer.iter().zip(&self.inner).enumerate() {
+                mesh.colored_vertex(outer, color);
+                mesh.colored_vertex(inner, color);
+                if i > 0 {
+                    let v = 2 * i as u32;
+                    mesh.add_triangle(v - 2, v, v - 1);
+                    mesh.add_triangle(v - 1, v, v + 1);
+                }
+            }
+        }
+
+        if feather > 0.0 {
+            self.add_feather(&mut mesh, color, feather);
         }
+        mesh
+    }
 
-        // Draw filled polygon
-        if points.len() >= 3 {
-            painter.add(egui::Shape::convex_polygon(
-                points.clone(),
-                self.fill_color,
-                Stroke::NONE,
-            ));
-
-            // Draw border between segments (radial lines only)
-            if self.border_width > 0.0 {
-                // Draw radial line at start angle
-                let start_outer = Pos2::new(
-                    self.center.x + start.cos() * self.outer_radius,
-                    self.center.y + start.sin() * self.outer_radius,
-                );
-                let start_inner = if self.inner_radius > 0.0 {
-                    Pos2::new(
-                        self.center.x + start.cos() * self.inner_radius,
-                        self.center.y + start.sin() * self.inner_radius,
-                    )
-                } else {
-                    self.center
-                };
-                painter.line_segment(
-                    [start_inner, start_outer],
-                    Stroke::new(self.border_width, self.border_color),
-                );
-
-                // Draw radial line at end angle
-                let end_outer = Pos2::new(
-                    self.center.x + end.cos() * self.outer_radius,
-                    self.center.y + end.sin() * self.outer_radius,
-                );
-                let end_inner = if self.inner_radius > 0.0 {
-                    Pos2::new(
-                        self.center.x + end.cos() * self.inner_radius,
-                        self.center.y + end.sin() * self.inner_radius,
-                    )
-                } else {
-                    self.center
-                };
-                painter.line_segment(
-                    [end_inner, end_outer],
-                    Stroke::new(self.border_width, self.border_color),
-                );
+    /// Band around the outline fading from `color` to transparent
+    fn add_feather(&self, mesh: &mut egui::Mesh, color: Color32, feather: f32) {
+        let outline = self.outline();
+        let n = outline.len();
+        if n < 3 {
+            return;
+        }
+
+        // The outline runs clockwise on screen, so edge normals point out
+        let normal = |from: Pos2, to: Pos2| {
+            let d = (to - from).normalized();
+            Vec2::new(d.y, -d.x)
+        };
+        let first = mesh.vertices.len() as u32;
+        for i in 0..n {
+            let (prev, point, next) = (outline[(i + n - 1) % n], outline[i], outline[(i + 1) % n]);
+            let out = (normal(prev, point) + normal(point, next)).normalized();
+            mesh.colored_vertex(point, color);
+            mesh.colored_vertex(point + out * feather, Color32::TRANSPARENT);
+        }
+        for i in 0..n as u32 {
+            let (a, b) = (first + 2 * i, first + 2 * ((i + 1) % n as u32));
+            mesh.add_triangle(a, a + 1, b);
+            mesh.add_triangle(a + 1, b + 1, b);
+        }
+    }
+
+    /// Closed outline: the outer edge, then the inner edge back
+    pub fn outline(&self) -> Vec<Pos2> {
+        let mut points = self.outer.clone();
+        points.extend(self.inner.iter().rev());
+        points.dedup();
+        points
+    }
+
+    /// The start and end sides, from the inner edge to the outer one, including their corners
+    pub fn sides(&self) -> [Vec<Pos2>; 2] {
+        let k = self.corner_points.min(self.outer.len());
+        let n = self.outer.len();
+        let side = |inner: &[Pos2], outer: &[Pos2]| {
+            let mut points: Vec<Pos2> = inner.iter().rev().chain(outer).copied().collect();
+            points.dedup();
+            points
+        };
+        let mut end_outer = self.outer[n - k..].to_vec();
+        end_outer.reverse();
+        let mut end_inner = self.inner[n - k..].to_vec();
+        end_inner.reverse();
+        [side(&self.inner[..k], &self.outer[..k]), side(&end_inner, &end_outer)]
+    }
+
+    /// Whether `pos` is inside the outline
+    pub fn contains(&self, pos: Pos2) -> bool {
+        let outline = self.outline();
+        let mut inside = false;
+        let mut j = outline.len().wrapping_sub(1);
+        for (i, a) in outline.iter().enumerate() {
+            let b = outline[j];
+            if (a.y > pos.y) != (b.y > pos.y) && pos.x < a.x + (b.x - a.x) * (pos.y - a.y) / (b.y - a.y) {
+                inside = !inside;
             }
+            j = i;
         }
+        inside
     }
 }
 
@@ -192,6 +337,23 @@ fn normalize_angle(angle: f32) -> f32 {
     a
 }
 
+/// Bounds of a ring sweeping `sweep` radians from `start` around a unit circle
+/// at the origin, with the inner radius given as a fraction of the outer one
+pub(crate) fn sweep_bounds(start: f32, sweep: f32, inner_ratio: f32) -> egui::Rect {
+    let at = |angle: f32, radius: f32| Pos2::new(angle.cos() * radius, angle.sin() * radius);
+    let end = start + sweep;
+    let mut points = vec![at(start, 1.0), at(end, 1.0), at(start, inner_ratio), at(end, inner_ratio)];
+
+    // Extremes where the outer edge crosses an axis
+    let first_quarter = (start / (PI / 2.0)).ceil() as i32;
+    let mut k = first_quarter;
+    while (k as f32) * PI / 2.0 <= end && k - first_quarter < 4 {
+        points.push(at(k as f32 * PI / 2.0, 1.0));
+        k += 1;
+    }
+    egui::Rect::from_points(&points)
+}
+
 /// Style for pie/donut charts
 #[derive(Clone, Debug)]
 pub struct PieStyle {
@@ -205,6 +367,16 @@ pub struct PieStyle {
     pub donut_ratio: f32,
     /// Start angle in radians (default: -PI/2 = top)
     pub start_angle: f32,
+    /// Angle covered by all segments together, in radians (default: 2PI)
+    pub circumference: f32,
+    /// Gap between segments, in pixels
+    pub spacing: f32,
+    /// Segment corner radius, in pixels
+    pub border_radius: f32,
+    /// Stroke around each whole segment
+    pub outline: Option<Stroke>,
+    /// Appearance of hovered segments (`offset` pulls the segment outward)
+    pub hover: HoverStyle,
 }
 
 impl Default for PieStyle {
@@ -222,6 +394,11 @@ impl Default for PieStyle {
             border_width: 2.0,
             donut_ratio: 0.0,
             start_angle: -PI / 2.0, // Start from top
+            circumference: 2.0 * PI,
+            spacing: 0.0,
+            border_radius: 0.0,
+            outline: None,
+            hover: HoverStyle::default(),
         }
     }
 }
@@ -230,6 +407,166 @@ impl Default for PieStyle {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_arc_contains_partial_sweeps() {
+        let center = Pos2::new(0.0, 0.0);
+
+        // Top half, starting at the left (a gauge)
+        let gauge = ArcElement::new(center, 10.0, 50.0, PI, 2.0 * PI);
+        assert!(gauge.contains(Pos2::new(0.0, -30.0)));
+        assert!(gauge.contains(Pos2::new(-30.0, -1.0)));
+        assert!(!gauge.contains(Pos2::new(0.0, 30.0)));
+
+        // Crossing the 0/2PI boundary, with angles outside 0..2PI
+        let wrap = ArcElement::new(center, 0.0, 50.0, -PI / 4.0, PI / 4.0);
+        assert!(wrap.contains(Pos2::new(30.0, 5.0)));
+        assert!(wrap.contains(Pos2::new(30.0, -5.0)));
+        assert!(!wrap.contains(Pos2::new(-30.0, 0.0)));
+
+        // A single segment covering the whole circle
+        let full = ArcElement::new(center, 0.0, 50.0, -PI / 2.0, 1.5 * PI);
+        assert!(full.contains(Pos2::new(-30.0, 0.0)));
+    }
+
+    #[test]
+    fn test_spacing_and_rounded_corners_hit_testing() {
+        let center = Pos2::ZERO;
+        let mut right = ArcElement::new(center, 20.0, 50.0, 0.0, PI);
+        let mut left = ArcElement::new(center, 20.0, 50.0, PI, 2.0 * PI);
+        for arc in [&mut right, &mut left] {
+            arc.spacing = 8.0;
+        }
+
+        // The gap along the shared side belongs to neither segment
+        let in_gap = Pos2::new(35.0, 2.0);
+        assert!(!right.contains(in_gap));
+        assert!(!left.contains(in_gap));
+        assert!(right.contains(Pos2::new(35.0, 6.0)));
+        assert!(left.contains(Pos2::new(35.0, -6.0)));
+
+        // A rounded corner no longer covers the corner of the plain segment
+        let mut rounded = ArcElement::new(center, 20.0, 50.0, 0.0, PI / 2.0);
+        let corner = Pos2::new(49.0, 1.0);
+        assert!(rounded.contains(corner));
+        rounded.border_radius = 10.0;
+        assert!(!rounded.contains(corner));
+        assert!(rounded.contains(Pos2::new(35.0, 35.0)));
+    }
+
+    #[test]
+    fn test_shape_with_spacing() {
+        // Pie sides meet away from the center
+        let mut pie = ArcElement::new(Pos2::ZERO, 0.0, 50.0, 0.0, PI / 2.0);
+        pie.spacing = 10.0;
+        let shape = pie.shape(pie.start_angle, pie.end_angle);
+        let apex = shape.inner[0];
+        assert!(shape.inner.iter().all(|&p| p == apex));
+        assert!((apex.x - 5.0).abs() < 1e-3 && (apex.y - 5.0).abs() < 1e-3);
+        assert_eq!(shape.outer.len(), shape.inner.len());
+
+        // Each side is parallel to its radius, half the spacing away
+        let [start_side, end_side] = shape.sides();
+        assert!(start_side.iter().all(|p| (p.y - 5.0).abs() < 1e-3));
+        assert!(end_side.iter().all(|p| (p.x - 5.0).abs() < 1e-3));
+
+        // Spacing wider than a sliver leaves nothing to draw
+        let mut sliver = ArcElement::new(Pos2::ZERO, 0.0, 50.0, 0.0, 0.05);
+        sliver.spacing = 10.0;
+        assert!(sliver.shape(0.0, 0.05).outer.is_empty());
+    }
+
+    /// Signed area of a triangle, positive when clockwise on screen
+    fn signed_area(a: Pos2, b: Pos2, c: Pos2) -> f32 {
+        ((b - a).x * (c - a).y - (b - a).y * (c - a).x) / 2.0
+    }
+
+    /// Number of the mesh's triangles containing `pos`
+    fn coverage(mesh: &egui::Mesh, pos: Pos2) -> usize {
+        mesh.indices
+            .chunks(3)
+            .filter(|t| {
+                let [a, b, c] = [0, 1, 2].map(|k| mesh.vertices[t[k] as usize].pos);
+                let areas = [signed_area(a, b, pos), signed_area(b, c, pos), signed_area(c, a, pos)];
+                areas.iter().all(|&s| s > 0.0) || areas.iter().all(|&s| s < 0.0)
+            })
+            .count()
+    }
+
+    fn assert_covers_once(arc: &ArcElement) {
+        let mesh = arc.shape(arc.start_angle, arc.end_angle).mesh(Color32::RED, 0.0);
+        let areas: Vec<f32> = mesh
+            .indices
+            .chunks(3)
+            .map(|t| signed_area(
+                mesh.vertices[t[0] as usize].pos,
+                mesh.vertices[t[1] as usize].pos,
+                mesh.vertices[t[2] as usize].pos,
+            ))
+            .collect();
+
+        // All triangles wind the same way, so none overlap or fold back
+        assert!(areas.iter().all(|&a| a >= 0.0));
+        let sweep = arc.end_angle - arc.start_angle;
+        let expected = sweep / 2.0 * (arc.outer_radius.powi(2) - arc.inner_radius.powi(2));
+        let total: f32 = areas.iter().sum();
+        assert!((total - expected).abs() / expected < 0.01, "{total} vs {expected}");
+
+        // Points in the segment are covered exactly once, points outside not at all
+        for k in 1..16 {
+            let angle = arc.start_angle + sweep * k as f32 / 16.0 + 0.01;
+            let radius = (arc.inner_radius + arc.outer_radius) / 2.0;
+            assert_eq!(coverage(&mesh, arc.center + Vec2::angled(angle) * radius), 1);
+        }
+        let outside = arc.center + Vec2::angled(arc.end_angle + (2.0 * PI - sweep) / 2.0) * arc.outer_radius / 2.0;
+        assert_eq!(coverage(&mesh, outside), 0);
+    }
+
+    #[test]
+    fn test_mesh_covers_large_pie_slice() {
+        // A 270° slice with its center point is not convex
+        assert_covers_once(&ArcElement::new(Pos2::new(50.0, 50.0), 0.0, 40.0, -PI / 2.0, PI));
+    }
+
+    #[test]
+    fn test_mesh_covers_ring_segments() {
+        assert_covers_once(&ArcElement::new(Pos2::ZERO, 20.0, 40.0, 0.0, PI / 3.0));
+        assert_covers_once(&ArcElement::new(Pos2::ZERO, 20.0, 40.0, 0.0, 5.0 * PI / 3.0));
+    }
+
+    #[test]
+    fn test_mesh_feathers_outward() {
+        let arc = ArcElement::new(Pos2::ZERO, 20.0, 40.0, 0.0, PI);
+        let shape = arc.shape(arc.start_angle, arc.end_angle);
+        let plain = shape.mesh(Color32::RED, 0.0);
+        let feathered = shape.mesh(Color32::RED, 1.0);
+        assert!(feathered.vertices.len() > plain.vertices.len());
+
+        // Faded vertices sit outside the segment: beyond the outer edge or inside the hole
+        for vertex in &feathered.vertices[plain.vertices.len()..] {
+            if vertex.color == Color32::TRANSPARENT {
+                let distance = vertex.pos.to_vec2().length();
+                assert!(!(19.5..=40.5).contains(&distance) || vertex.pos.y < -0.5, "{:?}", vertex.pos);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sweep_bounds() {
+        let full = sweep_bounds(-PI / 2.0, 2.0 * PI, 0.0);
+        assert!((full.min - Pos2::new(-1.0, -1.0)).length() < 1e-4);
+        assert!((full.max - Pos2::new(1.0, 1.0)).length() < 1e-4);
+
+        // Top semicircle: no room wasted below the center
+        let half = sweep_bounds(PI, PI, 0.5);
+        assert!((half.min - Pos2::new(-1.0, -1.0)).length() < 1e-4);
+        assert!((half.max - Pos2::new(1.0, 0.0)).length() < 1e-4);
+
+        // A quarter pie includes its center
+        let quarter = sweep_bounds(0.0, PI / 2.0, 0.0);
+        assert!((quarter.min - Pos2::ZERO).length() < 1e-4);
+        assert!((quarter.max - Pos2::new(1.0, 1.0)).length() < 1e-4);
+    }
+
     #[test]
     fn test_arc_contains_radius() {
         let arc = ArcElement::new(
@@ -1,4 +1,6 @@
-use egui::{Color32, CornerRadius, Painter, Pos2, Rect, Stroke, StrokeKind};
+use egui::{Color32, CornerRadius, Painter, Pos2, Rect, Shape, Stroke, StrokeKind};
+
+use crate::interaction::HoverStyle;
 
 /// Represents a single bar's geometry and style
 /// Mirrors Chart.js BarElement properties
@@ -65,25 +67,31 @@ impl BarElement {
     /// Draw the bar with current animation progress
     /// progress: 0.0 = start (no height), 1.0 = full height
     pub fn draw(&self, painter: &Painter, progress: f32) {
+        painter.extend(self.shapes(progress));
+    }
+
+    /// Shapes drawing the bar at the given animation progress
+    pub fn shapes(&self, progress: f32) -> Vec<Shape> {
         let rect = self.animated_rect(progress);
 
         // Don't draw if rect has no area
         if rect.width() <= 0.0 || rect.height() <= 0.0 {
-            return;
+            return Vec::new();
         }
 
-        // Draw fill
-        painter.rect_filled(rect, self.border_radius, self.fill_color);
+        // Fill
+        let mut shapes = vec![Shape::rect_filled(rect, self.border_radius, self.fill_color)];
 
-        // Draw border if specified
+        // Border if specified
         if self.border_width > 0.0 && self.border_color != Color32::TRANSPARENT {
-            painter.rect_stroke(
+            shapes.push(Shape::rect_stroke(
                 rect,
                 self.border_radius,
                 Stroke::new(self.border_width, self.border_color),
                 StrokeKind::Outside,
-            );
+            ));
         }
+        shapes
     }
 
     /// Calculate animated rectangle (grows from base)
@@ -131,6 +139,8 @@ pub struct BarStyle {
     pub bar_percentage: f32,
     /// Category width as percentage of available space [0.0, 1.0]
     pub category_percentage: f32,
+    /// Appearance while hovered
+    pub hover: HoverStyle,
 }
 
 impl Default for BarStyle {
@@ -150,6 +160,7 @@ impl Default for BarStyle {
             border_radius: CornerRadius::same(4),
             bar_percentage: 0.9,
             category_percentage: 0.8,
+            hover: HoverStyle::default(),
         }
     }
 }
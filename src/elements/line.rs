@@ -1,4 +1,10 @@
-use egui::{Color32, Painter, Pos2, Stroke};
+use std::ops::Range;
+
+use std::f32::consts::FRAC_PI_2;
+
+use egui::{Color32, Mesh, Painter, Pos2, Shape, Stroke, Vec2};
+
+use crate::interaction::HoverStyle;
 
 /// Represents a single data point on a line chart
 #[derive(Clone, Debug)]
@@ -45,19 +51,22 @@ impl PointElement {
 
     /// Draw the point
     pub fn draw(&self, painter: &Painter) {
+        painter.extend(self.shapes());
+    }
+
+    /// Shapes drawing the point
+    pub fn shapes(&self) -> Vec<Shape> {
         let center = self.pos();
+        let mut shapes = Vec::with_capacity(2);
 
-        // Draw border (larger circle behind)
+        // Border (larger circle behind)
         if self.border_width > 0.0 {
-            painter.circle_filled(
-                center,
-                self.radius + self.border_width,
-                self.border_color,
-            );
+            shapes.push(Shape::circle_filled(center, self.radius + self.border_width, self.border_color));
         }
 
-        // Draw fill
-        painter.circle_filled(center, self.radius, self.fill_color);
+        // Fill
+        shapes.push(Shape::circle_filled(center, self.radius, self.fill_color));
+        shapes
     }
 
     /// Draw the point with animated Y position
@@ -79,6 +88,61 @@ impl PointElement {
     }
 }
 
+/// Largest distance, in pixels, a flattened curve may stray from the true bezier
+const FLATNESS: f32 = 0.25;
+/// Deepest subdivision of one bezier segment
+const MAX_SUBDIVISIONS: u32 = 10;
+/// Width of the faded edge of stroke meshes, as egui anti-aliases at one pixel per point
+const FEATHER: f32 = 1.0;
+/// Largest angle one straight piece of a round join or cap turns by
+const ROUND_STEP: f32 = FRAC_PI_2 / 6.0;
+/// Longest mitered corner of a stroke mesh, in line widths, before it's cut off
+const MITER_LIMIT: f32 = 4.0;
+
+/// How the ends of a line are drawn
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LineCap {
+    /// Stop at the end points
+    #[default]
+    Butt,
+    /// Half circle past the end points
+    Round,
+    /// Extend half the line width past the end points
+    Square,
+}
+
+/// How straight segments meet
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LineJoin {
+    /// Sharp corners, clipped where they would spike
+    #[default]
+    Miter,
+    /// Rounded corners
+    Round,
+}
+
+/// How curved lines bend between points
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CubicInterpolation {
+    /// Catmull-Rom style curves shaped by the tension; may overshoot the points
+    #[default]
+    Default,
+    /// Monotone cubic (Fritsch–Carlson): never overshoots, so the curve stays
+    /// within the values of the points either side
+    Monotone,
+}
+
+/// Where a stepped line steps between two points
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Stepped {
+    /// Hold each value up to the next point, then step
+    Before,
+    /// Step at each point to the next value, then hold it
+    After,
+    /// Step halfway between the points
+    Middle,
+}
+
 /// Represents a line connecting multiple points
 #[derive(Clone, Debug)]
 pub struct LineElement {
@@ -92,6 +156,16 @@ pub struct LineElement {
     pub curved: bool,
     /// Tension for bezier curves (0.0 = straight, 0.4 = default Chart.js)
     pub tension: f32,
+    /// How curves bend between points
+    pub interpolation: CubicInterpolation,
+    /// Draw horizontal and vertical steps instead of a straight or curved line
+    pub stepped: Option<Stepped>,
+    /// Line ends
+    pub cap: LineCap,
+    /// Corners between straight segments
+    pub join: LineJoin,
+    /// Indices of the points after a gap, where the line breaks and starts again
+    pub breaks: Vec<usize>,
 }
 
 impl LineElement {
@@ -103,9 +177,31 @@ impl LineElement {
             width: 2.0,
             curved: true,
             tension: 0.4,
+            interpolation: CubicInterpolation::default(),
+            stepped: None,
+            cap: LineCap::default(),
+            join: LineJoin::default(),
+            breaks: Vec::new(),
         }
     }
 
+    /// Ranges of points drawn as one piece, between the breaks
+    pub fn segments(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        let ends = self.breaks.iter().copied().chain(std::iter::once(self.points.len()));
+        std::iter::once(0)
+            .chain(self.breaks.iter().copied())
+            .zip(ends)
+            .filter(|(start, end)| start < end)
+            .map(|(start, end)| start..end)
+    }
+
+    /// Points the line passes through on screen, ignoring breaks: the data
+    /// points, with curves flattened finely enough to look smooth
+    pub fn path(&self) -> Vec<Pos2> {
+        let positions: Vec<Pos2> = self.points.iter().map(|p| p.pos()).collect();
+        self.path_through(&positions)
+    }
+
     /// Draw the line
     pub fn draw(&self, painter: &Painter) {
         if self.points.len() < 2 {
@@ -113,7 +209,29 @@ impl LineElement {
         }
 
         let positions: Vec<Pos2> = self.points.iter().map(|p| p.pos()).collect();
-        self.draw_line_path(painter, &positions);
+        for segment in self.segments() {
+            self.draw_line_path(painter, &positions[segment]);
+        }
+    }
+
+    /// Shapes drawing the line, over the area down to `base_y` when a fill
+    /// `(base_y, color)` is given
+    pub fn shapes(&self, fill: Option<(f32, Color32)>) -> Vec<Shape> {
+        if self.points.len() < 2 {
+            return Vec::new();
+        }
+
+        let positions: Vec<Pos2> = self.points.iter().map(|p| p.pos()).collect();
+        let mut shapes = Vec::new();
+        for segment in self.segments() {
+            let positions = &positions[segment];
+            let path = self.path_through(positions);
+            if let Some((base_y, fill_color)) = fill {
+                shapes.push(Shape::mesh(fill_mesh(&path, base_y, fill_color)));
+            }
+            shapes.push(self.stroke_shape(path));
+        }
+        shapes
     }
 
     /// Draw the line with animated Y positions
@@ -122,49 +240,61 @@ impl LineElement {
             return;
         }
 
-        let positions: Vec<Pos2> = self
-            .points
+        let positions = self.animated_positions(base_y, progress);
+        for segment in self.segments() {
+            self.draw_line_path(painter, &positions[segment]);
+        }
+    }
+
+    /// Data point positions partway up from `base_y`
+    fn animated_positions(&self, base_y: f32, progress: f32) -> Vec<Pos2> {
+        self.points
             .iter()
             .map(|p| {
                 let animated_y = base_y + (p.y - base_y) * progress;
                 Pos2::new(p.x, animated_y)
             })
-            .collect();
+            .collect()
+    }
 
-        self.draw_line_path(painter, &positions);
+    /// Whether the line through `n` points is drawn as curves
+    fn draws_curves(&self, n: usize) -> bool {
+        self.curved && self.stepped.is_none() && n > 2
     }
 
-    /// Draw the actual line path
+    /// Path through the given data point positions
+    fn path_through(&self, positions: &[Pos2]) -> Vec<Pos2> {
+        if let Some(stepped) = self.stepped {
+            step_path(positions, stepped)
+        } else if self.draws_curves(positions.len()) {
+            self.collect_curve_points(positions)
+        } else {
+            positions.to_vec()
+        }
+    }
+
+    /// Stroke the line as a single path
     fn draw_line_path(&self, painter: &Painter, positions: &[Pos2]) {
         if positions.len() < 2 {
             return;
         }
-
-        let stroke = Stroke::new(self.width, self.color);
-
-        if self.curved && positions.len() > 2 {
-            // Draw bezier curves
-            self.draw_curved_line(painter, positions, stroke);
-        } else {
-            // Draw straight line segments
-            for i in 0..positions.len() - 1 {
-                painter.line_segment([positions[i], positions[i + 1]], stroke);
-            }
-        }
+        painter.add(self.stroke_shape(self.path_through(positions)));
     }
 
-    /// Draw curved line using quadratic bezier approximation
-    fn draw_curved_line(&self, painter: &Painter, positions: &[Pos2], stroke: Stroke) {
-        let control_points = self.calculate_control_points(positions);
-
-        // Draw bezier curves between each pair of points
-        for i in 0..positions.len() - 1 {
-            let p0 = positions[i];
-            let p1 = positions[i + 1];
-            let (cp1, cp2) = &control_points[i];
-
-            // Approximate cubic bezier with line segments
-            self.draw_cubic_bezier(painter, p0, *cp1, *cp2, p1, stroke);
+    /// Shape stroking `path`, the flattened line through the data points
+    pub(crate) fn stroke_shape(&self, mut path: Vec<Pos2>) -> Shape {
+        if path.len() < 2 {
+            return Shape::Noop;
+        }
+        if self.cap == LineCap::Square {
+            extend_ends(&mut path, self.width / 2.0);
+        }
+        // egui strokes paths with miter joins and butt ends; round ones are
+        // built into a mesh of their own
+        if self.join == LineJoin::Round || self.cap == LineCap::Round {
+            Shape::mesh(stroke_mesh(&path, self.width, self.color, self.join, self.cap))
+        } else {
+            Shape::line(path, Stroke::new(self.width, self.color))
         }
     }
 
@@ -198,37 +328,6 @@ impl LineElement {
         control_points
     }
 
-    /// Draw a cubic bezier curve approximated with line segments
-    fn draw_cubic_bezier(
-        &self,
-        painter: &Painter,
-        p0: Pos2,
-        cp1: Pos2,
-        cp2: Pos2,
-        p1: Pos2,
-        stroke: Stroke,
-    ) {
-        let segments = 16; // Number of line segments to approximate the curve
-        let mut prev = p0;
-
-        for i in 1..=segments {
-            let t = i as f32 / segments as f32;
-            let t2 = t * t;
-            let t3 = t2 * t;
-            let mt = 1.0 - t;
-            let mt2 = mt * mt;
-            let mt3 = mt2 * mt;
-
-            // Cubic bezier formula
-            let x = mt3 * p0.x + 3.0 * mt2 * t * cp1.x + 3.0 * mt * t2 * cp2.x + t3 * p1.x;
-            let y = mt3 * p0.y + 3.0 * mt2 * t * cp1.y + 3.0 * mt * t2 * cp2.y + t3 * p1.y;
-
-            let current = Pos2::new(x, y);
-            painter.line_segment([prev, current], stroke);
-            prev = current;
-        }
-    }
-
     /// Draw filled area under the line
     pub fn draw_fill(&self, painter: &Painter, base_y: f32, fill_color: Color32) {
         if self.points.len() < 2 {
@@ -236,7 +335,9 @@ impl LineElement {
         }
 
         let positions: Vec<Pos2> = self.points.iter().map(|p| p.pos()).collect();
-        self.draw_fill_path(painter, &positions, base_y, fill_color);
+        for segment in self.segments() {
+            self.draw_fill_path(painter, &positions[segment], base_y, fill_color);
+        }
     }
 
     /// Draw filled area with animated Y positions
@@ -251,16 +352,10 @@ impl LineElement {
             return;
         }
 
-        let positions: Vec<Pos2> = self
-            .points
-            .iter()
-            .map(|p| {
-                let animated_y = base_y + (p.y - base_y) * progress;
-                Pos2::new(p.x, animated_y)
-            })
-            .collect();
-
-        self.draw_fill_path(painter, &positions, base_y, fill_color);
+        let positions = self.animated_positions(base_y, progress);
+        for segment in self.segments() {
+            self.draw_fill_path(painter, &positions[segment], base_y, fill_color);
+        }
     }
 
     /// Draw the fill path using triangulation for non-convex shapes
@@ -269,91 +364,293 @@ impl LineElement {
             return;
         }
 
-        // For curved lines, we need to collect all the curve points
-        let curve_points = if self.curved && positions.len() > 2 {
-            self.collect_curve_points(positions)
-        } else {
-            positions.to_vec()
-        };
-
-        // Draw fill as a series of triangles (fan triangulation from baseline)
-        // Each triangle connects: baseline_left, curve_point[i], curve_point[i+1]
-        // Plus vertical strips from each curve point to baseline
-        use egui::epaint::Mesh;
-
-        let mut mesh = Mesh::default();
-
-        // Add vertices for all curve points and their baseline projections
-        for point in &curve_points {
-            // Curve point
-            mesh.vertices.push(egui::epaint::Vertex {
-                pos: *point,
-                uv: egui::epaint::WHITE_UV,
-                color: fill_color,
-            });
-            // Baseline point directly below
-            mesh.vertices.push(egui::epaint::Vertex {
-                pos: Pos2::new(point.x, base_y),
-                uv: egui::epaint::WHITE_UV,
-                color: fill_color,
-            });
-        }
-
-        // Create triangles: for each adjacent pair of points, create 2 triangles
-        // forming a quad from curve to baseline
-        for i in 0..(curve_points.len() - 1) {
-            let top_left = (i * 2) as u32;      // curve point i
-            let bottom_left = (i * 2 + 1) as u32;  // baseline below i
-            let top_right = (i * 2 + 2) as u32; // curve point i+1
-            let bottom_right = (i * 2 + 3) as u32; // baseline below i+1
-
-            // Triangle 1: top_left, bottom_left, top_right
-            mesh.indices.extend([top_left, bottom_left, top_right]);
-            // Triangle 2: top_right, bottom_left, bottom_right
-            mesh.indices.extend([top_right, bottom_left, bottom_right]);
-        }
-
+        // The same points the line is stroked through, so fill and line match
+        let mesh = fill_mesh(&self.path_through(positions), base_y, fill_color);
         painter.add(egui::Shape::mesh(mesh));
     }
 
-    /// Collect all points along the curved line (including bezier interpolation)
+    /// Collect all points along the curved line, subdividing each bezier
+    /// segment only as much as its on-screen curvature needs
     fn collect_curve_points(&self, positions: &[Pos2]) -> Vec<Pos2> {
         if positions.len() < 3 {
             return positions.to_vec();
         }
 
-        let control_points = self.calculate_control_points(positions);
-        let mut all_points = Vec::new();
-        let segments = 16;
+        let control_points = match self.interpolation {
+            CubicInterpolation::Default => self.calculate_control_points(positions),
+            CubicInterpolation::Monotone => monotone_control_points(positions),
+        };
+        let mut all_points = vec![positions[0]];
+        for (i, (cp1, cp2)) in control_points.into_iter().enumerate() {
+            flatten_cubic([positions[i], cp1, cp2, positions[i + 1]], MAX_SUBDIVISIONS, &mut all_points);
+        }
+        all_points
+    }
+}
+
+/// Control points of a monotone cubic through `positions`, which should
+/// not go back along x (Fritsch–Carlson). Tangents are the average of the
+/// slopes either side, flat at local extremes, and scaled down wherever
+/// they would make a segment overshoot its end points.
+fn monotone_control_points(positions: &[Pos2]) -> Vec<(Pos2, Pos2)> {
+    let n = positions.len();
+    let slopes: Vec<f32> = positions
+        .windows(2)
+        .map(|w| {
+            let dx = w[1].x - w[0].x;
+            if dx != 0.0 {
+                (w[1].y - w[0].y) / dx
+            } else {
+                0.0
+            }
+        })
+        .collect();
+
+    let mut tangents: Vec<f32> = (0..n)
+        .map(|i| match i {
+            0 => slopes[0],
+            _ if i == n - 1 => slopes[n - 2],
+            _ if slopes[i - 1] * slopes[i] <= 0.0 => 0.0,
+            _ => (slopes[i - 1] + slopes[i]) / 2.0,
+        })
+        .collect();
+
+    for (i, &slope) in slopes.iter().enumerate() {
+        if slope.abs() < f32::EPSILON {
+            tangents[i] = 0.0;
+            tangents[i + 1] = 0.0;
+            continue;
+        }
+        let (alpha, beta) = (tangents[i] / slope, tangents[i + 1] / slope);
+        let magnitude = alpha * alpha + beta * beta;
+        if magnitude > 9.0 {
+            let tau = 3.0 / magnitude.sqrt();
+            tangents[i] = alpha * tau * slope;
+            tangents[i + 1] = beta * tau * slope;
+        }
+    }
 
-        for i in 0..positions.len() - 1 {
-            let p0 = positions[i];
-            let p1 = positions[i + 1];
-            let (cp1, cp2) = &control_points[i];
+    positions
+        .windows(2)
+        .enumerate()
+        .map(|(i, w)| {
+            let third = (w[1].x - w[0].x) / 3.0;
+            (
+                Pos2::new(w[0].x + third, w[0].y + tangents[i] * third),
+                Pos2::new(w[1].x - third, w[1].y - tangents[i + 1] * third),
+            )
+        })
+        .collect()
+}
 
-            // Add start point (only for first segment)
-            if i == 0 {
-                all_points.push(p0);
+/// Corners of a stepped line through `positions`
+fn step_path(positions: &[Pos2], stepped: Stepped) -> Vec<Pos2> {
+    let Some(&first) = positions.first() else { return Vec::new() };
+    let mut path = vec![first];
+    for w in positions.windows(2) {
+        let (from, to) = (w[0], w[1]);
+        match stepped {
+            Stepped::Before => path.push(Pos2::new(to.x, from.y)),
+            Stepped::After => path.push(Pos2::new(from.x, to.y)),
+            Stepped::Middle => {
+                let mid = (from.x + to.x) / 2.0;
+                path.push(Pos2::new(mid, from.y));
+                path.push(Pos2::new(mid, to.y));
             }
+        }
+        path.push(to);
+    }
+    path
+}
+
+/// Mesh filling the area between `curve_points` and `base_y`, as vertical
+/// strips from each point down to the baseline
+pub(crate) fn fill_mesh(curve_points: &[Pos2], base_y: f32, fill_color: Color32) -> egui::Mesh {
+    let mut mesh = egui::Mesh::default();
+    if curve_points.len() < 2 {
+        return mesh;
+    }
 
-            // Add bezier interpolation points
-            for j in 1..=segments {
-                let t = j as f32 / segments as f32;
-                let t2 = t * t;
-                let t3 = t2 * t;
-                let mt = 1.0 - t;
-                let mt2 = mt * mt;
-                let mt3 = mt2 * mt;
+    // Each curve point and the baseline point directly below it
+    for point in curve_points {
+        mesh.colored_vertex(*point, fill_color);
+        mesh.colored_vertex(Pos2::new(point.x, base_y), fill_color);
+    }
 
-                let x = mt3 * p0.x + 3.0 * mt2 * t * cp1.x + 3.0 * mt * t2 * cp2.x + t3 * p1.x;
-                let y = mt3 * p0.y + 3.0 * mt2 * t * cp1.y + 3.0 * mt * t2 * cp2.y + t3 * p1.y;
+    // Two triangles forming a quad from the curve to the baseline between
+    // each adjacent pair of points
+    for i in 0..(curve_points.len() - 1) as u32 {
+        let (top_left, bottom_left) = (i * 2, i * 2 + 1);
+        let (top_right, bottom_right) = (i * 2 + 2, i * 2 + 3);
+        mesh.add_triangle(top_left, bottom_left, top_right);
+        mesh.add_triangle(top_right, bottom_left, bottom_right);
+    }
+    mesh
+}
+
+/// Append points approximating a cubic bezier, excluding its start, by
+/// splitting it in half until each piece is flat to within [`FLATNESS`]
+fn flatten_cubic(curve: [Pos2; 4], depth: u32, out: &mut Vec<Pos2>) {
+    let [p0, cp1, cp2, p1] = curve;
+    let flat = distance_to_line(cp1, p0, p1).max(distance_to_line(cp2, p0, p1)) <= FLATNESS;
+    if flat || depth == 0 {
+        out.push(p1);
+        return;
+    }
 
-                all_points.push(Pos2::new(x, y));
+    // de Casteljau split at t = 0.5
+    let a = p0.lerp(cp1, 0.5);
+    let b = cp1.lerp(cp2, 0.5);
+    let c = cp2.lerp(p1, 0.5);
+    let ab = a.lerp(b, 0.5);
+    let bc = b.lerp(c, 0.5);
+    let mid = ab.lerp(bc, 0.5);
+    flatten_cubic([p0, a, ab, mid], depth - 1, out);
+    flatten_cubic([mid, bc, c, p1], depth - 1, out);
+}
+
+/// Distance from `point` to the line through `a` and `b`
+fn distance_to_line(point: Pos2, a: Pos2, b: Pos2) -> f32 {
+    let dir = b - a;
+    let length = dir.length();
+    if length <= f32::EPSILON {
+        return (point - a).length();
+    }
+    let offset = point - a;
+    (offset.x * dir.y - offset.y * dir.x).abs() / length
+}
+
+/// Cross-section of a stroke mesh: a point on each edge of the outline, with
+/// the direction out of the stroke there
+#[derive(Clone, Copy, Debug)]
+struct Rib {
+    left: (Pos2, Vec2),
+    right: (Pos2, Vec2),
+}
+
+/// Left-hand normal of a direction
+fn left_normal(dir: Vec2) -> Vec2 {
+    Vec2::new(dir.y, -dir.x)
+}
+
+/// Mesh stroking `path` with its joins and caps as part of the outline, so
+/// each pixel is covered once and translucent lines blend evenly
+pub(crate) fn stroke_mesh(path: &[Pos2], width: f32, color: Color32, join: LineJoin, cap: LineCap) -> Mesh {
+    let mut mesh = Mesh::default();
+    let mut path = path.to_vec();
+    path.dedup_by(|a, b| (*a - *b).length_sq() < 1e-6);
+    if path.len() < 2 {
+        return mesh;
+    }
+
+    // Lines thinner than the faded edge are drawn that wide, but fainter
+    let (radius, color) = if width < FEATHER {
+        (FEATHER / 2.0, color.gamma_multiply(width / FEATHER))
+    } else {
+        (width / 2.0, color)
+    };
+
+    let n = path.len();
+    let dirs: Vec<Vec2> = path.windows(2).map(|w| (w[1] - w[0]).normalized()).collect();
+    let lengths: Vec<f32> = path.windows(2).map(|w| (w[1] - w[0]).length()).collect();
+    let mut ribs = cap_ribs(path[0], -dirs[0], radius, cap, true);
+    for i in 1..n - 1 {
+        ribs.extend(join_ribs(path[i], (dirs[i - 1], dirs[i]), lengths[i - 1].min(lengths[i]), radius, join));
+    }
+    ribs.extend(cap_ribs(path[n - 1], dirs[n - 2], radius, cap, false));
+
+    // Each rib is four vertices across: faded left edge, solid left and
+    // right edges and faded right edge, joined to the next rib by quads
+    for rib in &ribs {
+        let ((left, left_out), (right, right_out)) = (rib.left, rib.right);
+        mesh.colored_vertex(left + left_out * FEATHER / 2.0, Color32::TRANSPARENT);
+        mesh.colored_vertex(left - left_out * FEATHER / 2.0, color);
+        mesh.colored_vertex(right - right_out * FEATHER / 2.0, color);
+        mesh.colored_vertex(right + right_out * FEATHER / 2.0, Color32::TRANSPARENT);
+    }
+    for i in 0..ribs.len() as u32 - 1 {
+        for k in 0..3 {
+            let (a, b) = (i * 4 + k, (i + 1) * 4 + k);
+            mesh.add_triangle(a, a + 1, b);
+            mesh.add_triangle(a + 1, b + 1, b);
+        }
+    }
+    mesh
+}
+
+/// Ribs across the end of a line at `center`, where `out` points away from
+/// the line, from the outermost in at the start and outwards at the end
+fn cap_ribs(center: Pos2, out: Vec2, radius: f32, cap: LineCap, start: bool) -> Vec<Rib> {
+    // Left of the direction the line is drawn in
+    let normal = left_normal(if start { -out } else { out });
+    let steps = if cap == LineCap::Round { (FRAC_PI_2 / ROUND_STEP).ceil() as usize } else { 0 };
+    (0..=steps)
+        .map(|i| {
+            let i = if start { steps - i } else { i };
+            let angle = if steps == 0 { 0.0 } else { FRAC_PI_2 * i as f32 / steps as f32 };
+            let (left, right) = (normal * angle.cos() + out * angle.sin(), -normal * angle.cos() + out * angle.sin());
+            Rib {
+                left: (center + left * radius, left),
+                right: (center + right * radius, right),
             }
+        })
+        .collect()
+}
+
+/// Ribs around the corner at `corner` between segments going in `dirs`, the
+/// shorter of them `shortest` long: an arc or miter on the outside, all
+/// sharing one point on the inside
+fn join_ribs(corner: Pos2, dirs: (Vec2, Vec2), shortest: f32, radius: f32, join: LineJoin) -> Vec<Rib> {
+    let (dir_in, dir_out) = dirs;
+    let (normal_in, normal_out) = (left_normal(dir_in), left_normal(dir_out));
+    if dir_in.dot(dir_out) > 1.0 - 1e-6 {
+        return vec![Rib {
+            left: (corner + normal_in * radius, normal_in),
+            right: (corner - normal_in * radius, -normal_in),
+        }];
+    }
+
+    // Normals on the outside of the turn, and the direction halfway between
+    let left_outside = dir_out.dot(normal_in) < 0.0;
+    let side = if left_outside { 1.0 } else { -1.0 };
+    let (from, to) = (normal_in * side, normal_out * side);
+    let middle = if (from + to).length_sq() > 1e-6 { (from + to).normalized() } else { dir_in };
+    let cos_half = middle.dot(from).max(1e-3);
+
+    // The inner edges meet at a point, kept within reach of the shorter segment
+    let inner_len = (radius / cos_half).min((radius * radius + shortest * shortest).sqrt());
+    let inside = (corner - middle * inner_len, -middle);
+
+    let outside: Vec<(Pos2, Vec2)> = match join {
+        LineJoin::Round => {
+            let turn = (from.x * to.y - from.y * to.x).atan2(from.dot(to));
+            let steps = (turn.abs() / ROUND_STEP).ceil().max(1.0) as usize;
+            (0..=steps)
+                .map(|i| {
+                    let normal = Vec2::angled(from.angle() + turn * i as f32 / steps as f32);
+                    (corner + normal * radius, normal)
+                })
+                .collect()
         }
+        LineJoin::Miter if 1.0 / cos_half <= MITER_LIMIT => vec![(corner + middle * (radius / cos_half), middle)],
+        LineJoin::Miter => vec![(corner + from * radius, from), (corner + to * radius, to)],
+    };
+    outside
+        .into_iter()
+        .map(|edge| if left_outside { Rib { left: edge, right: inside } } else { Rib { left: inside, right: edge } })
+        .collect()
+}
 
-        all_points
+/// Lengthen a path by `amount` at both ends, along its end segments
+fn extend_ends(path: &mut [Pos2], amount: f32) {
+    let n = path.len();
+    if n < 2 {
+        return;
     }
+    let start_dir = (path[0] - path[1]).normalized();
+    let end_dir = (path[n - 1] - path[n - 2]).normalized();
+    path[0] += start_dir * amount;
+    path[n - 1] += end_dir * amount;
 }
 
 /// Style configuration for line charts
@@ -375,10 +672,23 @@ pub struct LineStyle {
     pub curved: bool,
     /// Curve tension (0.0-1.0)
     pub tension: f32,
+    /// How curves bend between points
+    pub interpolation: CubicInterpolation,
+    /// Draw steps instead of a straight or curved line
+    pub stepped: Option<Stepped>,
+    /// Line ends
+    pub cap: LineCap,
+    /// Corners between straight segments
+    pub join: LineJoin,
+    /// Connect the line across missing points when the points either side
+    /// are at most this far apart along x (`None`: break at every gap)
+    pub span_gaps: Option<f64>,
     /// Whether to fill area under line
     pub fill: bool,
     /// Fill color (with alpha for transparency)
     pub fill_color: Option<Color32>,
+    /// Appearance of hovered points
+    pub hover: HoverStyle,
 }
 
 impl Default for LineStyle {
@@ -392,8 +702,14 @@ impl Default for LineStyle {
             show_points: true,
             curved: true,
             tension: 0.4,
+            interpolation: CubicInterpolation::default(),
+            stepped: None,
+            cap: LineCap::default(),
+            join: LineJoin::default(),
+            span_gaps: None,
             fill: false,
             fill_color: None,
+            hover: HoverStyle::default(),
         }
     }
 }
@@ -442,4 +758,154 @@ mod tests {
 
         assert_eq!(control_points.len(), 2); // n-1 control point pairs
     }
+
+    #[test]
+    fn test_flatten_adapts_to_curvature() {
+        // A straight "curve" needs no subdivision
+        let mut straight = Vec::new();
+        let line = [Pos2::new(0.0, 0.0), Pos2::new(10.0, 0.0), Pos2::new(20.0, 0.0), Pos2::new(30.0, 0.0)];
+        flatten_cubic(line, MAX_SUBDIVISIONS, &mut straight);
+        assert_eq!(straight, vec![Pos2::new(30.0, 0.0)]);
+
+        // A tight bend gets more points than a gentle one, all within the tolerance
+        let bend = |height: f32| {
+            let mut points = Vec::new();
+            let curve = [Pos2::ZERO, Pos2::new(0.0, height), Pos2::new(100.0, height), Pos2::new(100.0, 0.0)];
+            flatten_cubic(curve, MAX_SUBDIVISIONS, &mut points);
+            points
+        };
+        let (gentle, tight) = (bend(5.0), bend(200.0));
+        assert!(tight.len() > gentle.len());
+        assert_eq!(tight.last(), Some(&Pos2::new(100.0, 0.0)));
+        // Peak of the tight curve: y = 0.75 * height at t = 0.5
+        let peak = tight.iter().map(|p| p.y).fold(0.0, f32::max);
+        assert!((peak - 150.0).abs() <= FLATNESS);
+    }
+
+    #[test]
+    fn test_fill_and_line_share_path() {
+        let points = vec![
+            PointElement::new(0.0, 100.0),
+            PointElement::new(50.0, 50.0),
+            PointElement::new(100.0, 75.0),
+        ];
+        let mut line = LineElement::new(points);
+        let path = line.path();
+        assert_eq!(path.first(), Some(&Pos2::new(0.0, 100.0)));
+        assert_eq!(path.last(), Some(&Pos2::new(100.0, 75.0)));
+        assert!(path.len() > 3);
+
+        line.curved = false;
+        assert_eq!(line.path().len(), 3);
+    }
+
+    #[test]
+    fn test_breaks_split_segments() {
+        let points = (0..5).map(|i| PointElement::new(i as f32 * 10.0, 50.0)).collect();
+        let mut line = LineElement::new(points);
+        line.curved = false;
+        assert_eq!(line.segments().collect::<Vec<_>>(), vec![0..5]);
+
+        // A lone point after a break draws no line
+        line.breaks = vec![2, 4];
+        assert_eq!(line.segments().collect::<Vec<_>>(), vec![0..2, 2..4, 4..5]);
+        let strokes = line.shapes(None).iter().filter(|s| matches!(s, Shape::Path(_))).count();
+        assert_eq!(strokes, 2);
+    }
+
+    #[test]
+    fn test_monotone_never_overshoots() {
+        // Flat, a drop, then flat again
+        let points = vec![
+            PointElement::new(0.0, 100.0),
+            PointElement::new(10.0, 100.0),
+            PointElement::new(20.0, 0.0),
+            PointElement::new(30.0, 0.0),
+        ];
+        let mut line = LineElement::new(points);
+        let overshoot = |path: Vec<Pos2>| path.iter().any(|p| p.y > 100.0 + 1e-3 || p.y < -1e-3);
+        assert!(overshoot(line.path()));
+
+        line.interpolation = CubicInterpolation::Monotone;
+        let path = line.path();
+        assert!(!overshoot(path.clone()));
+        assert!(path.len() > 4);
+        assert!(path.windows(2).all(|w| w[1].y <= w[0].y + 1e-3));
+    }
+
+    #[test]
+    fn test_step_paths() {
+        let positions = [Pos2::new(0.0, 0.0), Pos2::new(10.0, 10.0)];
+        assert_eq!(step_path(&positions, Stepped::Before)[1], Pos2::new(10.0, 0.0));
+        assert_eq!(step_path(&positions, Stepped::After)[1], Pos2::new(0.0, 10.0));
+        assert_eq!(
+            step_path(&positions, Stepped::Middle),
+            vec![positions[0], Pos2::new(5.0, 0.0), Pos2::new(5.0, 10.0), positions[1]]
+        );
+
+        // Steps replace curves, and have round corners
+        let points = (0..3).map(|i| PointElement::new(i as f32 * 10.0, i as f32)).collect();
+        let mut line = LineElement::new(points);
+        line.stepped = Some(Stepped::Before);
+        line.join = LineJoin::Round;
+        assert_eq!(line.path().len(), 5);
+        assert!(matches!(line.shapes(None)[..], [Shape::Mesh(_)]));
+    }
+
+    /// Number of triangles of `mesh` that `point` lies inside
+    fn coverage(mesh: &Mesh, point: Pos2) -> usize {
+        let cross = |a: Pos2, b: Pos2| (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x);
+        mesh.indices
+            .chunks(3)
+            .filter(|t| {
+                let [a, b, c] = [0, 1, 2].map(|k| mesh.vertices[t[k] as usize].pos);
+                let sides = [cross(a, b), cross(b, c), cross(c, a)];
+                sides.iter().all(|s| *s > 1e-4) || sides.iter().all(|s| *s < -1e-4)
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_round_joins_and_caps_cover_once() {
+        // Right, then down, 10 wide
+        let path = vec![Pos2::new(0.0, 0.0), Pos2::new(20.0, 0.0), Pos2::new(20.0, 20.0)];
+        let mut line = LineElement::new(Vec::new());
+        line.width = 10.0;
+        line.color = Color32::from_rgba_unmultiplied(200, 0, 0, 128);
+        line.join = LineJoin::Round;
+        line.cap = LineCap::Round;
+        let Shape::Mesh(mesh) = line.stroke_shape(path.clone()) else { panic!("round strokes are meshes") };
+
+        // The cap, the corner on both sides and the segments, each covered once
+        for point in [(-3.1, 2.2), (-4.1, -1.3), (10.3, 2.7), (23.1, -2.9), (17.3, 3.1), (21.7, 23.3)] {
+            assert_eq!(coverage(&mesh, Pos2::from(point)), 1, "{point:?}");
+        }
+        // Square ends and miter corners would reach these
+        for point in [(-4.9, -4.6), (24.2, -4.1), (-5.6, 0.2)] {
+            assert_eq!(coverage(&mesh, Pos2::from(point)), 0, "{point:?}");
+        }
+        // Only the faded edge is transparent
+        assert!(mesh.vertices.iter().all(|v| v.color == line.color || v.color == Color32::TRANSPARENT));
+
+        // Miter corners with round ends
+        line.join = LineJoin::Miter;
+        let Shape::Mesh(mesh) = line.stroke_shape(path.clone()) else { panic!("round strokes are meshes") };
+        assert_eq!(coverage(&mesh, Pos2::new(24.2, -4.1)), 1);
+        assert_eq!(coverage(&mesh, Pos2::new(-3.1, 2.2)), 1);
+
+        // Butt ends stop at the end points
+        line.join = LineJoin::Round;
+        line.cap = LineCap::Butt;
+        let Shape::Mesh(mesh) = line.stroke_shape(path) else { panic!("round strokes are meshes") };
+        assert_eq!(coverage(&mesh, Pos2::new(-3.1, 2.2)), 0);
+        assert_eq!(coverage(&mesh, Pos2::new(3.1, 2.2)), 1);
+    }
+
+    #[test]
+    fn test_extend_ends() {
+        let mut path = [Pos2::new(0.0, 0.0), Pos2::new(10.0, 0.0), Pos2::new(10.0, 10.0)];
+        extend_ends(&mut path, 2.0);
+        assert_eq!(path[0], Pos2::new(-2.0, 0.0));
+        assert_eq!(path[2], Pos2::new(10.0, 12.0));
+    }
 }
@@ -3,5 +3,5 @@ pub mod line;
 pub mod arc;
 
 pub use bar::{BarElement, BarStyle};
-pub use line::{LineElement, LineStyle, PointElement};
+pub use line::{CubicInterpolation, LineCap, LineElement, LineJoin, LineStyle, PointElement, Stepped};
 pub use arc::{ArcElement, PieStyle};
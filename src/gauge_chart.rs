@@ -0,0 +1,445 @@
+use egui::{Align2, Color32, CornerRadius, FontId, Id, Pos2, Rect, Response, Sense, Shape, Stroke, Ui, Vec2, Widget};
+use std::f32::consts::PI;
+
+use crate::animation::{update_chart_animation, AnimationConfig};
+use crate::data::is_missing;
+use crate::elements::arc::{sweep_bounds, ArcElement};
+use crate::helpers::color::ChartColor;
+use crate::theme::{ChartTheme, ThemePreset};
+use crate::tooltip::{calculate_tooltip_position, draw_tooltip, measure_tooltip_size, TooltipContent};
+
+/// Room below the gauge for the value and min/max labels
+const LABEL_SPACE: f32 = 28.0;
+
+/// Memory stored in egui context between frames
+#[derive(Clone, Default)]
+struct GaugeChartMemory {
+    /// Value the needle is animating towards
+    target: f64,
+    /// Value the needle started from
+    from: f64,
+    /// Value shown last frame
+    displayed: f64,
+}
+
+impl GaugeChartMemory {
+    /// Aim the needle at `value`, from where it is now. Missing values
+    /// leave it where it was. Returns whether the target changed.
+    fn retarget(&mut self, value: f64) -> bool {
+        if is_missing(value) || value == self.target {
+            return false;
+        }
+        self.from = if self.displayed.is_finite() { self.displayed } else { value };
+        self.target = value;
+        true
+    }
+
+    /// Move the needle `progress` of the way from where it started
+    fn advance(&mut self, progress: f32) {
+        self.displayed = self.from + (self.target - self.from) * progress as f64;
+    }
+}
+
+/// Response returned after showing the gauge
+#[derive(Clone, Debug)]
+pub struct GaugeChartResponse {
+    /// The egui Response for the chart area
+    pub response: Response,
+    /// Index of the hovered band
+    pub hovered_band: Option<usize>,
+    /// Index of the clicked band (if any this frame)
+    pub clicked_band: Option<usize>,
+}
+
+/// Half-donut gauge with a needle, min/max labels and colored bands
+///
+/// ```rust,ignore
+/// GaugeChart::new()
+///     .value(72.0)
+///     .range(0.0, 100.0)
+///     .band(60.0, "#4bc0c0")
+///     .band(85.0, "#ffce56")
+///     .band(100.0, "#ff6384")
+///     .label("CPU %")
+///     .show(ui);
+/// ```
+#[derive(Clone)]
+pub struct GaugeChart {
+    id: Option<Id>,
+    value: f64,
+    min: f64,
+    max: f64,
+    bands: Vec<(f64, ChartColor)>,
+    color: ChartColor,
+    thickness: f32,
+    start_angle: f32,
+    circumference: f32,
+    needle: bool,
+    needle_color: Option<Color32>,
+    show_value: bool,
+    show_min_max: bool,
+    label: Option<String>,
+    animation: AnimationConfig,
+    tooltip_enabled: bool,
+    theme: ChartTheme,
+    size: Option<Vec2>,
+}
+
+impl Default for GaugeChart {
+    fn default() -> Self {
+        Self {
+            id: None,
+            value: 0.0,
+            min: 0.0,
+            max: 100.0,
+            bands: Vec::new(),
+            color: ChartColor::Rgba(Color32::from_rgb(54, 162, 235)),
+            thickness: 0.35,
+            start_angle: PI,
+            circumference: PI,
+            needle: true,
+            needle_color: None,
+            show_value: true,
+            show_min_max: true,
+            label: None,
+            animation: AnimationConfig::default(),
+            tooltip_enabled: true,
+            theme: ChartTheme::default(),
+            size: None,
+        }
+    }
+}
+
+impl GaugeChart {
+    /// Create a new gauge, a 180° arc from 0 to 100
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set unique ID for this chart instance
+    pub fn id(mut self, id: impl Into<Id>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set the value the needle points at
+    pub fn value(mut self, value: impl Into<f64>) -> Self {
+        self.value = value.into();
+        self
+    }
+
+    /// Set the values at the start and end of the arc
+    pub fn range(mut self, min: impl Into<f64>, max: impl Into<f64>) -> Self {
+        self.min = min.into();
+        self.max = max.into();
+        self
+    }
+
+    /// Add a colored band from the previous band's end (or the minimum) up to `to`
+    pub fn band(mut self, to: impl Into<f64>, color: impl Into<ChartColor>) -> Self {
+        self.bands.push((to.into(), color.into()));
+        self
+    }
+
+    /// Color of the filled part of the arc when there are no bands
+    pub fn color(mut self, color: impl Into<ChartColor>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Set the arc thickness as a fraction of its radius
+    pub fn thickness(mut self, ratio: f32) -> Self {
+        self.thickness = ratio.clamp(0.05, 1.0);
+        self
+    }
+
+    /// Set where the arc starts, in degrees clockwise from the top (default: -90, the left)
+    pub fn rotation(mut self, degrees: f32) -> Self {
+        self.start_angle = (degrees - 90.0).to_radians();
+        self
+    }
+
+    /// Set the angle the arc covers, in degrees (default: 180)
+    pub fn circumference(mut self, degrees: f32) -> Self {
+        self.circumference = degrees.clamp(1.0, 360.0).to_radians();
+        self
+    }
+
+    /// Show/hide the needle
+    pub fn needle(mut self, show: bool) -> Self {
+        self.needle = show;
+        self
+    }
+
+    /// Set needle color (defaults to the theme's text color)
+    pub fn needle_color(mut self, color: impl Into<ChartColor>) -> Self {
+        self.needle_color = Some(color.into().to_color32());
+        self
+    }
+
+    /// Show/hide the value below the needle
+    pub fn show_value(mut self, show: bool) -> Self {
+        self.show_value = show;
+        self
+    }
+
+    /// Show/hide the min/max labels at the ends of the arc
+    pub fn show_min_max(mut self, show: bool) -> Self {
+        self.show_min_max = show;
+        self
+    }
+
+    /// Set a label shown with the value (e.g. a unit)
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Configure the needle animation
+    pub fn animate(mut self, config: AnimationConfig) -> Self {
+        self.animation = config;
+        self
+    }
+
+    /// Enable/disable tooltips
+    pub fn tooltip(mut self, enabled: bool) -> Self {
+        self.tooltip_enabled = enabled;
+        self
+    }
+
+    /// Set theme
+    pub fn theme(mut self, theme: impl Into<ChartTheme>) -> Self {
+        self.theme = theme.into();
+        self
+    }
+
+    /// Use theme preset
+    pub fn theme_preset(mut self, preset: ThemePreset) -> Self {
+        self.theme = preset.to_theme();
+        self
+    }
+
+    /// Set fixed size
+    pub fn size(mut self, size: impl Into<Vec2>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+
+    /// Show the gauge and return response
+    pub fn show(self, ui: &mut Ui) -> GaugeChartResponse {
+        let size = self.size.unwrap_or_else(|| {
+            let width = ui.available_width().clamp(100.0, 300.0);
+            Vec2::new(width, width * 0.6)
+        });
+        let (response, painter) = ui.allocate_painter(size, Sense::click());
+        let rect = response.rect;
+        let id = self.id.unwrap_or_else(|| ui.make_persistent_id("gauge_chart"));
+
+        // Animate the needle from wherever it was towards the new value
+        let mut memory = ui
+            .ctx()
+            .data_mut(|d| d.get_temp_mut_or_insert_with::<GaugeChartMemory>(id, Default::default).clone());
+        let value_changed = memory.retarget(self.value);
+        let animation = update_chart_animation(ui, id, &self.animation, value_changed, 1);
+        memory.advance(animation.progress());
+
+        if self.theme.background_color != Color32::TRANSPARENT {
+            painter.rect_filled(rect, CornerRadius::ZERO, self.theme.background_color);
+        }
+
+        // Fit the arc, and its center for the needle pivot, above the labels
+        let inner_ratio = 1.0 - self.thickness;
+        let bounds = sweep_bounds(self.start_angle, self.circumference, inner_ratio).union(Rect::from_pos(Pos2::ZERO));
+        let area = Rect::from_min_max(rect.min + Vec2::splat(10.0), rect.max - Vec2::new(10.0, 10.0 + LABEL_SPACE));
+        let outer_radius = (area.width() / bounds.width())
+            .min(area.height() / bounds.height())
+            .max(10.0);
+        let inner_radius = outer_radius * inner_ratio;
+        let center = area.center() - bounds.center().to_vec2() * outer_radius;
+
+        // Track, then bands or the filled value on top
+        let end_angle = self.start_angle + self.circumference;
+        let mut track = ArcElement::new(center, inner_radius, outer_radius, self.start_angle, end_angle);
+        track.fill_color = self.theme.grid_color;
+        track.border_width = 0.0;
+        track.draw(&painter);
+
+        let bands = self.band_arcs(center, inner_radius, outer_radius);
+        if bands.is_empty() {
+            let mut fill = track.clone();
+            fill.end_angle = self.angle_of(memory.displayed);
+            fill.fill_color = self.color.to_color32();
+            fill.draw(&painter);
+        }
+        for band in &bands {
+            band.draw(&painter);
+        }
+
+        let font_id = FontId::proportional(11.0);
+        if self.show_min_max {
+            for (value, angle) in [(self.min, self.start_angle), (self.max, end_angle)] {
+                let mid = (inner_radius + outer_radius) / 2.0;
+                let pos = center + Vec2::angled(angle) * mid + Vec2::new(0.0, 4.0);
+                painter.text(pos, Align2::CENTER_TOP, format_value(value), font_id.clone(), self.theme.text_color);
+            }
+        }
+
+        if self.needle {
+            self.draw_needle(&painter, center, inner_radius, outer_radius, memory.displayed);
+        }
+
+        if self.show_value {
+            let text = match &self.label {
+                Some(label) => format!("{} {}", format_value(memory.displayed), label),
+                None => format_value(memory.displayed),
+            };
+            painter.text(
+                center + Vec2::new(0.0, 10.0),
+                Align2::CENTER_TOP,
+                text,
+                FontId::proportional(16.0),
+                self.theme.text_color,
+            );
+        }
+
+        let band_at = |pos: Pos2| bands.iter().position(|band| band.contains(pos));
+        let hovered_band = response.hover_pos().and_then(band_at);
+        let clicked_band = response
+            .interact_pointer_pos()
+            .filter(|_| response.clicked())
+            .and_then(band_at);
+
+        // Tooltip with the value when hovering the arc
+        if let Some(pos) = response.hover_pos().filter(|_| self.tooltip_enabled) {
+            if track.contains(pos) {
+                let content = TooltipContent {
+                    title: None,
+                    label: self.label.clone().unwrap_or_else(|| "Value".to_string()),
+                    value: format_value(self.value),
+                    color: hovered_band
+                        .map(|i| bands[i].fill_color)
+                        .unwrap_or_else(|| self.color.to_color32()),
+                };
+                let tooltip_size = measure_tooltip_size(&painter, &content, &self.theme.tooltip);
+                let tooltip_pos = calculate_tooltip_position(pos, tooltip_size, rect);
+                draw_tooltip(&painter, &content, tooltip_pos, &self.theme.tooltip);
+            }
+        }
+
+        ui.ctx().data_mut(|d| d.insert_temp(id, memory));
+        animation.store(ui.ctx(), id);
+
+        GaugeChartResponse {
+            response,
+            hovered_band,
+            clicked_band,
+        }
+    }
+
+    /// Angle of `value` along the arc, clamped to its ends
+    fn angle_of(&self, value: f64) -> f32 {
+        let span = self.max - self.min;
+        let t = if span != 0.0 { ((value - self.min) / span).clamp(0.0, 1.0) } else { 0.0 };
+        self.start_angle + self.circumference * t as f32
+    }
+
+    /// One arc per band, each starting where the previous one ended
+    fn band_arcs(&self, center: Pos2, inner_radius: f32, outer_radius: f32) -> Vec<ArcElement> {
+        let mut from = self.min;
+        self.bands
+            .iter()
+            .map(|(to, color)| {
+                let mut arc = ArcElement::new(center, inner_radius, outer_radius, self.angle_of(from), self.angle_of(*to));
+                arc.fill_color = color.to_color32();
+                arc.border_width = 0.0;
+                from = *to;
+                arc
+            })
+            .collect()
+    }
+
+    fn draw_needle(&self, painter: &egui::Painter, center: Pos2, inner_radius: f32, outer_radius: f32, value: f64) {
+        let color = self.needle_color.unwrap_or(self.theme.text_color);
+        let dir = Vec2::angled(self.angle_of(value));
+        let normal = dir.rot90();
+        let tip = center + dir * (inner_radius + outer_radius) / 2.0;
+        let base_width = (outer_radius * 0.04).max(3.0);
+        painter.add(Shape::convex_polygon(
+            vec![center + normal * base_width, tip, center - normal * base_width],
+            color,
+            Stroke::NONE,
+        ));
+        painter.circle_filled(center, base_width * 1.6, color);
+    }
+}
+
+impl Widget for GaugeChart {
+    fn ui(self, ui: &mut Ui) -> Response {
+        self.show(ui).response
+    }
+}
+
+/// Format a value for display
+fn format_value(value: f64) -> String {
+    if value.abs() >= 1_000_000.0 {
+        format!("{:.1}M", value / 1_000_000.0)
+    } else if value.abs() >= 1_000.0 {
+        format!("{:.1}K", value / 1_000.0)
+    } else if value.fract().abs() < 0.001 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_angle_of_clamps_to_arc() {
+        let gauge = GaugeChart::new().range(0.0, 200.0);
+        assert_eq!(gauge.angle_of(0.0), PI);
+        assert!((gauge.angle_of(100.0) - 1.5 * PI).abs() < 1e-5);
+        assert_eq!(gauge.angle_of(500.0), 2.0 * PI);
+        assert_eq!(gauge.angle_of(-5.0), PI);
+    }
+
+    #[test]
+    fn test_bands_follow_each_other() {
+        let gauge = GaugeChart::new().band(60.0, "#4bc0c0").band(100.0, "#ff6384");
+        let bands = gauge.band_arcs(Pos2::ZERO, 30.0, 50.0);
+        assert_eq!(bands.len(), 2);
+        assert_eq!(bands[0].start_angle, PI);
+        assert_eq!(bands[0].end_angle, bands[1].start_angle);
+        assert_eq!(bands[1].end_angle, 2.0 * PI);
+
+        // Hit testing follows the partial sweeps
+        assert_eq!(bands.iter().position(|b| b.contains(Pos2::new(-40.0, -1.0))), Some(0));
+        assert_eq!(bands.iter().position(|b| b.contains(Pos2::new(40.0, -1.0))), Some(1));
+        assert_eq!(bands.iter().position(|b| b.contains(Pos2::new(0.0, 40.0))), None);
+    }
+
+    #[test]
+    fn test_missing_values_keep_the_needle() {
+        let mut memory = GaugeChartMemory::default();
+        assert!(memory.retarget(50.0));
+        memory.advance(1.0);
+
+        // A NaN neither restarts the animation nor moves the needle
+        assert!(!memory.retarget(f64::NAN));
+        memory.advance(1.0);
+        assert_eq!(memory.displayed, 50.0);
+
+        // The next real value animates from where the needle was
+        assert!(memory.retarget(80.0));
+        memory.advance(0.5);
+        assert_eq!(memory.displayed, 65.0);
+
+        // A needle left at NaN jumps to the next real value
+        memory.displayed = f64::NAN;
+        assert!(memory.retarget(20.0));
+        memory.advance(0.5);
+        assert_eq!(memory.displayed, 20.0);
+    }
+}
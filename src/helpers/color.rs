@@ -105,10 +105,103 @@ pub fn darken(color: Color32, factor: f32) -> Color32 {
     )
 }
 
+/// Interpolate between two colors in the OKLab perceptual color space
+///
+/// Produces even-looking transitions without the muddy midpoints of naive
+/// sRGB blending. Alpha is interpolated linearly.
+pub fn lerp_color(from: Color32, to: Color32, t: f32) -> Color32 {
+    let t = t.clamp(0.0, 1.0);
+    if t <= 0.0 {
+        return from;
+    }
+    if t >= 1.0 {
+        return to;
+    }
+
+    let [r0, g0, b0, a0] = from.to_srgba_unmultiplied();
+    let [r1, g1, b1, a1] = to.to_srgba_unmultiplied();
+
+    let lab0 = srgb_to_oklab([r0, g0, b0]);
+    let lab1 = srgb_to_oklab([r1, g1, b1]);
+    let lab = [
+        lab0[0] + (lab1[0] - lab0[0]) * t,
+        lab0[1] + (lab1[1] - lab0[1]) * t,
+        lab0[2] + (lab1[2] - lab0[2]) * t,
+    ];
+    let [r, g, b] = oklab_to_srgb(lab);
+    let a = a0 as f32 + (a1 as f32 - a0 as f32) * t;
+
+    Color32::from_rgba_unmultiplied(r, g, b, a.round() as u8)
+}
+
+/// Black or white, whichever reads better on `background`
+pub fn contrast_text_color(background: Color32) -> Color32 {
+    let [r, g, b, _] = background.to_srgba_unmultiplied();
+    if srgb_to_oklab([r, g, b])[0] > 0.7 {
+        Color32::from_gray(30)
+    } else {
+        Color32::WHITE
+    }
+}
+
+fn srgb_to_linear(c: u8) -> f32 {
+    let c = c as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let s = if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (s * 255.0).round() as u8
+}
+
+/// Convert sRGB to OKLab (Björn Ottosson's reference matrices)
+fn srgb_to_oklab([r, g, b]: [u8; 3]) -> [f32; 3] {
+    let (r, g, b) = (srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b));
+
+    let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+/// Convert OKLab back to sRGB
+fn oklab_to_srgb([l, a, b]: [f32; 3]) -> [u8; 3] {
+    let l_ = (l + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+    let m_ = (l - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+    let s_ = (l - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+
+    [
+        linear_to_srgb(4.076_741_7 * l_ - 3.307_711_6 * m_ + 0.230_969_94 * s_),
+        linear_to_srgb(-1.268_438 * l_ + 2.609_757_4 * m_ - 0.341_319_38 * s_),
+        linear_to_srgb(-0.004_196_086_3 * l_ - 0.703_418_6 * m_ + 1.707_614_7 * s_),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_contrast_text_color() {
+        assert_eq!(contrast_text_color(Color32::from_rgb(255, 206, 86)), Color32::from_gray(30));
+        assert_eq!(contrast_text_color(Color32::from_rgb(54, 162, 235)), Color32::WHITE);
+        assert_eq!(contrast_text_color(Color32::BLACK), Color32::WHITE);
+    }
+
     #[test]
     fn test_hex_parsing_6_digit() {
         assert_eq!(
@@ -161,4 +254,31 @@ mod tests {
         assert!(darkened.g() < color.g());
         assert!(darkened.b() < color.b());
     }
+
+    #[test]
+    fn test_lerp_color_endpoints() {
+        let a = Color32::from_rgb(54, 162, 235);
+        let b = Color32::from_rgb(255, 99, 132);
+        assert_eq!(lerp_color(a, b, 0.0), a);
+        assert_eq!(lerp_color(a, b, 1.0), b);
+    }
+
+    #[test]
+    fn test_lerp_color_roundtrip() {
+        // Interpolating a color with itself must not drift
+        let c = Color32::from_rgb(75, 192, 192);
+        let mid = lerp_color(c, c, 0.5);
+        assert!((mid.r() as i32 - c.r() as i32).abs() <= 1);
+        assert!((mid.g() as i32 - c.g() as i32).abs() <= 1);
+        assert!((mid.b() as i32 - c.b() as i32).abs() <= 1);
+    }
+
+    #[test]
+    fn test_lerp_color_perceptual_midpoint() {
+        // OKLab midpoint between black and white sits below the naive sRGB average
+        let mid = lerp_color(Color32::BLACK, Color32::WHITE, 0.5);
+        assert!(mid.r() > 90 && mid.r() < 127);
+        assert_eq!(mid.r(), mid.g());
+        assert_eq!(mid.g(), mid.b());
+    }
 }
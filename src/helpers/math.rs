@@ -61,6 +61,18 @@ pub fn compute_data_hash(data: &[f64]) -> u64 {
     hasher.finish()
 }
 
+/// Compute hash of several data series for change detection
+pub fn compute_series_hash<'a>(series: impl IntoIterator<Item = &'a [f64]>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for data in series {
+        data.len().hash(&mut hasher);
+        for val in data {
+            val.to_bits().hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
 /// Compute hash of string slice for change detection
 pub fn compute_labels_hash(labels: &[String]) -> u64 {
     let mut hasher = DefaultHasher::new();
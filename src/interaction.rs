@@ -1,6 +1,8 @@
-use egui::{Pos2, Response};
+use egui::{Color32, Context, Id, Pos2, Response};
 
+use crate::animation::Easing;
 use crate::elements::BarElement;
+use crate::helpers::color::{lerp_color, lighten};
 
 /// Result of interaction detection
 #[derive(Clone, Debug, Default)]
@@ -57,6 +59,125 @@ pub enum InteractionMode {
     Nearest,
 }
 
+/// Hover appearance of a dataset
+///
+/// Mirrors Chart.js `hoverBackgroundColor`, `hoverBorderColor`,
+/// `hoverBorderWidth`, `hoverRadius` and `hoverOffset`. Entering and leaving
+/// hover animates between the normal and hovered style.
+#[derive(Clone, Debug)]
+pub struct HoverStyle {
+    /// Fill color while hovered (`None` = lighten the normal color)
+    pub background_color: Option<Color32>,
+    /// Border color while hovered (`None` = keep the normal color)
+    pub border_color: Option<Color32>,
+    /// Border width while hovered (`None` = keep the normal width)
+    pub border_width: Option<f32>,
+    /// Point radius while hovered (`None` = 1.3x the normal radius)
+    pub radius: Option<f32>,
+    /// Distance a hovered arc moves away from the center
+    pub offset: f32,
+    /// Duration of the hover enter/leave transition in seconds
+    pub duration_secs: f32,
+    /// Easing of the hover transition
+    pub easing: Easing,
+}
+
+impl Default for HoverStyle {
+    fn default() -> Self {
+        Self {
+            background_color: None,
+            border_color: None,
+            border_width: None,
+            radius: None,
+            offset: 5.0,
+            duration_secs: 0.15,
+            easing: Easing::EaseOutQuad,
+        }
+    }
+}
+
+impl HoverStyle {
+    /// Fill color at hover amount `t` (0.0 = normal, 1.0 = fully hovered)
+    pub fn fill_color(&self, normal: Color32, t: f32) -> Color32 {
+        let hovered = self.background_color.unwrap_or_else(|| lighten(normal, 0.15));
+        lerp_color(normal, hovered, t)
+    }
+
+    /// Border color at hover amount `t`
+    pub fn border_color(&self, normal: Color32, t: f32) -> Color32 {
+        match self.border_color {
+            Some(hovered) => lerp_color(normal, hovered, t),
+            None => normal,
+        }
+    }
+
+    /// Border width at hover amount `t`
+    pub fn border_width(&self, normal: f32, t: f32) -> f32 {
+        let hovered = self.border_width.unwrap_or(normal);
+        normal + (hovered - normal) * t
+    }
+
+    /// Point radius at hover amount `t`
+    pub fn radius(&self, normal: f32, t: f32) -> f32 {
+        let hovered = self.radius.unwrap_or(normal * 1.3);
+        normal + (hovered - normal) * t
+    }
+
+    /// Arc offset at hover amount `t`
+    pub fn offset(&self, t: f32) -> f32 {
+        self.offset * t
+    }
+}
+
+/// Animated hover amount of each element, kept in chart memory between frames
+///
+/// Only elements that are hovered or still fading out are tracked, so large
+/// datasets don't pay for an animation per element.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct HoverTransitions {
+    active: Vec<(usize, f32)>,
+}
+
+impl HoverTransitions {
+    /// Advance transitions towards the currently hovered element
+    pub fn update(&mut self, ctx: &Context, id: Id, hovered: Option<usize>, style: &HoverStyle) {
+        let mut indices: Vec<usize> = self.active.iter().map(|(i, _)| *i).collect();
+        if let Some(h) = hovered {
+            if !indices.contains(&h) {
+                // Seed at zero so egui animates the enter instead of jumping
+                ctx.animate_bool_with_time(id.with(("hover", h)), false, style.duration_secs);
+                indices.push(h);
+            }
+        }
+
+        self.active = indices
+            .into_iter()
+            .filter_map(|i| {
+                let linear = ctx.animate_bool_with_time(
+                    id.with(("hover", i)),
+                    hovered == Some(i),
+                    style.duration_secs,
+                );
+                (linear > 0.0).then(|| (i, style.easing.apply(linear)))
+            })
+            .collect();
+    }
+
+    /// Hashable state of all transitions, for caching what they are drawn with
+    pub fn key(&self) -> Vec<(usize, u32)> {
+        self.active.iter().map(|(i, t)| (*i, t.to_bits())).collect()
+    }
+
+    /// Hover amount of an element (0.0 = not hovered, 1.0 = fully hovered)
+    pub fn amount(&self, index: usize) -> f32 {
+        self.active
+            .iter()
+            .find(|(i, _)| *i == index)
+            .map(|(_, t)| *t)
+            .unwrap_or(0.0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,4 +215,59 @@ mod tests {
         // Should return the last (top) bar when they overlap
         assert_eq!(find_bar_at_position(&bars, Pos2::new(100.0, 60.0)), Some(1));
     }
+
+    #[test]
+    fn test_hover_style_interpolation() {
+        let style = HoverStyle {
+            background_color: Some(Color32::WHITE),
+            border_width: Some(4.0),
+            radius: Some(10.0),
+            offset: 8.0,
+            ..Default::default()
+        };
+
+        assert_eq!(style.fill_color(Color32::BLACK, 0.0), Color32::BLACK);
+        assert_eq!(style.fill_color(Color32::BLACK, 1.0), Color32::WHITE);
+        assert!((style.border_width(2.0, 0.5) - 3.0).abs() < 0.001);
+        assert!((style.radius(4.0, 1.0) - 10.0).abs() < 0.001);
+        assert!((style.offset(0.25) - 2.0).abs() < 0.001);
+
+        // Unset hover border keeps the normal border
+        assert_eq!(style.border_color(Color32::RED, 1.0), Color32::RED);
+    }
+
+    #[test]
+    fn test_hover_transition_animates_enter_and_leave() {
+        let ctx = Context::default();
+        let id = Id::new("chart");
+        let style = HoverStyle {
+            duration_secs: 1.0,
+            easing: Easing::Linear,
+            ..Default::default()
+        };
+        let mut transitions = HoverTransitions::default();
+
+        let frame = |time: f64, hovered: Option<usize>, transitions: &mut HoverTransitions| {
+            let input = egui::RawInput {
+                time: Some(time),
+                ..Default::default()
+            };
+            let _ = ctx.run(input, |ctx| transitions.update(ctx, id, hovered, &style));
+        };
+
+        frame(0.0, Some(2), &mut transitions);
+        assert!(transitions.amount(2) < 0.5, "hover enter should not jump");
+
+        frame(0.5, Some(2), &mut transitions);
+        frame(1.5, Some(2), &mut transitions);
+        assert!((transitions.amount(2) - 1.0).abs() < 0.001);
+
+        // Leaving fades out, then the element stops being tracked
+        frame(2.0, None, &mut transitions);
+        assert!(transitions.amount(2) > 0.0 && transitions.amount(2) < 1.0);
+        frame(4.0, None, &mut transitions);
+        frame(5.0, None, &mut transitions);
+        assert_eq!(transitions.amount(2), 0.0);
+        assert!(transitions.active.is_empty());
+    }
 }
@@ -9,7 +9,8 @@
 //!
 //! - **BarChart** - Vertical bar charts
 //! - **LineChart** - Line charts with optional area fill
-//! - **PieChart** - Pie and donut charts
+//! - **PieChart** - Pie and donut charts, including partial circles, nested rings and sunbursts
+//! - **GaugeChart** - Half-donut gauges with a needle and colored bands
 //!
 //! ## Quick Start
 //!
@@ -47,10 +48,20 @@
 //! - **Themeable**: Light, dark, and minimal themes included
 //! - **Responsive**: Charts resize to fill available space
 //! - **Click handling**: Detect which element was clicked
+//! - **Zoom and pan**: Wheel, pinch, drag-select and drag-to-pan on bar and line charts
+//! - **Annotations**: Threshold lines, range boxes, labels, arrows and markers
+//! - **Data labels**: Value labels with placement, formatting and overlap handling
+//! - **Crosshair**: Cursor-following or snapping lines with axis value labels
+//! - **Linked charts**: Share cursor, zoom and visibility across a [`LinkGroup`]
+//! - **Plugins**: Draw extras through [`ChartPlugin`] lifecycle hooks
+//! - **Streaming**: Line charts scroll through live samples of a [`DataStream`]
 //!
 //! ## Animation
 //!
-//! Charts animate automatically when data changes:
+//! Charts animate automatically when data changes. Animations run on egui's
+//! input clock, so they are deterministic and can be paused or scrubbed via
+//! [`AnimationState`]. Setting `style.animation_time` to zero disables them
+//! (reduced motion).
 //!
 //! ```rust,ignore
 //! use egui_charts::prelude::*;
@@ -69,37 +80,92 @@
 //!     .data(vec![1.0, 2.0, 3.0])
 //!     .animate(Animation::none())
 //!     .show(ui);
+//!
+//! // Staggered wave: each bar starts 50ms after the previous one,
+//! // colors fade in over a longer duration
+//! BarChart::new()
+//!     .data(vec![1.0, 2.0, 3.0])
+//!     .animate(
+//!         Animation::ease_out_quart(0.6)
+//!             .delay(|i| i as f32 * 0.05)
+//!             .property(AnimatedProperty::Colors, PropertyAnimation::new(Easing::Linear, 1.2)),
+//!     )
+//!     .show(ui);
 //! ```
 
 mod animation;
+mod annotation;
 mod bar_chart;
+mod cache;
+mod crosshair;
+mod data;
+mod data_labels;
+mod decimation;
+mod donut_center;
+mod gauge_chart;
 mod line_chart;
 mod pie_chart;
+mod pie_labels;
+mod pie_rings;
+mod plugin;
 mod interaction;
+mod link;
+mod scale;
+mod state;
+mod stream;
 mod theme;
+mod title;
 mod tooltip;
+mod zoom;
 
 pub mod elements;
 pub mod helpers;
 
 // Re-exports
-pub use animation::{Animation, AnimationConfig, AnimationState, Easing};
+pub use animation::{
+    AnimatedProperty, Animation, AnimationConfig, AnimationFrom, AnimationState, Easing,
+    PropertyAnimation,
+};
+pub use annotation::{Annotation, AnnotationKind, AnnotationLayer};
 pub use bar_chart::{BarChart, BarChartResponse};
-pub use line_chart::{LineChart, LineChartResponse};
+pub use crosshair::Crosshair;
+pub use data::{ChartData, Versioned};
+pub use donut_center::{CenterContext, CenterLine, CenterSegment, DonutCenter};
+pub use data_labels::{DataLabels, LabelAlign, LabelAnchor, LabelContext, LabelOverlap};
+pub use decimation::{Decimation, DecimationAlgorithm};
+pub use gauge_chart::{GaugeChart, GaugeChartResponse};
+pub use line_chart::{LineChart, LineChartResponse, LineDataset};
 pub use pie_chart::{PieChart, PieChartResponse};
-pub use elements::{BarElement, BarStyle, LineElement, LineStyle, PointElement, ArcElement, PieStyle};
-pub use interaction::{InteractionMode, InteractionResult};
+pub use pie_rings::{PieDataset, SunburstNode};
+pub use plugin::{
+    clear_global_plugins, register_global_plugin, ChartElements, ChartEvent, ChartLayout, ChartPlugin,
+    ChartTooltip, PluginContext,
+};
+pub use elements::{BarElement, BarStyle, CubicInterpolation, LineCap, LineElement, LineJoin, LineStyle, PointElement, Stepped, ArcElement, PieStyle};
+pub use interaction::{HoverStyle, InteractionMode, InteractionResult};
+pub use link::LinkGroup;
+pub use scale::LinearScale;
+pub use state::ChartState;
+pub use stream::DataStream;
 pub use theme::{ChartTheme, ThemePreset};
+pub use title::Title;
 pub use tooltip::{TooltipConfig, TooltipContent};
+pub use zoom::{AxisLimits, VisibleRange, ZoomAxis, ZoomConfig};
 
 /// Prelude module for convenient imports
 pub mod prelude {
     pub use crate::{
-        Animation, AnimationConfig, Easing,
+        AnimatedProperty, Animation, AnimationConfig, AnimationFrom, AnimationState, Easing,
+        PropertyAnimation,
+        Annotation, AnnotationLayer,
         BarChart, BarChartResponse, BarStyle,
-        LineChart, LineChartResponse, LineStyle,
-        PieChart, PieChartResponse, PieStyle,
-        ChartTheme, ThemePreset, TooltipConfig,
+        GaugeChart, GaugeChartResponse,
+        CubicInterpolation, LineCap, LineChart, LineChartResponse, LineDataset, LineJoin, LineStyle, Stepped,
+        CenterLine, DonutCenter,
+        PieChart, PieChartResponse, PieDataset, PieStyle, SunburstNode,
+        ChartEvent, ChartLayout, ChartPlugin, PluginContext, Title,
+        ChartData, ChartState, ChartTheme, Crosshair, DataLabels, DataStream, Decimation, LabelAlign, LabelAnchor, LabelOverlap, HoverStyle, LinkGroup, ThemePreset, TooltipConfig,
+        Versioned, VisibleRange, ZoomAxis, ZoomConfig,
     };
     pub use crate::helpers::color::ChartColor;
 }
@@ -1,18 +1,59 @@
-use egui::{Color32, CornerRadius, Id, Painter, Pos2, Rect, Response, Sense, Stroke, Ui, Vec2, Widget};
-
-use crate::animation::{AnimationConfig, AnimationState};
-use crate::elements::line::{LineElement, LineStyle, PointElement};
-use crate::helpers::color::{lighten, ChartColor};
-use crate::helpers::math::{compute_data_hash, nice_ticks};
+use std::sync::Arc;
+
+use egui::{Align2, Color32, CornerRadius, FontId, Id, Mesh, Painter, Pos2, Rect, Response, Sense, Shape, Stroke, Ui, Vec2, Widget};
+
+use crate::animation::{update_chart_animation, AnimatedProperty, AnimationConfig, AnimationState};
+use crate::cache::{atlas_key, cache_key, cached, range_bits, rect_bits, tessellate, Cached, PlacedGalleys};
+use crate::annotation::{annotation_at, draw_annotations, Annotation, AnnotationLayer};
+use crate::crosshair::{draw_crosshair, Crosshair, CrosshairTarget};
+use crate::decimation::{visible_indices, Decimation};
+use crate::data_labels::{draw_data_labels, DataLabels, LabelContext, LabelRequest, LabelTarget};
+use crate::elements::line::{CubicInterpolation, LineCap, LineElement, LineJoin, LineStyle, PointElement, Stepped};
+use crate::helpers::color::ChartColor;
+use crate::data::{data_key, is_missing, line_breaks, nearest_point, values, ChartData, Values};
+use crate::helpers::math::compute_labels_hash;
+use crate::interaction::{HoverStyle, HoverTransitions};
+use crate::link::{draw_cursor_line, LinkGroup};
+use crate::scale::{value_axis_range, LinearScale};
+use crate::state::ChartState;
+use crate::stream::{BySample, DataStream, StreamScroll, StreamView};
+use crate::plugin::{chart_plugins, ChartElements, ChartEvent, ChartLayout, ChartPlugin, ChartTooltip, PluginContext};
 use crate::theme::{ChartTheme, ThemePreset};
-use crate::tooltip::{calculate_tooltip_position, draw_tooltip, measure_tooltip_size, TooltipContent};
+use crate::title::Title;
+use crate::tooltip::{TooltipContent, TooltipPlugin};
+use crate::zoom::{draw_selection, handle_zoom_input, VisibleRange, ZoomConfig};
+
+/// Narrowest spacing of x axis labels, in pixels
+const MIN_LABEL_SPACING: f32 = 30.0;
 
 /// Memory stored in egui context between frames
 #[derive(Clone, Default)]
 struct LineChartMemory {
-    animation: AnimationState,
     data_hash: u64,
-    hovered_index: Option<usize>,
+    /// Hovered (dataset, point index)
+    hovered: Option<(usize, usize)>,
+    hover: HoverTransitions,
+    /// Scroll position when showing a stream
+    scroll: StreamScroll,
+    /// Value range of the visible datasets
+    value_range: Option<Cached<(f64, f64)>>,
+    /// Elements of each dataset, rebuilt when its data, the chart size,
+    /// visible range or style change
+    geometry: Vec<Option<Cached<LineGeometry>>>,
+    /// Tessellated fill and line of each dataset once animations finish
+    line_meshes: Vec<Option<Cached<Mesh>>>,
+    /// Tessellated points of all datasets once animations finish
+    points_mesh: Option<Cached<Mesh>>,
+    /// Laid out axis labels and their positions
+    axis_labels: Option<Cached<PlacedGalleys>>,
+}
+
+/// Line and points of one dataset, built for the data on screen
+#[derive(Clone, Debug)]
+struct LineGeometry {
+    /// Data index of each drawn point
+    indices: Vec<usize>,
+    line: LineElement,
 }
 
 /// Response returned after showing the chart
@@ -22,15 +63,68 @@ pub struct LineChartResponse {
     pub response: Response,
     /// Index of currently hovered point
     pub hovered: Option<usize>,
+    /// Dataset of the currently hovered point
+    pub hovered_dataset: Option<usize>,
     /// Index of clicked point (if any this frame)
     pub clicked: Option<usize>,
+    /// Dataset of the clicked point
+    pub clicked_dataset: Option<usize>,
+    /// Interactive annotation under the pointer
+    pub hovered_annotation: Option<usize>,
+    /// Interactive annotation clicked this frame
+    pub clicked_annotation: Option<usize>,
+    /// Data range on screen; point `i` sits at `x = i`
+    pub visible_range: VisibleRange,
+}
+
+/// An additional series drawn by a [`LineChart`]
+#[derive(Clone, Debug)]
+pub struct LineDataset<'a> {
+    pub label: String,
+    data: Values<'a>,
+    pub color: ChartColor,
+}
+
+impl<'a> LineDataset<'a> {
+    pub fn new(label: impl Into<String>, data: impl IntoIterator<Item = impl Into<f64>>) -> Self {
+        Self {
+            label: label.into(),
+            data: Values::Owned(data.into_iter().map(|v| v.into()).collect()),
+            color: ChartColor::default(),
+        }
+    }
+
+    /// A series read in place from `data`, such as `&samples[..]` or a
+    /// reference to your own [`ChartData`] type
+    pub fn from_source(label: impl Into<String>, data: impl ChartData + 'a) -> Self {
+        Self {
+            label: label.into(),
+            data: Values::Source(Arc::new(data)),
+            color: ChartColor::default(),
+        }
+    }
+
+    /// Set line color
+    pub fn color(mut self, color: impl Into<ChartColor>) -> Self {
+        self.color = color.into();
+        self
+    }
+}
+
+/// Borrowed view of one dataset while drawing
+struct Series<'a> {
+    label: Option<&'a str>,
+    data: &'a dyn ChartData,
+    color: Color32,
 }
 
 /// Line chart widget with Chart.js-inspired API
 #[derive(Clone)]
-pub struct LineChart {
+pub struct LineChart<'a> {
     id: Option<Id>,
-    data: Vec<f64>,
+    data: Values<'a>,
+    label: Option<String>,
+    datasets: Vec<LineDataset<'a>>,
     labels: Vec<String>,
     color: ChartColor,
     animation: AnimationConfig,
@@ -40,15 +134,24 @@ pub struct LineChart {
     min_size: Vec2,
     show_grid: bool,
     show_axes: bool,
-    show_values: bool,
+    data_labels: Option<DataLabels>,
     line_style: LineStyle,
+    decimation: Decimation,
+    stream: Option<StreamView>,
+    zoom: ZoomConfig,
+    link: Option<LinkGroup>,
+    crosshair: Option<Crosshair>,
+    annotations: Vec<Annotation>,
+    plugins: Vec<Arc<dyn ChartPlugin>>,
 }
 
-impl Default for LineChart {
+impl Default for LineChart<'_> {
     fn default() -> Self {
         Self {
             id: None,
-            data: Vec::new(),
+            data: Values::default(),
+            label: None,
+            datasets: Vec::new(),
             labels: Vec::new(),
             color: ChartColor::Rgba(Color32::from_rgb(54, 162, 235)),
             animation: AnimationConfig::default(),
@@ -58,13 +161,20 @@ impl Default for LineChart {
             min_size: Vec2::new(100.0, 80.0),
             show_grid: true,
             show_axes: true,
-            show_values: false,
+            data_labels: None,
             line_style: LineStyle::default(),
+            decimation: Decimation::default(),
+            stream: None,
+            zoom: ZoomConfig::default(),
+            link: None,
+            crosshair: None,
+            annotations: Vec::new(),
+            plugins: Vec::new(),
         }
     }
 }
 
-impl LineChart {
+impl<'a> LineChart<'a> {
     /// Create a new line chart
     pub fn new() -> Self {
         Self::default()
@@ -78,7 +188,26 @@ impl LineChart {
 
     /// Set chart data values
     pub fn data(mut self, data: impl IntoIterator<Item = impl Into<f64>>) -> Self {
-        self.data = data.into_iter().map(|v| v.into()).collect();
+        self.data = Values::Owned(data.into_iter().map(|v| v.into()).collect());
+        self
+    }
+
+    /// Read the chart data in place, from `&samples[..]` or a reference to
+    /// your own [`ChartData`] type, instead of copying it into the chart
+    pub fn data_source(mut self, data: impl ChartData + 'a) -> Self {
+        self.data = Values::Source(Arc::new(data));
+        self
+    }
+
+    /// Name the primary dataset (shown in tooltips)
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Add another series; dataset indices start at 1 after the primary `.data()`
+    pub fn dataset(mut self, dataset: LineDataset<'a>) -> Self {
+        self.datasets.push(dataset);
         self
     }
 
@@ -100,6 +229,18 @@ impl LineChart {
         self
     }
 
+    /// Set how line ends are drawn
+    pub fn line_cap(mut self, cap: LineCap) -> Self {
+        self.line_style.cap = cap;
+        self
+    }
+
+    /// Set how straight line segments meet
+    pub fn line_join(mut self, join: LineJoin) -> Self {
+        self.line_style.join = join;
+        self
+    }
+
     /// Set point radius
     pub fn point_radius(mut self, radius: f32) -> Self {
         self.line_style.point_radius = radius;
@@ -136,6 +277,37 @@ impl LineChart {
         self
     }
 
+    /// Set how curves bend between points; `Monotone` never overshoots the data
+    pub fn cubic_interpolation(mut self, interpolation: CubicInterpolation) -> Self {
+        self.line_style.interpolation = interpolation;
+        self
+    }
+
+    /// Draw the line as steps, for values that hold until they change
+    pub fn stepped(mut self, stepped: Stepped) -> Self {
+        self.line_style.stepped = Some(stepped);
+        self
+    }
+
+    /// Connect the line across missing points instead of breaking it
+    pub fn span_gaps(mut self, span: bool) -> Self {
+        self.line_style.span_gaps = span.then_some(f64::INFINITY);
+        self
+    }
+
+    /// Connect the line across missing points, but break it where the points
+    /// either side of them are more than `distance` apart along x
+    pub fn max_gap(mut self, distance: f64) -> Self {
+        self.line_style.span_gaps = Some(distance);
+        self
+    }
+
+    /// Set hover appearance and transition of points
+    pub fn hover_style(mut self, hover: HoverStyle) -> Self {
+        self.line_style.hover = hover;
+        self
+    }
+
     /// Configure animation
     pub fn animate(mut self, config: AnimationConfig) -> Self {
         self.animation = config;
@@ -186,10 +358,75 @@ impl LineChart {
 
     /// Show/hide value labels on points
     pub fn show_values(mut self, show: bool) -> Self {
-        self.show_values = show;
+        self.data_labels = show.then(DataLabels::default);
+        self
+    }
+
+    /// Configure value labels on points
+    pub fn data_labels(mut self, labels: DataLabels) -> Self {
+        self.data_labels = Some(labels);
+        self
+    }
+
+    /// Draw fewer points for large series (default: min/max above 4 points per pixel)
+    pub fn decimation(mut self, decimation: Decimation) -> Self {
+        self.decimation = decimation;
+        self
+    }
+
+    /// Show the samples of a live [`DataStream`], scrolling smoothly as
+    /// new ones arrive instead of animating in again. Time-window streams
+    /// are placed by time; others one step per sample.
+    pub fn stream(mut self, stream: &'a DataStream) -> Self {
+        self.data = match stream.window() {
+            Some(_) => Values::Source(Arc::new(stream)),
+            None => Values::Source(Arc::new(BySample(stream))),
+        };
+        self.stream = Some(StreamView::new(stream));
+        self
+    }
+
+    /// Enable zoom and pan
+    pub fn zoom(mut self, config: ZoomConfig) -> Self {
+        self.zoom = config;
+        self
+    }
+
+    /// Synchronize cursor, zoom and visibility with other charts in the group
+    pub fn link(mut self, group: LinkGroup) -> Self {
+        self.link = Some(group);
+        self
+    }
+
+    /// Draw crosshair lines following the cursor
+    pub fn crosshair(mut self, crosshair: Crosshair) -> Self {
+        self.crosshair = Some(crosshair);
+        self
+    }
+
+    /// Add an annotation (threshold line, range box, label, arrow or marker)
+    pub fn annotation(mut self, annotation: Annotation) -> Self {
+        self.annotations.push(annotation);
+        self
+    }
+
+    /// Add several annotations
+    pub fn annotations(mut self, annotations: impl IntoIterator<Item = Annotation>) -> Self {
+        self.annotations.extend(annotations);
+        self
+    }
+
+    /// Add a plugin hooking into this chart's drawing
+    pub fn plugin(mut self, plugin: impl ChartPlugin + 'static) -> Self {
+        self.plugins.push(Arc::new(plugin));
         self
     }
 
+    /// Add a title above the chart
+    pub fn title(self, text: impl Into<String>) -> Self {
+        self.plugin(Title::new(text))
+    }
+
     /// Show the chart and return response
     pub fn show(self, ui: &mut Ui) -> LineChartResponse {
         // Determine size
@@ -212,85 +449,307 @@ impl LineChart {
         let mut memory = ui
             .ctx()
             .data_mut(|d| d.get_temp_mut_or_insert_with::<LineChartMemory>(id, Default::default).clone());
-
-        // Check for data changes
-        let new_data_hash = compute_data_hash(&self.data);
-        if memory.data_hash != new_data_hash {
-            memory.animation = AnimationState::new(self.animation.clone());
-            memory.data_hash = new_data_hash;
+        let mut state = ChartState::load(ui.ctx(), id).unwrap_or_default();
+        if let Some(link) = &self.link {
+            link.pull(ui.ctx(), id, &mut state);
         }
 
+        let series = self.series();
+        let n = series.iter().map(|s| s.data.len()).max().unwrap_or(0);
+
+        // Check for data changes, by version for sources that have one
+        let data_hashes: Vec<u64> = series.iter().map(|s| data_key(s.data)).collect();
+        let new_data_hash = cache_key(&data_hashes);
+        let data_changed = match self.stream {
+            // Streams scroll to new samples instead of animating in again
+            Some(stream) => {
+                let animate = self.animation.enabled && ui.style().animation_time > 0.0;
+                let restarted = memory.scroll.update(stream, ui.input(|i| i.time), animate);
+                if memory.scroll.is_scrolling() {
+                    ui.ctx().request_repaint();
+                }
+                restarted
+            }
+            None => memory.data_hash != new_data_hash,
+        };
+        memory.data_hash = new_data_hash;
+        let animation = update_chart_animation(ui, id, &self.animation, data_changed, n);
+
         // Get animation progress
-        let progress = memory.animation.progress();
-        memory.animation.request_repaint_if_animating(ui.ctx());
-
-        // Calculate layout
-        let y_axis_width = 45.0;
-        let x_axis_height = 30.0;
-        let top_padding = 15.0;
-        let right_padding = 15.0;
-
-        let chart_rect = Rect::from_min_max(
-            Pos2::new(rect.min.x + y_axis_width, rect.min.y + top_padding),
-            Pos2::new(rect.max.x - right_padding, rect.max.y - x_axis_height),
-        );
+        let progress = animation.progress();
+
+        // Animated visibility of each dataset
+        let transition_secs = self.animation.transition_secs(ui);
+        let visibility: Vec<f32> = (0..series.len())
+            .map(|d| state.dataset_amount(ui.ctx(), id, d, transition_secs))
+            .collect();
+
+        // Calculate layout, letting plugins reserve space
+        let plugins = chart_plugins(ui.ctx(), &self.plugins);
+        let mut layout = ChartLayout {
+            left: 45.0,
+            right: 15.0,
+            top: 15.0,
+            bottom: 30.0,
+        };
+        for plugin in &plugins {
+            plugin.before_layout(&mut layout);
+        }
+        let chart_rect = layout.chart_area(rect);
 
         // Draw background
         if self.theme.background_color != Color32::TRANSPARENT {
             painter.rect_filled(rect, CornerRadius::ZERO, self.theme.background_color);
         }
 
-        // Build line and points
-        let (line, points) = self.build_line_elements(chart_rect);
+        // Y scale covers visible datasets, rescaling smoothly when that changes
+        let shown: Vec<bool> = (0..series.len()).map(|d| state.is_visible(d)).collect();
+        let (target_min, target_max) = *cached(&mut memory.value_range, cache_key((new_data_hash, &shown)), || {
+            value_axis_range(
+                series
+                    .iter()
+                    .zip(&shown)
+                    .filter(|(_, shown)| **shown)
+                    .flat_map(|(s, _)| values(s.data)),
+            )
+        });
+        let y_min = ui.ctx().animate_value_with_time(id.with("y_min"), target_min as f32, transition_secs);
+        let y_max = ui.ctx().animate_value_with_time(id.with("y_max"), target_max as f32, transition_secs);
+
+        // Zoom and pan within the full data range
+        let full_range = VisibleRange::new(self.x_range(&series, memory.scroll.offset()), (y_min as f64, y_max as f64));
+        let scales = |range: VisibleRange| {
+            (
+                LinearScale::new(range.x_min, range.x_max, chart_rect.min.x, chart_rect.max.x),
+                LinearScale::new(range.y_min, range.y_max, chart_rect.max.y, chart_rect.min.y),
+            )
+        };
+        let (x_scale, y_scale) = scales(state.visible_range(full_range));
+        let selection = handle_zoom_input(ui, &response, &self.zoom, &x_scale, &y_scale, &mut state);
+        let visible_range = state.visible_range(full_range);
+        let (x_scale, y_scale) = scales(visible_range);
+
+        // Publish the hovered x position to the link group and follow the group's cursor
+        let pointer_x = response
+            .hover_pos()
+            .filter(|p| chart_rect.contains(*p))
+            .map(|p| x_scale.pixel_to_value(p.x));
+        let linked_cursor = self.link.and_then(|link| {
+            link.set_cursor(ui.ctx(), id, pointer_x);
+            link.cursor(ui.ctx())
+        });
         let base_y = chart_rect.max.y;
 
+        // Keep zoomed-in and scrolling data inside the plot area
+        let clip_rect = if state.is_zoomed() || self.stream.is_some() { chart_rect } else { rect };
+        let plot_painter = painter.with_clip_rect(clip_rect);
+
+        // Annotations under the pointer
+        let annotation_under = |pos: Pos2| annotation_at(&self.annotations, pos, chart_rect, (&x_scale, &y_scale));
+        let hovered_annotation = response.hover_pos().and_then(annotation_under);
+        let clicked_annotation = if response.clicked() {
+            response.interact_pointer_pos().and_then(annotation_under)
+        } else {
+            None
+        };
+        let annotation_painter = painter.with_clip_rect(chart_rect);
+
+        let plugin_ctx = PluginContext {
+            painter: &painter,
+            chart_id: id,
+            rect,
+            chart_area: chart_rect,
+            x_scale: Some(&x_scale),
+            y_scale: Some(&y_scale),
+            elements: ChartElements::None,
+            theme: &self.theme,
+            response: &response,
+            hovered: memory.hovered,
+            tooltip: None,
+        };
+        for plugin in &plugins {
+            plugin.before_draw(&plugin_ctx);
+        }
+
         // Draw grid
         if self.show_grid {
-            self.draw_grid(&painter, chart_rect);
+            self.draw_grid(&painter, chart_rect, &y_scale);
         }
 
-        // Draw fill (before line)
-        if self.line_style.fill {
-            let fill_color = self.line_style.fill_color.unwrap_or_else(|| {
-                let c = self.color.to_color32();
-                Color32::from_rgba_unmultiplied(c.r(), c.g(), c.b(), 50)
+        draw_annotations(
+            &annotation_painter,
+            &self.annotations,
+            AnnotationLayer::Behind,
+            chart_rect,
+            (&x_scale, &y_scale),
+            hovered_annotation,
+        );
+
+        // Build, animate and draw each visible dataset, from the points on
+        // screen, decimated when there are many. `dataset_indices` maps the
+        // drawn points back to data indices. Elements are reused until what
+        // they are built from changes, and once animations finish the
+        // tessellated meshes are too.
+        let finished = animation.is_finished();
+        let atlas = atlas_key(ui.ctx());
+        let style_key = self.style_key();
+        memory.geometry.resize_with(series.len(), || None);
+        memory.line_meshes.resize_with(series.len(), || None);
+        let mut geometry_keys: Vec<u64> = Vec::with_capacity(series.len());
+        let mut dataset_points: Vec<Vec<PointElement>> = Vec::with_capacity(series.len());
+        let mut dataset_indices: Vec<Vec<usize>> = Vec::with_capacity(series.len());
+        for (d, s) in series.iter().enumerate() {
+            let amount = visibility[d];
+            if amount <= 0.0 {
+                geometry_keys.push(0);
+                dataset_points.push(Vec::new());
+                dataset_indices.push(Vec::new());
+                continue;
+            }
+
+            let key = cache_key((data_hashes[d], s.color, rect_bits(chart_rect), range_bits(visible_range), style_key));
+            let geometry = cached(&mut memory.geometry[d], key, || {
+                let visible = visible_indices(s.data, visible_range.x_min, visible_range.x_max);
+                let indices: Vec<usize> = self
+                    .decimation
+                    .indices(s.data, visible, chart_rect.width(), |i| x_scale.value_to_pixel(s.data.x(i)))
+                    .into_iter()
+                    .filter(|&i| !is_missing(s.data.y(i)))
+                    .collect();
+                let line = self.build_line_element(s, &indices, &x_scale, &y_scale);
+                LineGeometry { indices, line }
+            });
+            let fill_color = self.line_style.fill.then(|| {
+                self.line_style.fill_color.unwrap_or_else(|| {
+                    let c = s.color;
+                    Color32::from_rgba_unmultiplied(c.r(), c.g(), c.b(), 50)
+                })
             });
-            line.draw_fill_animated(&painter, base_y, progress, fill_color);
-        }
 
-        // Draw line
-        line.draw_animated(&painter, base_y, progress);
+            let points: Vec<PointElement> = if finished {
+                let points: Vec<PointElement> = geometry
+                    .line
+                    .points
+                    .iter()
+                    .map(|point| fade_point(point, amount))
+                    .collect();
+                let fill_color = fill_color.map(|c| c.gamma_multiply(amount));
+                let mesh_key = cache_key((key, amount.to_bits(), fill_color, atlas));
+                let mesh = cached(&mut memory.line_meshes[d], mesh_key, || {
+                    let line = LineElement {
+                        points: points.clone(),
+                        color: geometry.line.color.gamma_multiply(amount),
+                        ..geometry.line.clone()
+                    };
+                    tessellate(ui.ctx(), line.shapes(fill_color.map(|c| (base_y, c))))
+                });
+                plot_painter.add(Shape::Mesh(mesh));
+                points
+            } else {
+                let points: Vec<PointElement> = geometry
+                    .line
+                    .points
+                    .iter()
+                    .zip(&geometry.indices)
+                    .map(|(point, &i)| fade_point(&self.animate_point(point, i, &animation, chart_rect), amount))
+                    .collect();
+                let line = LineElement {
+                    points: points.clone(),
+                    color: animation.animate_color(geometry.line.color, 0).gamma_multiply(amount),
+                    ..geometry.line.clone()
+                };
+                let fill = fill_color.map(|c| (base_y, animation.animate_color(c, 0).gamma_multiply(amount)));
+                plot_painter.extend(line.shapes(fill));
+                points
+            };
+
+            geometry_keys.push(key);
+            dataset_points.push(points);
+            dataset_indices.push(geometry.indices.clone());
+        }
 
         // Draw points
+        let hover_key = memory.hovered.map(|(d, i)| d * n + i);
+        memory.hover.update(ui.ctx(), id, hover_key, &self.line_style.hover);
         if self.line_style.show_points {
-            for (i, point) in points.iter().enumerate() {
-                let mut point = point.clone();
-
-                // Hover effect
-                if memory.hovered_index == Some(i) {
-                    point.radius *= 1.3;
-                    point.fill_color = lighten(point.fill_color, 0.2);
+            let hover_style = &self.line_style.hover;
+            let point_shapes = || {
+                let mut shapes = Vec::new();
+                for (d, points) in dataset_points.iter().enumerate() {
+                    for (point, &i) in points.iter().zip(&dataset_indices[d]) {
+                        let mut point = point.clone();
+
+                        // Hover effect
+                        let hover = memory.hover.amount(d * n + i);
+                        if hover > 0.0 {
+                            point.radius = hover_style.radius(point.radius, hover);
+                            point.fill_color = hover_style.fill_color(point.fill_color, hover);
+                            point.border_color = hover_style.border_color(point.border_color, hover);
+                            point.border_width = hover_style.border_width(point.border_width, hover);
+                        }
+
+                        shapes.extend(point.shapes());
+                    }
                 }
-
-                point.draw_animated(&painter, base_y, progress);
+                shapes
+            };
+
+            if finished {
+                let visibility_bits: Vec<u32> = visibility.iter().map(|v| v.to_bits()).collect();
+                let key = cache_key((&geometry_keys, visibility_bits, memory.hover.key(), atlas));
+                let mesh = cached(&mut memory.points_mesh, key, || tessellate(ui.ctx(), point_shapes()));
+                plot_painter.add(Shape::Mesh(mesh));
+            } else {
+                plot_painter.extend(point_shapes());
             }
         }
 
+        draw_annotations(
+            &annotation_painter,
+            &self.annotations,
+            AnnotationLayer::Front,
+            chart_rect,
+            (&x_scale, &y_scale),
+            hovered_annotation,
+        );
+
         // Draw value labels on points
-        if self.show_values && progress > 0.5 {
-            for (i, point) in points.iter().enumerate() {
-                if let Some(&value) = self.data.get(i) {
-                    let animated_y = base_y + (point.y - base_y) * progress;
-                    let label_pos = Pos2::new(point.x, animated_y - 10.0);
-                    painter.text(
-                        label_pos,
-                        egui::Align2::CENTER_BOTTOM,
-                        format_value(value),
-                        egui::FontId::proportional(10.0),
-                        self.theme.text_color,
-                    );
+        if let Some(data_labels) = self.data_labels.as_ref().filter(|_| progress > 0.5) {
+            let mut requests = Vec::new();
+            for (d, points) in dataset_points.iter().enumerate() {
+                if visibility[d] <= 0.0 {
+                    continue;
+                }
+                for (point, &i) in points.iter().zip(&dataset_indices[d]) {
+                    let Some(value) = (i < series[d].data.len()).then(|| series[d].data.y(i)) else { continue };
+                    let context = LabelContext {
+                        dataset: d,
+                        index: i,
+                        value,
+                        fraction: None,
+                    };
+                    if let Some(text) = data_labels.text(&context, format_value) {
+                        requests.push(LabelRequest {
+                            target: LabelTarget::Point {
+                                pos: point.pos(),
+                                radius: point.radius,
+                            },
+                            text,
+                            color: point.fill_color,
+                            opacity: visibility[d],
+                        });
+                    }
                 }
             }
+            draw_data_labels(&plot_painter, data_labels, &requests, chart_rect, &self.theme);
+        }
+
+        let plugin_ctx = PluginContext {
+            elements: ChartElements::Points(&dataset_points),
+            ..plugin_ctx
+        };
+        for plugin in &plugins {
+            plugin.after_datasets_draw(&plugin_ctx);
         }
 
         // Draw axes
@@ -298,113 +757,275 @@ impl LineChart {
             self.draw_axes(&painter, chart_rect);
         }
 
-        // Draw labels
-        self.draw_labels(&painter, chart_rect, &points);
+        // Draw axis labels, laid out again only when they change
+        let labels_key = cache_key((
+            compute_labels_hash(&self.labels),
+            (new_data_hash, self.first_index()),
+            self.show_axes,
+            rect_bits(chart_rect),
+            range_bits(visible_range),
+            self.theme.text_color,
+            atlas,
+        ));
+        let labels = cached(&mut memory.axis_labels, labels_key, || {
+            self.layout_axis_labels(&painter, chart_rect, longest(&series), &x_scale, &y_scale)
+        });
+        for (pos, galley) in labels.iter() {
+            painter.galley(*pos, galley.clone(), self.theme.text_color);
+        }
 
-        // Handle interaction - check point hover
-        let mut hovered_index = None;
-        let mut clicked_index = None;
+        if let Some(selection) = selection {
+            draw_selection(&painter, selection, self.theme.axis_color);
+        }
 
-        if let Some(hover_pos) = response.hover_pos() {
-            for (i, point) in points.iter().enumerate() {
-                // Check animated position
-                let animated_y = base_y + (point.y - base_y) * progress;
-                let animated_point = PointElement {
-                    y: animated_y,
-                    ..point.clone()
-                };
-                if animated_point.contains(hover_pos) {
-                    hovered_index = Some(i);
-                    break;
-                }
+        // Handle interaction - check point hover on visible datasets, topmost first
+        let find_point = |pos: Pos2| {
+            if !clip_rect.contains(pos) {
+                return None;
             }
-        }
+            dataset_points
+                .iter()
+                .enumerate()
+                .rev()
+                .filter(|(d, _)| state.is_visible(*d))
+                .find_map(|(d, points)| {
+                    points
+                        .iter()
+                        .position(|p| clip_rect.contains(p.pos()) && p.contains(pos))
+                        .map(|k| (d, dataset_indices[d][k]))
+                })
+        };
 
-        if response.clicked() {
-            if let Some(pos) = response.interact_pointer_pos() {
-                for (i, point) in points.iter().enumerate() {
-                    let animated_y = base_y + (point.y - base_y) * progress;
-                    let animated_point = PointElement {
-                        y: animated_y,
-                        ..point.clone()
-                    };
-                    if animated_point.contains(pos) {
-                        clicked_index = Some(i);
-                        break;
-                    }
-                }
+        let hovered = response.hover_pos().and_then(find_point);
+        let clicked = if response.clicked() {
+            response.interact_pointer_pos().and_then(find_point)
+        } else {
+            None
+        };
+
+        // Follow a cursor hovering another chart of the link group
+        let linked_hover = linked_cursor.filter(|c| c.source != id).and_then(|c| {
+            let data = longest(&series).filter(|data| {
+                !data.is_empty() && c.x >= data.x(0) - 0.5 && c.x < data.x(data.len() - 1) + 0.5
+            })?;
+            let i = nearest_point(data, c.x)?;
+            (0..series.len())
+                .find(|d| state.is_visible(*d) && i < series[*d].data.len() && !is_missing(series[*d].data.y(i)))
+                .map(|d| (d, i))
+        });
+        memory.hovered = hovered.or(linked_hover);
+
+        if let Some(crosshair) = &self.crosshair {
+            let pointer = response.hover_pos().filter(|p| chart_rect.contains(*p));
+            let target = self.crosshair_target(crosshair, pointer, &x_scale, &y_scale, &series, &state)
+                .or_else(|| {
+                    // Only the vertical line follows another chart's cursor
+                    let cursor = linked_cursor.filter(|c| c.source != id)?;
+                    let data = longest(&series)?;
+                    let index = nearest_point(data, cursor.x)?;
+                    let x = if crosshair.snap_to_index { data.x(index) } else { cursor.x };
+                    Some(CrosshairTarget {
+                        x: Some((x, self.category_label(index))),
+                        y: None,
+                    })
+                });
+            if let Some(target) = target {
+                draw_crosshair(&painter, chart_rect, &x_scale, &y_scale, crosshair, &target, &self.theme);
             }
+        } else if let Some(cursor) = linked_cursor {
+            draw_cursor_line(&painter, chart_rect, x_scale.value_to_pixel(cursor.x), self.theme.axis_color);
         }
 
-        memory.hovered_index = hovered_index;
-
-        // Draw tooltip
-        if self.tooltip_enabled {
-            if let Some(idx) = memory.hovered_index {
-                if idx < self.data.len() {
-                    let point = &points[idx];
-                    let animated_y = base_y + (point.y - base_y) * progress;
-
-                    let content = TooltipContent {
-                        title: None,
-                        label: self
-                            .labels
-                            .get(idx)
-                            .cloned()
-                            .unwrap_or_else(|| format!("Point {}", idx + 1)),
-                        value: format_value(self.data[idx]),
-                        color: point.fill_color,
-                    };
+        let plugin_ctx = PluginContext {
+            hovered: memory.hovered,
+            ..plugin_ctx
+        };
+        for event in ChartEvent::from_response(&response) {
+            for plugin in &plugins {
+                plugin.on_event(&plugin_ctx, &event);
+            }
+        }
 
-                    let tooltip_size = measure_tooltip_size(&painter, &content, &self.theme.tooltip);
-                    let anchor = Pos2::new(point.x, animated_y);
-                    let tooltip_pos = calculate_tooltip_position(anchor, tooltip_size, rect);
+        // Tooltip for the hovered point
+        let tooltip = memory.hovered.filter(|_| self.tooltip_enabled).and_then(|(d, idx)| {
+            let data = series[d].data;
+            let value = (idx < data.len()).then(|| data.y(idx)).filter(|v| !is_missing(*v))?;
+            // The drawn point, or where it would be when decimation left it out
+            let (anchor, color) = match dataset_indices[d].binary_search(&idx) {
+                Ok(k) => (dataset_points[d][k].pos(), dataset_points[d][k].fill_color),
+                Err(_) => (
+                    Pos2::new(x_scale.value_to_pixel(data.x(idx)), y_scale.value_to_pixel(value)),
+                    series[d].color,
+                ),
+            };
+            let category = self
+                .labels
+                .get(idx)
+                .cloned()
+                .unwrap_or_else(|| format!("Point {}", self.first_index() + idx as u64 + 1));
+            let (title, label) = match series[d].label {
+                Some(dataset_label) => (Some(category), dataset_label.to_string()),
+                None => (None, category),
+            };
+
+            Some(ChartTooltip {
+                content: TooltipContent {
+                    title,
+                    label,
+                    value: format_value(value),
+                    color,
+                },
+                anchor,
+            })
+        });
 
-                    draw_tooltip(&painter, &content, tooltip_pos, &self.theme.tooltip);
-                }
-            }
+        let plugin_ctx = PluginContext {
+            tooltip: tooltip.as_ref(),
+            ..plugin_ctx
+        };
+        for plugin in &plugins {
+            plugin.after_draw(&plugin_ctx);
         }
+        TooltipPlugin.after_draw(&plugin_ctx);
 
         // Store memory
         ui.ctx().data_mut(|d| d.insert_temp(id, memory.clone()));
+        animation.store(ui.ctx(), id);
+        if let Some(link) = &self.link {
+            link.push(ui.ctx(), id, &state);
+        }
+        state.store(ui.ctx(), id);
 
         LineChartResponse {
             response,
-            hovered: memory.hovered_index,
-            clicked: clicked_index,
+            hovered: hovered.map(|(_, i)| i),
+            hovered_dataset: hovered.map(|(d, _)| d),
+            clicked: clicked.map(|(_, i)| i),
+            clicked_dataset: clicked.map(|(d, _)| d),
+            hovered_annotation,
+            clicked_annotation,
+            visible_range,
         }
     }
 
-    /// Build line and point elements
-    fn build_line_elements(&self, chart_rect: Rect) -> (LineElement, Vec<PointElement>) {
-        if self.data.is_empty() {
-            return (LineElement::new(vec![]), vec![]);
-        }
-
-        let line_color = self.color.to_color32();
-        let n = self.data.len();
+    /// All datasets: the primary one from `.data()` followed by `.dataset()`
+    /// extras. The primary one keeps index 0 even when empty, so dataset
+    /// indices don't shift when its data comes and goes.
+    fn series(&self) -> Vec<Series<'_>> {
+        let primary = Series {
+            label: self.label.as_deref(),
+            data: &self.data,
+            color: self.color.to_color32(),
+        };
 
-        // Calculate scales
-        let max_val = self.data.iter().cloned().fold(f64::NEG_INFINITY, f64::max).max(0.0) * 1.1;
-        let min_val = self.data.iter().cloned().fold(f64::INFINITY, f64::min).min(0.0);
+        std::iter::once(primary)
+            .chain(self.datasets.iter().map(|ds| Series {
+                label: Some(ds.label.as_str()),
+                data: &ds.data,
+                color: ds.color.to_color32(),
+            }))
+            .collect()
+    }
 
-        let y_range = max_val - min_val;
-        let y_scale = if y_range > 0.0 {
-            chart_rect.height() as f64 / y_range
+    /// Crosshair position under the pointer, snapped to the nearest point if configured
+    fn crosshair_target(
+        &self,
+        crosshair: &Crosshair,
+        pointer: Option<Pos2>,
+        x_scale: &LinearScale,
+        y_scale: &LinearScale,
+        series: &[Series<'_>],
+        state: &ChartState,
+    ) -> Option<CrosshairTarget> {
+        let pos = pointer?;
+        let data = longest(series)?;
+        let index = nearest_point(data, x_scale.pixel_to_value(pos.x))?;
+
+        let (x, y) = if crosshair.snap_to_index {
+            // The visible point at that index closest to the pointer
+            let y = series
+                .iter()
+                .enumerate()
+                .filter(|(d, _)| state.is_visible(*d))
+                .filter(|(_, s)| index < s.data.len())
+                .map(|(_, s)| s.data.y(index))
+                .filter(|y| !is_missing(*y))
+                .min_by(|a, b| {
+                    let da = (y_scale.value_to_pixel(*a) - pos.y).abs();
+                    let db = (y_scale.value_to_pixel(*b) - pos.y).abs();
+                    da.total_cmp(&db)
+                })
+                .unwrap_or_else(|| y_scale.pixel_to_value(pos.y));
+            (data.x(index), y)
         } else {
-            1.0
+            (x_scale.pixel_to_value(pos.x), y_scale.pixel_to_value(pos.y))
         };
 
-        let x_step = chart_rect.width() / (n - 1).max(1) as f32;
+        Some(CrosshairTarget {
+            x: Some((x, self.category_label(index))),
+            y: Some((y, format_value(y))),
+        })
+    }
+
+    /// Label of the category at `index`
+    fn category_label(&self, index: usize) -> String {
+        self.labels
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| format!("{}", self.first_index() + index as u64 + 1))
+    }
+
+    /// Number of the first data point: how many samples of a stream slid out
+    fn first_index(&self) -> u64 {
+        self.stream.map_or(0, |stream| stream.first_sample(self.data.len()))
+    }
+
+    /// Full x range for `n` points. Streams keep the newest sample at the
+    /// right edge, lagging behind by the scroll `offset`, and span their
+    /// time window or a full buffer from the start.
+    fn x_range(&self, series: &[Series<'_>], offset: f32) -> (f64, f64) {
+        if let Some(stream) = self.stream {
+            if let Some(window) = stream.window {
+                let last = stream.latest.unwrap_or(0.0) + offset as f64;
+                return (last - window, last);
+            }
+            let n = series.iter().map(|s| s.data.len()).max().unwrap_or(0);
+            let span = stream.span.unwrap_or(n).max(2) as f64;
+            let last = n as f64 - 1.0 + offset as f64;
+            return (last - (span - 1.0), last);
+        }
+
+        let (first, last) = series
+            .iter()
+            .filter(|s| !s.data.is_empty())
+            .map(|s| (s.data.x(0), s.data.x(s.data.len() - 1)))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), (first, last)| (a.min(first), b.max(last)));
+        if first.is_finite() && last > first {
+            (first, last)
+        } else if first.is_finite() {
+            (first, first + 1.0)
+        } else {
+            (0.0, 1.0)
+        }
+    }
+
+    /// Build the line, with its points, through the given data indices of one
+    /// dataset, none of them missing
+    fn build_line_element(
+        &self,
+        series: &Series<'_>,
+        indices: &[usize],
+        x_scale: &LinearScale,
+        y_scale: &LinearScale,
+    ) -> LineElement {
+        let line_color = series.color;
 
         // Build points
-        let points: Vec<PointElement> = self
-            .data
+        let points: Vec<PointElement> = indices
             .iter()
-            .enumerate()
-            .map(|(i, &val)| {
-                let x = chart_rect.min.x + i as f32 * x_step;
-                let y = chart_rect.max.y - ((val - min_val) * y_scale) as f32;
+            .map(|&i| {
+                let x = x_scale.value_to_pixel(series.data.x(i));
+                let y = y_scale.value_to_pixel(series.data.y(i));
 
                 let mut point = PointElement::new(x, y);
                 point.fill_color = line_color;
@@ -415,35 +1036,69 @@ impl LineChart {
             })
             .collect();
 
-        // Build line
-        let mut line = LineElement::new(points.clone());
+        // Build line, broken at gaps
+        let mut line = LineElement::new(points);
+        line.breaks = line_breaks(series.data, indices, self.line_style.span_gaps);
         line.color = line_color;
         line.width = self.line_style.width;
         line.curved = self.line_style.curved;
         line.tension = self.line_style.tension;
+        line.interpolation = self.line_style.interpolation;
+        line.stepped = self.line_style.stepped;
+        line.cap = self.line_style.cap;
+        line.join = self.line_style.join;
 
-        (line, points)
+        line
     }
 
-    /// Draw grid lines
-    fn draw_grid(&self, painter: &Painter, chart_rect: Rect) {
-        let max_val = self.data.iter().cloned().fold(0.0_f64, f64::max) * 1.1;
-        let ticks = nice_ticks(0.0, max_val, 5);
+    /// Hash of the style settings elements and meshes are built from
+    fn style_key(&self) -> u64 {
+        let style = &self.line_style;
+        let hover = &style.hover;
+        cache_key((
+            [style.width, style.point_radius, style.point_border_width, style.tension].map(f32::to_bits),
+            style.point_border_color,
+            (style.curved, style.interpolation, style.stepped, style.cap, style.join),
+            style.span_gaps.map(f64::to_bits),
+            (hover.background_color, hover.border_color),
+            [hover.border_width, hover.radius].map(|v| v.map(f32::to_bits)),
+            self.decimation,
+        ))
+    }
 
-        let y_scale = if max_val > 0.0 {
-            chart_rect.height() as f64 / max_val
-        } else {
-            1.0
-        };
+    /// Apply the animated properties of one point
+    fn animate_point(
+        &self,
+        point: &PointElement,
+        index: usize,
+        animation: &AnimationState,
+        chart_rect: Rect,
+    ) -> PointElement {
+        let base_y = chart_rect.max.y;
+        let mut point = point.clone();
 
-        for tick in &ticks {
-            let y = chart_rect.max.y - (*tick * y_scale) as f32;
-            if y >= chart_rect.min.y && y <= chart_rect.max.y {
-                painter.line_segment(
-                    [Pos2::new(chart_rect.min.x, y), Pos2::new(chart_rect.max.x, y)],
-                    Stroke::new(1.0, self.theme.grid_color),
-                );
-            }
+        point.y = base_y + (point.y - base_y) * animation.property_progress(AnimatedProperty::Y, index);
+        if self.animation.animates(AnimatedProperty::X) {
+            let p = animation.property_progress(AnimatedProperty::X, index);
+            point.x = chart_rect.min.x + (point.x - chart_rect.min.x) * p;
+        }
+        if self.animation.animates(AnimatedProperty::Radius) {
+            point.radius *= animation.property_progress(AnimatedProperty::Radius, index);
+        }
+        point.fill_color = animation.animate_color(point.fill_color, index);
+        point.border_color = animation.animate_color(point.border_color, index);
+
+        point
+    }
+
+    /// Draw grid lines
+    fn draw_grid(&self, painter: &Painter, chart_rect: Rect, y_scale: &LinearScale) {
+        for tick in y_scale.ticks(5) {
+            let y = y_scale.value_to_pixel(tick);
+            painter.line_segment(
+                [Pos2::new(chart_rect.min.x, y), Pos2::new(chart_rect.max.x, y)],
+                Stroke::new(1.0, self.theme.grid_color),
+            );
         }
     }
 
@@ -453,48 +1108,66 @@ impl LineChart {
 
         painter.line_segment([chart_rect.left_bottom(), chart_rect.left_top()], stroke);
         painter.line_segment([chart_rect.left_bottom(), chart_rect.right_bottom()], stroke);
+    }
 
-        // Y axis labels
-        let max_val = self.data.iter().cloned().fold(0.0_f64, f64::max) * 1.1;
-        let ticks = nice_ticks(0.0, max_val, 5);
-
-        let y_scale = if max_val > 0.0 {
-            chart_rect.height() as f64 / max_val
-        } else {
-            1.0
+    /// Lay out the y axis labels (with axes shown) and the labels of the
+    /// points inside the visible x range, skipping some when there are too
+    /// many to fit. Returns each galley with its top left corner.
+    fn layout_axis_labels(
+        &self,
+        painter: &Painter,
+        chart_rect: Rect,
+        data: Option<&dyn ChartData>,
+        x_scale: &LinearScale,
+        y_scale: &LinearScale,
+    ) -> PlacedGalleys {
+        let font = FontId::proportional(11.0);
+        let color = self.theme.text_color;
+        let mut labels = Vec::new();
+        let mut place = |text: String, anchor: Pos2, align: Align2| {
+            let galley = painter.layout_no_wrap(text, font.clone(), color);
+            labels.push((align.anchor_size(anchor, galley.size()).min, galley));
         };
 
-        for tick in &ticks {
-            let y = chart_rect.max.y - (*tick * y_scale) as f32;
-            if y >= chart_rect.min.y && y <= chart_rect.max.y {
-                painter.text(
-                    Pos2::new(chart_rect.min.x - 8.0, y),
-                    egui::Align2::RIGHT_CENTER,
-                    format_axis_value(*tick),
-                    egui::FontId::proportional(11.0),
-                    self.theme.text_color,
-                );
+        if self.show_axes {
+            for tick in y_scale.ticks(5) {
+                let anchor = Pos2::new(chart_rect.min.x - 8.0, y_scale.value_to_pixel(tick));
+                place(format_axis_value(tick), anchor, Align2::RIGHT_CENTER);
             }
         }
-    }
 
-    /// Draw labels
-    fn draw_labels(&self, painter: &Painter, chart_rect: Rect, points: &[PointElement]) {
-        for (i, point) in points.iter().enumerate() {
-            let label = self.labels.get(i).cloned().unwrap_or_else(|| format!("{}", i + 1));
-
-            painter.text(
-                Pos2::new(point.x, chart_rect.max.y + 12.0),
-                egui::Align2::CENTER_TOP,
-                label,
-                egui::FontId::proportional(11.0),
-                self.theme.text_color,
-            );
+        let Some(data) = data else { return labels };
+        let visible = visible_indices(data, x_scale.pixel_to_value(chart_rect.min.x), x_scale.pixel_to_value(chart_rect.max.x));
+        let slots = (chart_rect.width() / MIN_LABEL_SPACING).max(1.0) as usize;
+        let step = visible.len().div_ceil(slots).max(1) as u64;
+        // Every `step`th point counting from the first, so labels stay with
+        // their points while a stream scrolls
+        let first = self.first_index();
+        for i in visible.filter(|i| (first + *i as u64).is_multiple_of(step)) {
+            let x = x_scale.value_to_pixel(data.x(i));
+            if x < chart_rect.min.x - 0.5 || x > chart_rect.max.x + 0.5 {
+                continue;
+            }
+            place(self.category_label(i), Pos2::new(x, chart_rect.max.y + 12.0), Align2::CENTER_TOP);
         }
+        labels
     }
 }
 
-impl Widget for LineChart {
+/// The dataset with the most points, which x axis labels and the crosshair follow
+fn longest<'a>(series: &[Series<'a>]) -> Option<&'a dyn ChartData> {
+    series.iter().rev().max_by_key(|s| s.data.len()).map(|s| s.data)
+}
+
+/// A point faded by the visibility `amount` of its dataset
+fn fade_point(point: &PointElement, amount: f32) -> PointElement {
+    let mut point = point.clone();
+    point.fill_color = point.fill_color.gamma_multiply(amount);
+    point.border_color = point.border_color.gamma_multiply(amount);
+    point
+}
+
+impl Widget for LineChart<'_> {
     fn ui(self, ui: &mut Ui) -> Response {
         self.show(ui).response
     }
@@ -523,3 +1196,87 @@ fn format_axis_value(value: f64) -> String {
         format!("{:.1}", value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::Animation;
+    use crate::data_labels::LabelOverlap;
+    use egui::{CentralPanel, Context, Event, RawInput};
+
+    /// Show the chart `build` makes for two frames with the pointer at `pointer`
+    fn show_frames(ctx: &Context, pointer: Pos2, build: impl Fn() -> LineChart<'static>) -> LineChartResponse {
+        let mut response = None;
+        for _ in 0..2 {
+            let input = RawInput {
+                screen_rect: Some(Rect::from_min_size(Pos2::ZERO, Vec2::new(600.0, 400.0))),
+                events: vec![Event::PointerMoved(pointer)],
+                ..Default::default()
+            };
+            let _ = ctx.run(input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| response = Some(build().show(ui)));
+            });
+        }
+        response.unwrap()
+    }
+
+    #[test]
+    fn test_datasets_keep_indices_without_primary_data() {
+        let ctx = Context::default();
+        let id = Id::new("line");
+        let mut state = ChartState::default();
+        state.set_visible(1, false);
+        state.store(&ctx, id);
+
+        let build = || {
+            LineChart::new()
+                .id(id)
+                .data(Vec::<f64>::new())
+                .dataset(LineDataset::new("A", [10.0, 10.0, 10.0]))
+                .dataset(LineDataset::new("B", [90.0, 90.0, 90.0]))
+                .animate(Animation::none())
+                .size([400.0, 300.0])
+        };
+        let chart = build();
+        let labels: Vec<Option<&str>> = chart.series().iter().map(|s| s.label).collect();
+        assert_eq!(labels, vec![None, Some("A"), Some("B")]);
+
+        // Find the chart area, then point at the middle point of each line;
+        // the y axis runs from 0 to 99 with only B shown
+        let rect = show_frames(&ctx, Pos2::ZERO, build).response.rect;
+        let area = Rect::from_min_max(rect.min + Vec2::new(45.0, 15.0), rect.max - Vec2::new(15.0, 30.0));
+        let point = |value: f32| Pos2::new(area.center().x, area.max.y - area.height() * value / 99.0);
+
+        let hovered = show_frames(&ctx, point(90.0), build);
+        assert_eq!((hovered.hovered_dataset, hovered.hovered), (Some(2), Some(1)));
+        // Hiding dataset 1 hid A, not B
+        assert_eq!(show_frames(&ctx, point(10.0), build).hovered, None);
+    }
+
+    #[test]
+    fn test_stream_x_range() {
+        let mut windowed = DataStream::with_window(2.0);
+        let mut capped = DataStream::with_capacity(5);
+        for i in 0..8 {
+            windowed.push(10.0 + i as f64 * 0.5, 1.0);
+            capped.push(10.0 + i as f64 * 0.5, 1.0);
+        }
+
+        // By time, spanning the window up to the newest sample
+        let chart = LineChart::new().stream(&windowed);
+        assert_eq!(chart.data.x(0), 11.5);
+        assert_eq!(chart.x_range(&chart.series(), 0.0), (11.5, 13.5));
+        assert_eq!(chart.x_range(&chart.series(), -0.25), (11.25, 13.25));
+
+        // One step per sample, spanning a full buffer
+        let chart = LineChart::new().stream(&capped);
+        assert_eq!(chart.data.x(4), 4.0);
+        assert_eq!(chart.x_range(&chart.series(), 0.0), (0.0, 4.0));
+    }
+
+    #[test]
+    fn test_show_values_draws_every_label() {
+        let chart = LineChart::new().show_values(true);
+        assert_eq!(chart.data_labels.map(|labels| labels.overlap), Some(LabelOverlap::Show));
+    }
+}
@@ -0,0 +1,292 @@
+use egui::{Color32, Context, Id, Painter, Pos2, Rect, Stroke};
+
+use crate::state::ChartState;
+
+/// Charts sharing a link group synchronize their cursor, zoom and visibility
+///
+/// Mirrors egui_plot's `link_axis` / `link_cursor`. Give every chart in the
+/// group the same group id; each chart still needs its own `.id()`.
+///
+/// ```rust,ignore
+/// let group = LinkGroup::new("dashboard");
+/// for (i, series) in all_series.iter().enumerate() {
+///     LineChart::new().id(("cpu", i)).data(series.clone()).link(group).show(ui);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinkGroup {
+    pub id: Id,
+    /// Share the zoomed/panned x range
+    pub link_x: bool,
+    /// Share the zoomed/panned y range
+    pub link_y: bool,
+    /// Show the hovered x position (crosshair and tooltip) on every chart
+    pub link_cursor: bool,
+    /// Share dataset and data visibility
+    pub link_visibility: bool,
+}
+
+impl LinkGroup {
+    /// Link x range, cursor and visibility; y ranges stay independent
+    pub fn new(id: impl Into<Id>) -> Self {
+        Self {
+            id: id.into(),
+            link_x: true,
+            link_y: false,
+            link_cursor: true,
+            link_visibility: true,
+        }
+    }
+
+    /// Share the x range
+    pub fn link_x(mut self, link: bool) -> Self {
+        self.link_x = link;
+        self
+    }
+
+    /// Share the y range
+    pub fn link_y(mut self, link: bool) -> Self {
+        self.link_y = link;
+        self
+    }
+
+    /// Share the hovered x position
+    pub fn link_cursor(mut self, link: bool) -> Self {
+        self.link_cursor = link;
+        self
+    }
+
+    /// Share dataset and data visibility
+    pub fn link_visibility(mut self, link: bool) -> Self {
+        self.link_visibility = link;
+        self
+    }
+
+    /// Pull the group's shared zoom and visibility into a member's state
+    ///
+    /// Edits made directly to the member's [`ChartState`] since its last frame
+    /// win, and are published to the group by [`LinkGroup::push`].
+    pub(crate) fn pull(&self, ctx: &Context, chart_id: Id, state: &mut ChartState) {
+        let synced: Option<ChartState> = ctx.data(|d| d.get_temp::<LinkMember>(chart_id)).map(|m| m.synced);
+        if synced.is_some_and(|synced| synced != *state) {
+            return;
+        }
+        if let Some(shared) = self.load(ctx).state {
+            state.copy_linked(&shared, self);
+        }
+    }
+
+    /// Publish a member's state to the group at the end of its frame
+    pub(crate) fn push(&self, ctx: &Context, chart_id: Id, state: &ChartState) {
+        let mut group = self.load(ctx);
+        group.state.get_or_insert_with(ChartState::default).copy_linked(state, self);
+        ctx.data_mut(|d| {
+            d.insert_temp(self.id, group);
+            d.insert_temp(chart_id, LinkMember { synced: state.clone() });
+        });
+    }
+
+    /// The hovered x position in the group, if any chart is hovered
+    pub(crate) fn cursor(&self, ctx: &Context) -> Option<LinkedCursor> {
+        if !self.link_cursor {
+            return None;
+        }
+        // Charts drawn earlier in the frame see the cursor one frame late
+        let pass = ctx.cumulative_pass_nr();
+        self.load(ctx).cursor.filter(|c| c.pass + 1 >= pass)
+    }
+
+    /// Publish (or clear) the x position hovered on the chart `chart_id`
+    pub(crate) fn set_cursor(&self, ctx: &Context, chart_id: Id, x: Option<f64>) {
+        if !self.link_cursor {
+            return;
+        }
+        let mut group = self.load(ctx);
+        match x {
+            Some(x) => {
+                group.cursor = Some(LinkedCursor {
+                    x,
+                    source: chart_id,
+                    pass: ctx.cumulative_pass_nr(),
+                });
+            }
+            None if group.cursor.is_some_and(|c| c.source == chart_id) => group.cursor = None,
+            None => return,
+        }
+        ctx.data_mut(|d| d.insert_temp(self.id, group));
+    }
+
+    fn load(&self, ctx: &Context) -> LinkGroupMemory {
+        ctx.data(|d| d.get_temp(self.id)).unwrap_or_default()
+    }
+}
+
+/// Hovered x position shared within a link group
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct LinkedCursor {
+    /// Position along the data, with point or category `i` at `x = i`.
+    /// Bar charts, whose category `i` spans `[i, i + 1)` on their own axis,
+    /// convert to and from it.
+    pub x: f64,
+    /// Chart that is hovered
+    pub source: Id,
+    pass: u64,
+}
+
+/// Shared state of a link group, stored under the group id
+#[derive(Clone, Debug, Default)]
+struct LinkGroupMemory {
+    state: Option<ChartState>,
+    cursor: Option<LinkedCursor>,
+}
+
+/// A member's state as of its last sync, to tell external edits apart
+#[derive(Clone, Debug)]
+struct LinkMember {
+    synced: ChartState,
+}
+
+/// Vertical line marking the linked cursor
+pub(crate) fn draw_cursor_line(painter: &Painter, chart_rect: Rect, x: f32, color: Color32) {
+    if x < chart_rect.min.x || x > chart_rect.max.x {
+        return;
+    }
+    painter.line_segment(
+        [Pos2::new(x, chart_rect.min.y), Pos2::new(x, chart_rect.max.y)],
+        Stroke::new(1.0, color),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    use egui::{CentralPanel, Event, RawInput, Vec2};
+
+    use crate::animation::Animation;
+    use crate::plugin::{ChartPlugin, PluginContext};
+    use crate::{BarChart, LineChart};
+
+    /// Chart area and hovered (dataset, index) seen by a plugin
+    type Seen = Option<(Rect, Option<(usize, usize)>)>;
+
+    /// Records what the chart last drew, linked hover included
+    #[derive(Clone, Default)]
+    struct Probe(Arc<Mutex<Seen>>);
+
+    impl ChartPlugin for Probe {
+        fn after_draw(&self, ctx: &PluginContext) {
+            *self.0.lock().unwrap() = Some((ctx.chart_area, ctx.hovered));
+        }
+    }
+
+    impl Probe {
+        fn area(&self) -> Rect {
+            self.0.lock().unwrap().unwrap().0
+        }
+
+        fn hovered(&self) -> Option<usize> {
+            self.0.lock().unwrap().unwrap().1.map(|(_, i)| i)
+        }
+    }
+
+    #[test]
+    fn test_zoom_and_visibility_propagate() {
+        let ctx = Context::default();
+        let group = LinkGroup::new("group");
+        let (a, b) = (Id::new("a"), Id::new("b"));
+
+        // Chart A zooms and hides a dataset
+        let mut state_a = ChartState::default();
+        group.pull(&ctx, a, &mut state_a);
+        state_a.set_x_range(Some((2.0, 5.0)));
+        state_a.set_y_range(Some((0.0, 10.0)));
+        state_a.set_visible(1, false);
+        group.push(&ctx, a, &state_a);
+
+        // Chart B picks up the x range and visibility, but not the y range
+        let mut state_b = ChartState::default();
+        group.pull(&ctx, b, &mut state_b);
+        assert_eq!(state_b.x_range(), Some((2.0, 5.0)));
+        assert_eq!(state_b.y_range(), None);
+        assert!(!state_b.is_visible(1));
+        group.push(&ctx, b, &state_b);
+
+        // An external edit to B's state wins over the group on its next frame
+        let mut edited = state_b.clone();
+        edited.reset_zoom();
+        group.pull(&ctx, b, &mut edited);
+        assert_eq!(edited.x_range(), None);
+        group.push(&ctx, b, &edited);
+
+        let mut state_a = state_a.clone();
+        group.pull(&ctx, a, &mut state_a);
+        assert_eq!(state_a.x_range(), None);
+    }
+
+    #[test]
+    fn test_cursor_cleared_by_source_only() {
+        let ctx = Context::default();
+        let group = LinkGroup::new("group");
+        let (a, b) = (Id::new("a"), Id::new("b"));
+
+        group.set_cursor(&ctx, a, Some(3.0));
+        assert_eq!(group.cursor(&ctx).map(|c| (c.x, c.source)), Some((3.0, a)));
+
+        group.set_cursor(&ctx, b, None);
+        assert!(group.cursor(&ctx).is_some());
+
+        group.set_cursor(&ctx, a, None);
+        assert!(group.cursor(&ctx).is_none());
+
+        assert!(group.link_cursor(false).cursor(&ctx).is_none());
+    }
+
+    #[test]
+    fn test_bar_and_line_cursor_agree() {
+        let ctx = Context::default();
+        let group = LinkGroup::new("group");
+        let (bar, line) = (Probe::default(), Probe::default());
+        let frames = |pointer: Pos2| {
+            for _ in 0..3 {
+                let input = RawInput {
+                    screen_rect: Some(Rect::from_min_size(Pos2::ZERO, Vec2::new(600.0, 600.0))),
+                    events: vec![Event::PointerMoved(pointer)],
+                    ..Default::default()
+                };
+                let _ = ctx.run(input, |ctx| {
+                    CentralPanel::default().show(ctx, |ui| {
+                        let data = [10.0, 20.0, 30.0];
+                        BarChart::new()
+                            .id("bar")
+                            .data(data)
+                            .link(group)
+                            .animate(Animation::none())
+                            .size([400.0, 200.0])
+                            .plugin(bar.clone())
+                            .show(ui);
+                        LineChart::new()
+                            .id("line")
+                            .data(data)
+                            .link(group)
+                            .animate(Animation::none())
+                            .size([400.0, 200.0])
+                            .plugin(line.clone())
+                            .show(ui);
+                    });
+                });
+            }
+        };
+        frames(Pos2::ZERO);
+        let at = |area: Rect, x: f32, span: f32| Pos2::new(area.min.x + area.width() * x / span, area.center().y);
+
+        // The right half of bar 1 (bars span 0..3) highlights line point 1
+        frames(at(bar.area(), 1.8, 3.0));
+        assert_eq!(line.hovered(), Some(1));
+
+        // Nearest to line point 2 (points span 0..2) highlights bar 2
+        frames(at(line.area(), 1.6, 2.0));
+        assert_eq!(bar.hovered(), Some(2));
+    }
+}
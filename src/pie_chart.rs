@@ -1,19 +1,35 @@
-use egui::{Color32, CornerRadius, Id, Pos2, Response, Sense, Ui, Vec2, Widget};
-use std::f32::consts::PI;
-
-use crate::animation::{AnimationConfig, AnimationState};
-use crate::elements::arc::{ArcElement, PieStyle};
-use crate::helpers::color::{lighten, ChartColor};
-use crate::helpers::math::compute_data_hash;
+use egui::{Color32, CornerRadius, Id, Pos2, Response, Sense, Stroke, Ui, Vec2, Widget};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use crate::animation::{update_chart_animation, AnimatedProperty, AnimationConfig};
+use crate::cache::cache_key;
+use crate::data::{data_key, is_missing, values, ChartData, Values};
+use crate::data_labels::{draw_data_labels, DataLabels, LabelContext, LabelRequest, LabelTarget};
+use crate::donut_center::{CenterContext, CenterSegment, DonutCenter};
+use crate::elements::arc::{sweep_bounds, ArcElement, PieStyle};
+use crate::helpers::color::{darken, lerp_color, ChartColor};
+use crate::interaction::{HoverStyle, HoverTransitions};
+use crate::helpers::math::compute_series_hash;
+use crate::state::ChartState;
+use crate::pie_rings::{
+    dataset_rings, ring_radii, segment_value, sunburst_rings, PieDataset, RingGeometry, RingSegment, SunburstNode,
+};
+use crate::pie_labels::{layout_outer_labels, outer_label_padding, OUTER_LABEL_FONT_SIZE};
+use crate::plugin::{chart_plugins, ChartElements, ChartEvent, ChartLayout, ChartPlugin, ChartTooltip, PluginContext};
 use crate::theme::{ChartTheme, ThemePreset};
-use crate::tooltip::{calculate_tooltip_position, draw_tooltip, measure_tooltip_size, TooltipContent};
+use crate::title::Title;
+use crate::tooltip::{TooltipContent, TooltipPlugin};
 
 /// Memory stored in egui context between frames
 #[derive(Clone, Default)]
 struct PieChartMemory {
-    animation: AnimationState,
     data_hash: u64,
     hovered_index: Option<usize>,
+    hovered_segment: Option<(usize, usize)>,
+    hover: HoverTransitions,
+    /// Segments toggled on by clicking, when the chart is selectable
+    selected: BTreeSet<usize>,
 }
 
 /// Response returned after showing the chart
@@ -25,13 +41,26 @@ pub struct PieChartResponse {
     pub hovered: Option<usize>,
     /// Index of clicked segment (if any this frame)
     pub clicked: Option<usize>,
+    /// Hovered (ring, segment) of any ring
+    ///
+    /// Ring 0 is the main ring (`.data()`, or the roots of a sunburst). Extra
+    /// datasets are numbered inwards from 1, sunburst levels outwards.
+    pub hovered_segment: Option<(usize, usize)>,
+    /// Clicked (ring, segment) of any ring (if any this frame)
+    pub clicked_segment: Option<(usize, usize)>,
+    /// Selected segments of the main ring, in index order
+    pub selected: Vec<usize>,
 }
 
 /// Pie/Donut chart widget with Chart.js-inspired API
 #[derive(Clone)]
-pub struct PieChart {
+pub struct PieChart<'a> {
     id: Option<Id>,
-    data: Vec<f64>,
+    data: Values<'a>,
+    label: Option<String>,
+    weight: f32,
+    datasets: Vec<PieDataset>,
+    sunburst: Vec<SunburstNode>,
     labels: Vec<String>,
     colors: Vec<ChartColor>,
     animation: AnimationConfig,
@@ -42,13 +71,26 @@ pub struct PieChart {
     pie_style: PieStyle,
     show_labels: bool,
     show_percentages: bool,
+    label_min_angle: f32,
+    data_labels: Option<DataLabels>,
+    center: DonutCenter,
+    offset: f32,
+    offsets: Vec<f32>,
+    selectable: bool,
+    selected_offset: f32,
+    selected_border: Option<Stroke>,
+    plugins: Vec<Arc<dyn ChartPlugin>>,
 }
 
-impl Default for PieChart {
+impl Default for PieChart<'_> {
     fn default() -> Self {
         Self {
             id: None,
-            data: Vec::new(),
+            data: Values::default(),
+            label: None,
+            weight: 1.0,
+            datasets: Vec::new(),
+            sunburst: Vec::new(),
             labels: Vec::new(),
             colors: Vec::new(),
             animation: AnimationConfig::default(),
@@ -59,11 +101,20 @@ impl Default for PieChart {
             pie_style: PieStyle::default(),
             show_labels: false,
             show_percentages: false,
+            label_min_angle: 4.0_f32.to_radians(),
+            data_labels: None,
+            center: DonutCenter::default(),
+            offset: 0.0,
+            offsets: Vec::new(),
+            selectable: false,
+            selected_offset: 10.0,
+            selected_border: None,
+            plugins: Vec::new(),
         }
     }
 }
 
-impl PieChart {
+impl<'a> PieChart<'a> {
     /// Create a new pie chart
     pub fn new() -> Self {
         Self::default()
@@ -77,7 +128,52 @@ impl PieChart {
 
     /// Set chart data values
     pub fn data(mut self, data: impl IntoIterator<Item = impl Into<f64>>) -> Self {
-        self.data = data.into_iter().map(|v| v.into()).collect();
+        self.data = Values::Owned(data.into_iter().map(|v| v.into()).collect());
+        self
+    }
+
+    /// Read the chart data in place, from `&values[..]` or a reference to
+    /// your own [`ChartData`] type, instead of copying it into the chart
+    pub fn data_source(mut self, data: impl ChartData + 'a) -> Self {
+        self.data = Values::Source(Arc::new(data));
+        self
+    }
+
+    /// Set the label of the main dataset, shown in tooltips when there are several rings
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the main ring's thickness relative to the other rings (default: 1)
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.weight = weight.max(0.0);
+        self
+    }
+
+    /// Add another dataset, drawn as a ring inside the previous ones
+    pub fn dataset(mut self, dataset: PieDataset) -> Self {
+        self.datasets.push(dataset);
+        self
+    }
+
+    /// Show a hierarchy as a sunburst: the roots form the inner ring and each
+    /// level of children a ring further out, within their parent's angle
+    ///
+    /// Replaces `.data()` and `.labels()` with the roots' totals and labels.
+    pub fn sunburst(mut self, roots: impl IntoIterator<Item = SunburstNode>) -> Self {
+        self.sunburst = roots.into_iter().collect();
+        self.data = Values::Owned(self.sunburst.iter().map(SunburstNode::total).collect());
+        self.labels = self.sunburst.iter().map(|node| node.label.clone()).collect();
+        if self.sunburst.iter().any(|node| node.color.is_some()) {
+            let palette = self.palette();
+            self.colors = self
+                .sunburst
+                .iter()
+                .enumerate()
+                .map(|(i, node)| node.color.clone().unwrap_or(ChartColor::Rgba(palette[i % palette.len()])))
+                .collect();
+        }
         self
     }
 
@@ -99,18 +195,90 @@ impl PieChart {
         self
     }
 
+    /// Set where the first segment starts, in degrees clockwise from the top
+    pub fn rotation(mut self, degrees: f32) -> Self {
+        self.pie_style.start_angle = (degrees - 90.0).to_radians();
+        self
+    }
+
+    /// Set the angle covered by all segments, in degrees (180 for a half donut)
+    pub fn circumference(mut self, degrees: f32) -> Self {
+        self.pie_style.circumference = degrees.clamp(1.0, 360.0).to_radians();
+        self
+    }
+
     /// Set border width between segments
     pub fn border_width(mut self, width: f32) -> Self {
         self.pie_style.border_width = width;
         self
     }
 
+    /// Gap between segments, in pixels
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.pie_style.spacing = spacing.max(0.0);
+        self
+    }
+
+    /// Round segment corners, in pixels
+    pub fn border_radius(mut self, radius: f32) -> Self {
+        self.pie_style.border_radius = radius.max(0.0);
+        self
+    }
+
+    /// Stroke around each whole segment
+    pub fn outline(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.pie_style.outline = Some(stroke.into());
+        self
+    }
+
     /// Set border color
     pub fn border_color(mut self, color: impl Into<ChartColor>) -> Self {
         self.pie_style.border_color = color.into().to_color32();
         self
     }
 
+    /// Set hover appearance and transition of segments
+    pub fn hover_style(mut self, hover: HoverStyle) -> Self {
+        self.pie_style.hover = hover;
+        self
+    }
+
+    /// Distance a hovered segment moves outward
+    pub fn hover_offset(mut self, offset: f32) -> Self {
+        self.pie_style.hover.offset = offset;
+        self
+    }
+
+    /// Pull every segment out from the center (explode)
+    pub fn offset(mut self, offset: f32) -> Self {
+        self.offset = offset.max(0.0);
+        self
+    }
+
+    /// Pull individual segments out from the center, by index (added to `.offset()`)
+    pub fn offsets(mut self, offsets: impl IntoIterator<Item = f32>) -> Self {
+        self.offsets = offsets.into_iter().map(|o| o.max(0.0)).collect();
+        self
+    }
+
+    /// Toggle segments as selected by clicking them
+    pub fn selectable(mut self, selectable: bool) -> Self {
+        self.selectable = selectable;
+        self
+    }
+
+    /// Distance selected segments are pulled out
+    pub fn selected_offset(mut self, offset: f32) -> Self {
+        self.selected_offset = offset.max(0.0);
+        self
+    }
+
+    /// Border of selected segments (default: a darker shade of the segment)
+    pub fn selected_border(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.selected_border = Some(stroke.into());
+        self
+    }
+
     /// Show labels outside segments
     pub fn show_labels(mut self, show: bool) -> Self {
         self.show_labels = show;
@@ -123,6 +291,24 @@ impl PieChart {
         self
     }
 
+    /// Hide outside labels of segments narrower than this angle, in degrees
+    pub fn label_min_angle(mut self, degrees: f32) -> Self {
+        self.label_min_angle = degrees.to_radians();
+        self
+    }
+
+    /// Configure value labels on segments, inside when they fit
+    pub fn data_labels(mut self, labels: DataLabels) -> Self {
+        self.data_labels = Some(labels);
+        self
+    }
+
+    /// Set what the donut hole shows (default: the total)
+    pub fn center(mut self, center: DonutCenter) -> Self {
+        self.center = center;
+        self
+    }
+
     /// Configure animation
     pub fn animate(mut self, config: AnimationConfig) -> Self {
         self.animation = config;
@@ -159,6 +345,17 @@ impl PieChart {
         self
     }
 
+    /// Add a plugin hooking into this chart's drawing
+    pub fn plugin(mut self, plugin: impl ChartPlugin + 'static) -> Self {
+        self.plugins.push(Arc::new(plugin));
+        self
+    }
+
+    /// Add a title above the chart
+    pub fn title(self, text: impl Into<String>) -> Self {
+        self.plugin(Title::new(text))
+    }
+
     /// Show the chart and return response
     pub fn show(self, ui: &mut Ui) -> PieChartResponse {
         // Determine size (square for pie chart)
@@ -179,122 +376,253 @@ impl PieChart {
         let mut memory = ui
             .ctx()
             .data_mut(|d| d.get_temp_mut_or_insert_with::<PieChartMemory>(id, Default::default).clone());
+        let state = ChartState::load(ui.ctx(), id).unwrap_or_default();
 
         // Check for data changes
-        let new_data_hash = compute_data_hash(&self.data);
-        if memory.data_hash != new_data_hash {
-            memory.animation = AnimationState::new(self.animation.clone());
-            memory.data_hash = new_data_hash;
+        let mut sunburst_totals = Vec::new();
+        for node in &self.sunburst {
+            node.collect_totals(&mut sunburst_totals);
         }
-
-        let progress = memory.animation.progress();
-        memory.animation.request_repaint_if_animating(ui.ctx());
+        let new_data_hash = cache_key((
+            data_key(&self.data),
+            compute_series_hash(
+                self.datasets
+                    .iter()
+                    .map(|d| d.data.as_slice())
+                    .chain(std::iter::once(sunburst_totals.as_slice())),
+            ),
+        ));
+        let data_changed = memory.data_hash != new_data_hash;
+        memory.data_hash = new_data_hash;
+        let animation =
+            update_chart_animation(ui, id, &self.animation, data_changed, self.data.len());
+
+        let progress = animation.progress();
+
+        // Hidden segments shrink to nothing while the others close the gap;
+        // missing values have no segment
+        let transition_secs = self.animation.transition_secs(ui);
+        let shown: Vec<bool> = values(&self.data)
+            .enumerate()
+            .map(|(i, v)| state.is_data_visible(i) && !is_missing(v))
+            .collect();
+        let weights: Vec<f32> = (0..self.data.len())
+            .map(|i| if is_missing(self.data.y(i)) { 0.0 } else { state.data_amount(ui.ctx(), id, i, transition_secs) })
+            .collect();
+        let total: f64 = values(&self.data).zip(&shown).filter(|(_, shown)| **shown).map(|(v, _)| v).sum();
 
         // Draw background
         if self.theme.background_color != Color32::TRANSPARENT {
             painter.rect_filled(rect, CornerRadius::ZERO, self.theme.background_color);
         }
 
-        // Calculate pie geometry
-        let center = rect.center();
-        // Use more padding when labels are shown outside
-        let padding = if self.show_labels || self.show_percentages { 60.0 } else { 20.0 };
-        let outer_radius = (rect.width().min(rect.height()) / 2.0 - padding).max(10.0);
+        // Measure outside labels to leave room for them
+        let label_font = egui::FontId::proportional(OUTER_LABEL_FONT_SIZE);
+        let label_texts: Vec<(usize, String, Vec2)> = (0..self.data.len())
+            .filter(|&i| shown[i])
+            .filter_map(|i| {
+                let text = self.outer_label_text(i, total)?;
+                let size = painter.layout_no_wrap(text.clone(), label_font.clone(), Color32::PLACEHOLDER).size();
+                Some((i, text, size))
+            })
+            .collect();
+
+        // Calculate pie geometry, letting plugins reserve space
+        let plugins = chart_plugins(ui.ctx(), &self.plugins);
+        let mut layout = ChartLayout::uniform(20.0);
+        for plugin in &plugins {
+            plugin.before_layout(&mut layout);
+        }
+        let label_area = layout.chart_area(rect);
+        let label_padding = outer_label_padding(label_texts.iter().map(|(_, _, size)| *size));
+        layout.left += label_padding.left;
+        layout.right += label_padding.right;
+        layout.top += label_padding.top;
+        layout.bottom += label_padding.bottom;
+        let chart_area = layout.chart_area(rect);
+
+        // Fit the (possibly partial) circle into the chart area
+        let style = &self.pie_style;
+        let bounds = sweep_bounds(style.start_angle, style.circumference, style.donut_ratio);
+        // Leave room for segments pulled out from the center
+        let max_offset = self.offset
+            + self.offsets.iter().copied().fold(0.0, f32::max)
+            + if self.selectable { self.selected_offset } else { 0.0 };
+        let outer_radius = ((chart_area.width() / bounds.width()).min(chart_area.height() / bounds.height())
+            - max_offset)
+            .max(10.0);
+        let center = chart_area.center() - bounds.center().to_vec2() * outer_radius;
         let inner_radius = outer_radius * self.pie_style.donut_ratio;
 
-        // Build arc elements
-        let arcs = self.build_arc_elements(center, inner_radius, outer_radius);
+        // Split the radius between rings: datasets from the outside in,
+        // sunburst levels from the inside out. Ring 0 is the main one.
+        let sunburst_depth = self.sunburst.iter().map(SunburstNode::depth).max().unwrap_or(0);
+        let radii = if sunburst_depth > 0 {
+            let mut radii = ring_radii(inner_radius, outer_radius, &vec![1.0; sunburst_depth]);
+            radii.reverse();
+            radii
+        } else {
+            let ring_weights: Vec<f32> = std::iter::once(self.weight)
+                .chain(self.datasets.iter().map(|d| d.weight))
+                .collect();
+            ring_radii(inner_radius, outer_radius, &ring_weights)
+        };
+        let (main_inner, main_outer) = radii[0];
+
+        // Build arc elements, pulling out offset and selected segments
+        update_selection(&mut memory.selected, None, &shown);
+        let mut arcs = self.build_arc_elements(center, main_inner, main_outer, &weights);
+        let selected_amounts: Vec<f32> = (0..arcs.len())
+            .map(|i| {
+                let selected = self.selectable && memory.selected.contains(&i);
+                ui.ctx().animate_bool_with_time(id.with(("pie_selected", i)), selected, transition_secs)
+            })
+            .collect();
+        for (i, arc) in arcs.iter_mut().enumerate() {
+            let offset = self.segment_offset(i) + self.selected_offset * selected_amounts[i];
+            arc.center += Vec2::angled(arc.mid_angle()) * offset;
+        }
+        let rings = if sunburst_depth > 0 {
+            sunburst_rings(&self.sunburst, &arcs, &radii[1..])
+        } else {
+            let palette = self.palette();
+            let geometry = RingGeometry {
+                center,
+                start_angle: self.pie_style.start_angle,
+                circumference: self.pie_style.circumference,
+                weights: &weights,
+                colors: &palette,
+                border_color: self.pie_style.border_color,
+                border_width: self.pie_style.border_width,
+                spacing: self.pie_style.spacing,
+                border_radius: self.pie_style.border_radius,
+                outline: self.pie_style.outline,
+            };
+            dataset_rings(&self.datasets, &radii[1..], &self.labels, &geometry)
+        };
+
+        let plugin_ctx = PluginContext {
+            painter: &painter,
+            chart_id: id,
+            rect,
+            chart_area,
+            x_scale: None,
+            y_scale: None,
+            elements: ChartElements::None,
+            theme: &self.theme,
+            response: &response,
+            hovered: memory.hovered_segment,
+            tooltip: None,
+        };
+        for plugin in &plugins {
+            plugin.before_draw(&plugin_ctx);
+        }
 
         // Draw arcs
+        memory.hover.update(ui.ctx(), id, memory.hovered_index, &self.pie_style.hover);
+        let hover_style = &self.pie_style.hover;
         for (i, arc) in arcs.iter().enumerate() {
+            if weights[i] <= 0.0 {
+                continue;
+            }
             let mut arc = arc.clone();
+            arc.fill_color = animation.animate_color(arc.fill_color, i);
+            arc.border_color = animation.animate_color(arc.border_color, i);
+            if self.animation.animates(AnimatedProperty::Radius) {
+                let scale = animation.property_progress(AnimatedProperty::Radius, i);
+                arc.inner_radius *= scale;
+                arc.outer_radius *= scale;
+            }
 
-            // Hover effect - slightly expand
-            if memory.hovered_index == Some(i) {
-                arc.fill_color = lighten(arc.fill_color, 0.15);
-                // Expand outward slightly
-                let expand = 5.0;
-                let mid_angle = arc.mid_angle();
-                arc.center = Pos2::new(
-                    center.x + mid_angle.cos() * expand,
-                    center.y + mid_angle.sin() * expand,
-                );
+            // Hover effect - pull the segment outward
+            let hover = memory.hover.amount(i);
+            if hover > 0.0 {
+                arc.fill_color = hover_style.fill_color(arc.fill_color, hover);
+                arc.border_color = hover_style.border_color(arc.border_color, hover);
+                arc.border_width = hover_style.border_width(arc.border_width, hover);
+                let expand = hover_style.offset(hover);
+                arc.center += Vec2::angled(arc.mid_angle()) * expand;
             }
 
-            arc.draw_animated(&painter, progress);
+            // Selected segments get a distinct border
+            if selected_amounts[i] > 0.0 {
+                let stroke = self
+                    .selected_border
+                    .unwrap_or_else(|| Stroke::new(2.0, darken(arc.fill_color, 0.3)));
+                let amount = selected_amounts[i];
+                arc.border_color = lerp_color(arc.border_color, stroke.color, amount);
+                arc.border_width += (stroke.width - arc.border_width) * amount;
+            }
+
+            arc.draw_animated(
+                &painter,
+                animation.property_progress(AnimatedProperty::Angle, i),
+            );
         }
 
-        // Draw donut hole as a filled circle on top for perfectly round inner edge
-        if inner_radius > 0.0 {
-            let hole_color = if self.theme.background_color != Color32::TRANSPARENT {
-                self.theme.background_color
-            } else {
-                // Use white or dark based on theme
-                Color32::WHITE
-            };
-            painter.circle_filled(center, inner_radius, hole_color);
+        // Draw the other rings
+        let angle_progress = animation.property_progress(AnimatedProperty::Angle, 0);
+        for (k, ring) in rings.iter().enumerate() {
+            self.draw_ring(&painter, ring, k + 1, memory.hovered_segment, angle_progress);
         }
 
-        // Draw labels
-        if self.show_labels || self.show_percentages {
-            let total: f64 = self.data.iter().sum();
-            for (i, arc) in arcs.iter().enumerate() {
-                if progress > 0.5 {
-                    // Only show labels after animation is halfway
-                    let label_radius = outer_radius + 15.0;
-                    let pos = arc.mid_point(label_radius);
-
-                    let mut text = String::new();
-                    if self.show_labels {
-                        if let Some(label) = self.labels.get(i) {
-                            text.push_str(label);
-                        }
-                    }
-                    if self.show_percentages && total > 0.0 {
-                        let pct = self.data.get(i).unwrap_or(&0.0) / total * 100.0;
-                        if !text.is_empty() {
-                            text.push_str(": ");
-                        }
-                        text.push_str(&format!("{:.0}%", pct));
-                    }
+        let plugin_ctx = PluginContext {
+            elements: ChartElements::Arcs(&arcs),
+            ..plugin_ctx
+        };
+        for plugin in &plugins {
+            plugin.after_datasets_draw(&plugin_ctx);
+        }
 
-                    if !text.is_empty() {
-                        // Determine alignment based on position relative to center
-                        let align = if pos.x < center.x - 10.0 {
-                            egui::Align2::RIGHT_CENTER
-                        } else if pos.x > center.x + 10.0 {
-                            egui::Align2::LEFT_CENTER
-                        } else {
-                            // Near center horizontally - align based on vertical position
-                            if pos.y < center.y {
-                                egui::Align2::CENTER_BOTTOM
-                            } else {
-                                egui::Align2::CENTER_TOP
-                            }
-                        };
-
-                        painter.text(
-                            pos,
-                            align,
-                            text,
-                            egui::FontId::proportional(11.0),
-                            self.theme.text_color,
-                        );
-                    }
-                }
+        // Draw outside labels with leader lines, once the animation is halfway
+        if progress > 0.5 {
+            let sizes: Vec<(usize, Vec2)> = label_texts.iter().map(|(i, _, size)| (*i, *size)).collect();
+            let outer_arcs: Vec<ArcElement> = arcs
+                .iter()
+                .map(|arc| ArcElement { outer_radius, ..arc.clone() })
+                .collect();
+            for label in layout_outer_labels(&outer_arcs, &sizes, label_area, self.label_min_angle) {
+                let Some((_, text, _)) = label_texts.iter().find(|(i, _, _)| *i == label.index) else {
+                    continue;
+                };
+                let opacity = weights[label.index];
+                painter.add(egui::Shape::line(
+                    label.leader.to_vec(),
+                    Stroke::new(1.0, arcs[label.index].fill_color.gamma_multiply(opacity)),
+                ));
+                painter.text(
+                    label.text_pos,
+                    label.align,
+                    text,
+                    label_font.clone(),
+                    self.theme.text_color.gamma_multiply(opacity),
+                );
             }
         }
 
-        // Draw center text for donut
-        if self.pie_style.donut_ratio > 0.0 {
-            let total: f64 = self.data.iter().sum();
-            painter.text(
-                center,
-                egui::Align2::CENTER_CENTER,
-                format_value(total),
-                egui::FontId::proportional(16.0),
-                self.theme.text_color,
-            );
+        // Draw value labels
+        if let Some(data_labels) = self.data_labels.as_ref().filter(|_| progress > 0.5) {
+            let requests: Vec<LabelRequest> = arcs
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| weights[*i] > 0.0)
+                .filter_map(|(i, arc)| {
+                    let value = self.value(i)?;
+                    let context = LabelContext {
+                        dataset: 0,
+                        index: i,
+                        value,
+                        fraction: (total > 0.0).then(|| value / total),
+                    };
+                    Some(LabelRequest {
+                        target: LabelTarget::Arc(arc.clone()),
+                        text: data_labels.text(&context, format_value)?,
+                        color: arc.fill_color,
+                        opacity: weights[i],
+                    })
+                })
+                .collect();
+            draw_data_labels(&painter, data_labels, &requests, rect, &self.theme);
         }
 
         // Handle interaction
@@ -303,7 +631,7 @@ impl PieChart {
 
         if let Some(hover_pos) = response.hover_pos() {
             for (i, arc) in arcs.iter().enumerate() {
-                if arc.contains(hover_pos) {
+                if shown[i] && arc.contains(hover_pos) {
                     hovered_index = Some(i);
                     break;
                 }
@@ -313,7 +641,7 @@ impl PieChart {
         if response.clicked() {
             if let Some(pos) = response.interact_pointer_pos() {
                 for (i, arc) in arcs.iter().enumerate() {
-                    if arc.contains(pos) {
+                    if shown[i] && arc.contains(pos) {
                         clicked_index = Some(i);
                         break;
                     }
@@ -321,75 +649,223 @@ impl PieChart {
             }
         }
 
-        memory.hovered_index = hovered_index;
+        let ring_segment_at = |pos: Pos2| {
+            rings.iter().enumerate().find_map(|(k, ring)| {
+                ring.iter()
+                    .position(|segment| segment.arc.end_angle > segment.arc.start_angle && segment.arc.contains(pos))
+                    .map(|j| (k + 1, j))
+            })
+        };
+        let hovered_segment = match hovered_index {
+            Some(i) => Some((0, i)),
+            None => response.hover_pos().and_then(ring_segment_at),
+        };
+        if let Some(i) = clicked_index.filter(|_| self.selectable) {
+            update_selection(&mut memory.selected, Some(i), &shown);
+            ui.ctx().request_repaint();
+        }
 
-        // Draw tooltip
-        if self.tooltip_enabled {
-            if let Some(idx) = memory.hovered_index {
-                if idx < self.data.len() {
-                    let arc = &arcs[idx];
-                    let total: f64 = self.data.iter().sum();
-                    let value = self.data[idx];
-                    let pct = if total > 0.0 { value / total * 100.0 } else { 0.0 };
-
-                    let content = TooltipContent {
-                        title: None,
-                        label: self
-                            .labels
-                            .get(idx)
-                            .cloned()
-                            .unwrap_or_else(|| format!("Segment {}", idx + 1)),
-                        value: format!("{} ({:.1}%)", format_value(value), pct),
-                        color: arc.fill_color,
-                    };
+        let clicked_segment = match clicked_index {
+            Some(i) => Some((0, i)),
+            None => response
+                .interact_pointer_pos()
+                .filter(|_| response.clicked())
+                .and_then(ring_segment_at),
+        };
 
-                    let tooltip_size = measure_tooltip_size(&painter, &content, &self.theme.tooltip);
-                    let anchor = arc.mid_point((inner_radius + outer_radius) / 2.0);
-                    let tooltip_pos = calculate_tooltip_position(anchor, tooltip_size, rect);
+        memory.hovered_index = hovered_index;
+        memory.hovered_segment = hovered_segment;
 
-                    draw_tooltip(&painter, &content, tooltip_pos, &self.theme.tooltip);
-                }
+        // Draw the donut center
+        if inner_radius > 0.0 {
+            let hovered = hovered_segment.and_then(|(ring, j)| match ring {
+                0 => Some(CenterSegment {
+                    ring,
+                    index: j,
+                    label: self.labels.get(j).cloned().unwrap_or_else(|| format!("Segment {}", j + 1)),
+                    value: self.value(j)?,
+                    fraction: if total > 0.0 { self.data.y(j) / total } else { 0.0 },
+                    color: arcs.get(j)?.fill_color,
+                }),
+                _ => rings.get(ring - 1)?.get(j).map(|segment| CenterSegment {
+                    ring,
+                    index: j,
+                    label: segment.label.clone(),
+                    value: segment.value,
+                    fraction: segment.fraction,
+                    color: segment.arc.fill_color,
+                }),
+            });
+            let context = CenterContext {
+                center,
+                radius: inner_radius,
+                total,
+                hovered,
+                text_color: self.theme.text_color,
+            };
+            self.center.draw(&painter, &context, format_value);
+        }
+
+        let plugin_ctx = PluginContext {
+            hovered: memory.hovered_segment,
+            ..plugin_ctx
+        };
+        for event in ChartEvent::from_response(&response) {
+            for plugin in &plugins {
+                plugin.on_event(&plugin_ctx, &event);
             }
         }
 
+        // Tooltip for the hovered segment
+        let ring_tooltip = memory
+            .hovered_segment
+            .filter(|&(ring, _)| ring > 0 && self.tooltip_enabled)
+            .and_then(|(ring, j)| {
+                let segment = rings.get(ring - 1)?.get(j)?;
+                Some(ChartTooltip {
+                    content: TooltipContent {
+                        title: segment.title.clone(),
+                        label: segment.label.clone(),
+                        value: format!("{} ({:.1}%)", format_value(segment.value), segment.fraction * 100.0),
+                        color: segment.arc.fill_color,
+                    },
+                    anchor: segment.arc.mid_point((segment.arc.inner_radius + segment.arc.outer_radius) / 2.0),
+                })
+            });
+        let tooltip = memory.hovered_index.filter(|_| self.tooltip_enabled).and_then(|idx| {
+            let (arc, value) = (arcs.get(idx)?, self.value(idx)?);
+            let pct = if total > 0.0 { value / total * 100.0 } else { 0.0 };
+            Some(ChartTooltip {
+                content: TooltipContent {
+                    title: self.label.clone().filter(|_| !self.datasets.is_empty()),
+                    label: self
+                        .labels
+                        .get(idx)
+                        .cloned()
+                        .unwrap_or_else(|| format!("Segment {}", idx + 1)),
+                    value: format!("{} ({:.1}%)", format_value(value), pct),
+                    color: arc.fill_color,
+                },
+                anchor: arc.mid_point((main_inner + main_outer) / 2.0),
+            })
+        });
+        let tooltip = tooltip.or(ring_tooltip);
+
+        let plugin_ctx = PluginContext {
+            tooltip: tooltip.as_ref(),
+            ..plugin_ctx
+        };
+        for plugin in &plugins {
+            plugin.after_draw(&plugin_ctx);
+        }
+        TooltipPlugin.after_draw(&plugin_ctx);
+
         // Store memory
         ui.ctx().data_mut(|d| d.insert_temp(id, memory.clone()));
+        animation.store(ui.ctx(), id);
 
         PieChartResponse {
             response,
             hovered: memory.hovered_index,
             clicked: clicked_index,
+            hovered_segment,
+            clicked_segment,
+            selected: memory.selected.iter().copied().collect(),
+        }
+    }
+
+    /// Text of the label outside segment `index`, if labels or percentages are shown
+    fn outer_label_text(&self, index: usize, total: f64) -> Option<String> {
+        let mut text = String::new();
+        if self.show_labels {
+            if let Some(label) = self.labels.get(index) {
+                text.push_str(label);
+            }
+        }
+        if self.show_percentages && total > 0.0 {
+            let pct = self.value(index).unwrap_or(0.0) / total * 100.0;
+            if !text.is_empty() {
+                text.push_str(": ");
+            }
+            text.push_str(&format!("{:.0}%", pct));
+        }
+        (!text.is_empty()).then_some(text)
+    }
+
+    /// Fixed distance segment `index` is pulled out
+    fn segment_offset(&self, index: usize) -> f32 {
+        self.offset + self.offsets.get(index).copied().unwrap_or(0.0)
+    }
+
+    /// Value of the main ring's segment `index`
+    fn value(&self, index: usize) -> Option<f64> {
+        (index < self.data.len()).then(|| self.data.y(index))
+    }
+
+    /// Colors of the main ring's segments
+    fn palette(&self) -> Vec<Color32> {
+        if self.colors.is_empty() {
+            self.pie_style.colors.clone()
+        } else {
+            self.colors.iter().map(|c| c.to_color32()).collect()
+        }
+    }
+
+    /// Draw one of the extra rings, highlighting its hovered segment
+    fn draw_ring(
+        &self,
+        painter: &egui::Painter,
+        ring: &[RingSegment],
+        ring_index: usize,
+        hovered: Option<(usize, usize)>,
+        progress: f32,
+    ) {
+        for (j, segment) in ring.iter().enumerate() {
+            let mut arc = segment.arc.clone();
+            if hovered == Some((ring_index, j)) {
+                arc.fill_color = self.pie_style.hover.fill_color(arc.fill_color, 1.0);
+            }
+            arc.draw_animated(painter, progress);
         }
     }
 
-    /// Build arc elements from data
-    fn build_arc_elements(&self, center: Pos2, inner_radius: f32, outer_radius: f32) -> Vec<ArcElement> {
+    /// Build arc elements from data, scaling each value by its visibility weight
+    fn build_arc_elements(
+        &self,
+        center: Pos2,
+        inner_radius: f32,
+        outer_radius: f32,
+        weights: &[f32],
+    ) -> Vec<ArcElement> {
         if self.data.is_empty() {
             return vec![];
         }
 
-        let total: f64 = self.data.iter().sum();
+        let weighted: Vec<f64> = values(&self.data)
+            .enumerate()
+            .map(|(i, v)| segment_value(v, weights.get(i).copied().unwrap_or(1.0)))
+            .collect();
+        let total: f64 = weighted.iter().sum();
         if total <= 0.0 {
             return vec![];
         }
 
-        let colors: Vec<Color32> = if self.colors.is_empty() {
-            self.pie_style.colors.clone()
-        } else {
-            self.colors.iter().map(|c| c.to_color32()).collect()
-        };
+        let colors = self.palette();
 
         let mut start_angle = self.pie_style.start_angle;
         let mut arcs = Vec::with_capacity(self.data.len());
 
-        for (i, &value) in self.data.iter().enumerate() {
-            let sweep = (value / total) as f32 * 2.0 * PI;
+        for (i, &value) in weighted.iter().enumerate() {
+            let sweep = (value / total) as f32 * self.pie_style.circumference;
             let end_angle = start_angle + sweep;
 
             let mut arc = ArcElement::new(center, inner_radius, outer_radius, start_angle, end_angle);
             arc.fill_color = colors.get(i % colors.len()).cloned().unwrap_or(Color32::GRAY);
             arc.border_color = self.pie_style.border_color;
             arc.border_width = self.pie_style.border_width;
+            arc.spacing = self.pie_style.spacing;
+            arc.border_radius = self.pie_style.border_radius;
+            arc.outline = self.pie_style.outline;
 
             arcs.push(arc);
             start_angle = end_angle;
@@ -399,7 +875,18 @@ impl PieChart {
     }
 }
 
-impl Widget for PieChart {
+/// Toggle the `clicked` segment in the selection, and drop the segments that
+/// aren't `shown`: hidden, missing or past the end of the data
+fn update_selection(selected: &mut BTreeSet<usize>, clicked: Option<usize>, shown: &[bool]) {
+    if let Some(i) = clicked {
+        if !selected.remove(&i) {
+            selected.insert(i);
+        }
+    }
+    selected.retain(|&i| shown.get(i).copied().unwrap_or(false));
+}
+
+impl Widget for PieChart<'_> {
     fn ui(self, ui: &mut Ui) -> Response {
         self.show(ui).response
     }
@@ -416,3 +903,40 @@ fn format_value(value: f64) -> String {
         format!("{:.1}", value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selection_toggles_and_follows_data() {
+        let mut selected = BTreeSet::new();
+        let shown = [true; 4];
+        update_selection(&mut selected, Some(2), &shown);
+        update_selection(&mut selected, Some(0), &shown);
+        assert_eq!(selected.iter().copied().collect::<Vec<_>>(), vec![0, 2]);
+
+        // Clicking again deselects
+        update_selection(&mut selected, Some(2), &shown);
+        assert_eq!(selected.iter().copied().collect::<Vec<_>>(), vec![0]);
+
+        // Hidden or missing segments, and those past the end, drop out
+        update_selection(&mut selected, Some(3), &shown);
+        update_selection(&mut selected, None, &[false, true]);
+        assert!(selected.is_empty());
+    }
+
+    #[test]
+    fn test_data_source_matches_copied_data() {
+        let values = vec![30.0, f64::NAN, 50.0, 20.0];
+        let copied = PieChart::new().data(values.clone());
+        let borrowed = PieChart::new().data_source(&values[..]);
+        let arcs = |chart: &PieChart| {
+            let arcs = chart.build_arc_elements(Pos2::ZERO, 0.0, 100.0, &[1.0; 4]);
+            arcs.iter().map(|arc| (arc.start_angle, arc.end_angle)).collect::<Vec<_>>()
+        };
+        assert_eq!(arcs(&borrowed), arcs(&copied));
+        assert_eq!(borrowed.value(2), Some(50.0));
+        assert_eq!(borrowed.value(4), None);
+    }
+}
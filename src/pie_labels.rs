@@ -0,0 +1,161 @@
+use egui::{Align2, Pos2, Rect, Vec2};
+
+use crate::elements::ArcElement;
+use crate::plugin::ChartLayout;
+
+/// Font size of labels outside the pie
+pub(crate) const OUTER_LABEL_FONT_SIZE: f32 = 11.0;
+
+/// Length of the leader line leaving the slice
+const LEADER_RADIAL: f32 = 12.0;
+/// Length of the horizontal part of the leader line
+const LEADER_TAIL: f32 = 10.0;
+/// Space between the end of the leader line and the text
+const TEXT_GAP: f32 = 4.0;
+
+/// A label placed outside the pie
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct OuterLabel {
+    /// Segment index
+    pub index: usize,
+    /// Elbow leader line: slice edge, bend, end beside the text
+    pub leader: [Pos2; 3],
+    pub text_pos: Pos2,
+    pub align: Align2,
+}
+
+/// Room needed around the pie for labels of the measured `sizes`
+pub(crate) fn outer_label_padding(sizes: impl IntoIterator<Item = Vec2>) -> ChartLayout {
+    let max = sizes.into_iter().fold(Vec2::ZERO, |max, size| max.max(size));
+    if max == Vec2::ZERO {
+        return ChartLayout::uniform(0.0);
+    }
+    let horizontal = LEADER_RADIAL + LEADER_TAIL + TEXT_GAP + max.x;
+    let vertical = LEADER_RADIAL + max.y;
+    ChartLayout {
+        left: horizontal,
+        right: horizontal,
+        top: vertical,
+        bottom: vertical,
+    }
+}
+
+/// Place labels beside their slices, spreading each side of the pie vertically
+/// so labels don't overlap. Slices narrower than `min_angle` get no label.
+pub(crate) fn layout_outer_labels(
+    arcs: &[ArcElement],
+    labels: &[(usize, Vec2)],
+    bounds: Rect,
+    min_angle: f32,
+) -> Vec<OuterLabel> {
+    let mut sides: [Vec<(usize, f32, Vec2)>; 2] = [Vec::new(), Vec::new()];
+    for &(index, size) in labels {
+        let Some(arc) = arcs.get(index) else { continue };
+        if (arc.end_angle - arc.start_angle).abs() < min_angle {
+            continue;
+        }
+        let right = arc.mid_angle().cos() >= 0.0;
+        let bend = arc.mid_point(arc.outer_radius + LEADER_RADIAL);
+        sides[right as usize].push((index, bend.y, size));
+    }
+
+    let mut placed = Vec::new();
+    for (right, side) in sides.iter_mut().enumerate() {
+        side.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let spans: Vec<(f32, f32)> = side.iter().map(|&(_, y, size)| (y, size.y)).collect();
+        let ys = spread_vertically(&spans, bounds.min.y, bounds.max.y);
+
+        let dir = if right == 1 { 1.0 } else { -1.0 };
+        for (&(index, _, _), y) in side.iter().zip(ys) {
+            let arc = &arcs[index];
+            let edge = arc.mid_point(arc.outer_radius);
+            let bend = arc.mid_point(arc.outer_radius + LEADER_RADIAL);
+            let x = arc.center.x + dir * (arc.outer_radius + LEADER_RADIAL + LEADER_TAIL);
+            let end = Pos2::new(x, y);
+            placed.push(OuterLabel {
+                index,
+                leader: [edge, bend, end],
+                text_pos: Pos2::new(x + dir * TEXT_GAP, y),
+                align: if right == 1 { Align2::LEFT_CENTER } else { Align2::RIGHT_CENTER },
+            });
+        }
+    }
+    placed.sort_by_key(|label| label.index);
+    placed
+}
+
+/// Move labels (desired center y, height), sorted by y, apart so they don't
+/// overlap, staying within `min..max` where there is room
+fn spread_vertically(spans: &[(f32, f32)], min: f32, max: f32) -> Vec<f32> {
+    let mut ys: Vec<f32> = spans.iter().map(|&(y, _)| y).collect();
+
+    // Push down from the top...
+    for i in 0..ys.len() {
+        let lowest = match i {
+            0 => min + spans[i].1 / 2.0,
+            _ => ys[i - 1] + (spans[i - 1].1 + spans[i].1) / 2.0,
+        };
+        ys[i] = ys[i].max(lowest);
+    }
+    // ...then back up from the bottom if that ran past it
+    for i in (0..ys.len()).rev() {
+        let highest = match ys.get(i + 1) {
+            None => max - spans[i].1 / 2.0,
+            Some(next) => next - (spans[i + 1].1 + spans[i].1) / 2.0,
+        };
+        ys[i] = ys[i].min(highest);
+    }
+    ys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_spread_vertically() {
+        // Separated labels stay put
+        assert_eq!(spread_vertically(&[(10.0, 10.0), (50.0, 10.0)], 0.0, 100.0), vec![10.0, 50.0]);
+
+        // Overlapping labels are pushed apart
+        assert_eq!(spread_vertically(&[(50.0, 10.0), (52.0, 10.0), (53.0, 10.0)], 0.0, 100.0), vec![50.0, 60.0, 70.0]);
+
+        // ...and back up when they would run past the bottom
+        assert_eq!(spread_vertically(&[(90.0, 10.0), (95.0, 10.0)], 0.0, 100.0), vec![85.0, 95.0]);
+    }
+
+    #[test]
+    fn test_layout_outer_labels() {
+        let center = Pos2::new(100.0, 100.0);
+        let arcs = [
+            ArcElement::new(center, 0.0, 50.0, -0.1, 0.1),
+            ArcElement::new(center, 0.0, 50.0, 0.1, 0.12),
+            ArcElement::new(center, 0.0, 50.0, PI - 0.1, PI + 0.1),
+        ];
+        let size = Vec2::new(30.0, 12.0);
+        let labels = [(0, size), (1, size), (2, size)];
+        let bounds = Rect::from_min_size(Pos2::ZERO, Vec2::splat(200.0));
+
+        let placed = layout_outer_labels(&arcs, &labels, bounds, 0.05);
+        // The sliver gets no label
+        assert_eq!(placed.iter().map(|l| l.index).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(placed[0].align, Align2::LEFT_CENTER);
+        assert!(placed[0].text_pos.x > 150.0);
+        assert_eq!(placed[1].align, Align2::RIGHT_CENTER);
+        assert!(placed[1].text_pos.x < 50.0);
+
+        // Without the minimum angle, neighbours on the same side are spread apart
+        let placed = layout_outer_labels(&arcs, &labels, bounds, 0.0);
+        let (a, b) = (placed[0].text_pos.y, placed[1].text_pos.y);
+        assert!((a - b).abs() >= size.y);
+    }
+
+    #[test]
+    fn test_padding_from_label_sizes() {
+        let layout = outer_label_padding([Vec2::new(40.0, 12.0), Vec2::new(60.0, 10.0)]);
+        assert_eq!(layout.left, LEADER_RADIAL + LEADER_TAIL + TEXT_GAP + 60.0);
+        assert_eq!(layout.top, LEADER_RADIAL + 12.0);
+        assert_eq!(outer_label_padding([]), ChartLayout::uniform(0.0));
+    }
+}
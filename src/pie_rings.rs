@@ -0,0 +1,354 @@
+use egui::{Color32, Pos2, Stroke};
+
+use crate::data::is_missing;
+use crate::elements::ArcElement;
+use crate::helpers::color::{lighten, ChartColor};
+
+/// An additional ring drawn by a [`PieChart`](crate::PieChart), inside the previous ones
+#[derive(Clone, Debug)]
+pub struct PieDataset {
+    pub label: String,
+    pub data: Vec<f64>,
+    /// Segment colors, defaulting to the chart's colors
+    pub colors: Vec<ChartColor>,
+    /// Ring thickness relative to the other rings
+    pub weight: f32,
+}
+
+impl PieDataset {
+    pub fn new(label: impl Into<String>, data: impl IntoIterator<Item = impl Into<f64>>) -> Self {
+        Self {
+            label: label.into(),
+            data: data.into_iter().map(|v| v.into()).collect(),
+            colors: Vec::new(),
+            weight: 1.0,
+        }
+    }
+
+    /// Set segment colors
+    pub fn colors(mut self, colors: impl IntoIterator<Item = impl Into<ChartColor>>) -> Self {
+        self.colors = colors.into_iter().map(|c| c.into()).collect();
+        self
+    }
+
+    /// Set ring thickness relative to the other rings (default: 1)
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.weight = weight.max(0.0);
+        self
+    }
+}
+
+/// A slice of a sunburst chart; its children split its angle in the next ring out
+///
+/// ```rust,ignore
+/// PieChart::new()
+///     .sunburst([
+///         SunburstNode::new("Europe", 0.0)
+///             .child(SunburstNode::new("France", 68.0))
+///             .child(SunburstNode::new("Spain", 48.0)),
+///         SunburstNode::new("Asia", 0.0).child(SunburstNode::new("Japan", 125.0)),
+///     ])
+///     .show(ui);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SunburstNode {
+    pub label: String,
+    /// Own value; the children's sum is used when it is larger
+    pub value: f64,
+    /// Color, defaulting to a lighter shade of the parent's
+    pub color: Option<ChartColor>,
+    pub children: Vec<SunburstNode>,
+}
+
+impl SunburstNode {
+    pub fn new(label: impl Into<String>, value: impl Into<f64>) -> Self {
+        Self {
+            label: label.into(),
+            value: value.into(),
+            color: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Set slice color
+    pub fn color(mut self, color: impl Into<ChartColor>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Add a child slice
+    pub fn child(mut self, child: SunburstNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Add child slices
+    pub fn children(mut self, children: impl IntoIterator<Item = SunburstNode>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    /// Value that sizes this slice: its own value or its children's sum, whichever is larger
+    pub fn total(&self) -> f64 {
+        let children: f64 = self.children.iter().map(SunburstNode::total).sum();
+        self.value.max(children)
+    }
+
+    /// Number of rings this node and its descendants take up
+    pub(crate) fn depth(&self) -> usize {
+        1 + self.children.iter().map(SunburstNode::depth).max().unwrap_or(0)
+    }
+
+    /// Totals of this node and its descendants, depth first
+    pub(crate) fn collect_totals(&self, totals: &mut Vec<f64>) {
+        totals.push(self.total());
+        for child in &self.children {
+            child.collect_totals(totals);
+        }
+    }
+}
+
+/// Share of a ring a value takes at visibility `weight`; missing values take none
+pub(crate) fn segment_value(value: f64, weight: f32) -> f64 {
+    if is_missing(value) {
+        0.0
+    } else {
+        value * weight as f64
+    }
+}
+
+/// A segment of a ring other than the chart's main one
+#[derive(Clone, Debug)]
+pub(crate) struct RingSegment {
+    pub arc: ArcElement,
+    pub value: f64,
+    /// Segment label; the path from the root for sunburst slices
+    pub label: String,
+    /// Dataset label
+    pub title: Option<String>,
+    /// Share of the ring (datasets) or of the parent slice (sunburst)
+    pub fraction: f64,
+}
+
+/// Shape shared by all rings
+pub(crate) struct RingGeometry<'a> {
+    pub center: Pos2,
+    pub start_angle: f32,
+    pub circumference: f32,
+    /// Visibility weight of each data index
+    pub weights: &'a [f32],
+    /// Colors of the main ring
+    pub colors: &'a [Color32],
+    pub border_color: Color32,
+    pub border_width: f32,
+    pub spacing: f32,
+    pub border_radius: f32,
+    pub outline: Option<Stroke>,
+}
+
+/// Radii of rings filling `inner..outer` in proportion to `weights`, first ring outermost
+pub(crate) fn ring_radii(inner: f32, outer: f32, weights: &[f32]) -> Vec<(f32, f32)> {
+    let total: f32 = weights.iter().sum();
+    let mut ring_outer = outer;
+    weights
+        .iter()
+        .map(|weight| {
+            let thickness = if total > 0.0 { (outer - inner) * weight / total } else { 0.0 };
+            let ring = (ring_outer - thickness, ring_outer);
+            ring_outer -= thickness;
+            ring
+        })
+        .collect()
+}
+
+/// Segments of each extra dataset ring, hiding the same data indices as the main ring
+pub(crate) fn dataset_rings(
+    datasets: &[PieDataset],
+    radii: &[(f32, f32)],
+    labels: &[String],
+    geometry: &RingGeometry,
+) -> Vec<Vec<RingSegment>> {
+    datasets
+        .iter()
+        .zip(radii)
+        .map(|(dataset, &(inner, outer))| {
+            let weighted: Vec<f64> = dataset
+                .data
+                .iter()
+                .enumerate()
+                .map(|(i, v)| segment_value(*v, geometry.weights.get(i).copied().unwrap_or(1.0)))
+                .collect();
+            let total: f64 = weighted.iter().sum();
+            if total <= 0.0 {
+                return Vec::new();
+            }
+
+            let mut start = geometry.start_angle;
+            weighted
+                .iter()
+                .enumerate()
+                .map(|(i, &value)| {
+                    let sweep = (value / total) as f32 * geometry.circumference;
+                    let mut arc = ArcElement::new(geometry.center, inner, outer, start, start + sweep);
+                    arc.fill_color = match dataset.colors.get(i) {
+                        Some(color) => color.to_color32(),
+                        None => geometry.colors.get(i % geometry.colors.len().max(1)).copied().unwrap_or(Color32::GRAY),
+                    };
+                    arc.border_color = geometry.border_color;
+                    arc.border_width = geometry.border_width;
+                    arc.spacing = geometry.spacing;
+                    arc.border_radius = geometry.border_radius;
+                    arc.outline = geometry.outline;
+                    start += sweep;
+                    RingSegment {
+                        arc,
+                        value: dataset.data[i],
+                        label: labels.get(i).cloned().unwrap_or_else(|| format!("Segment {}", i + 1)),
+                        title: Some(dataset.label.clone()),
+                        fraction: value / total,
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Segments of each sunburst ring below the roots, whose arcs are `roots_arcs`;
+/// children share their root's center, so they move with an offset root
+pub(crate) fn sunburst_rings(
+    roots: &[SunburstNode],
+    root_arcs: &[ArcElement],
+    radii: &[(f32, f32)],
+) -> Vec<Vec<RingSegment>> {
+    // Parents of the next ring: node, angular range, path and color
+    let mut parents: Vec<(&SunburstNode, f32, f32, String, Color32, Pos2)> = roots
+        .iter()
+        .zip(root_arcs)
+        .map(|(node, arc)| (node, arc.start_angle, arc.end_angle, node.label.clone(), arc.fill_color, arc.center))
+        .collect();
+    let Some(template) = root_arcs.first() else { return Vec::new() };
+
+    let mut rings = Vec::new();
+    for &(inner, outer) in radii {
+        let mut ring = Vec::new();
+        let mut next = Vec::new();
+        for (node, start, end, path, color, center) in &parents {
+            let total = node.total();
+            if total <= 0.0 {
+                continue;
+            }
+            let scale = (end - start) / total as f32;
+            let mut angle = *start;
+            let count = node.children.len();
+            for (k, child) in node.children.iter().enumerate() {
+                let sweep = child.total() as f32 * scale;
+                let fill = match &child.color {
+                    Some(color) => color.to_color32(),
+                    None => lighten(*color, 0.25 + 0.35 * k as f32 / count as f32),
+                };
+                let mut arc = ArcElement::new(*center, inner, outer, angle, angle + sweep);
+                arc.fill_color = fill;
+                arc.border_color = template.border_color;
+                arc.border_width = template.border_width;
+                arc.spacing = template.spacing;
+                arc.border_radius = template.border_radius;
+                arc.outline = template.outline;
+
+                let child_path = format!("{} › {}", path, child.label);
+                ring.push(RingSegment {
+                    arc,
+                    value: child.total(),
+                    label: child_path.clone(),
+                    title: None,
+                    fraction: child.total() / total,
+                });
+                next.push((child, angle, angle + sweep, child_path, fill, *center));
+                angle += sweep;
+            }
+        }
+        rings.push(ring);
+        parents = next;
+    }
+    rings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_ring_radii() {
+        assert_eq!(ring_radii(0.0, 100.0, &[1.0]), vec![(0.0, 100.0)]);
+        assert_eq!(ring_radii(40.0, 100.0, &[2.0, 1.0]), vec![(60.0, 100.0), (40.0, 60.0)]);
+    }
+
+    #[test]
+    fn test_sunburst_children_share_parent_angle() {
+        let roots = [
+            SunburstNode::new("A", 0.0)
+                .child(SunburstNode::new("A1", 1.0))
+                .child(SunburstNode::new("A2", 3.0)),
+            SunburstNode::new("B", 4.0),
+        ];
+        assert_eq!(roots[0].total(), 4.0);
+        assert_eq!(roots[0].depth(), 2);
+
+        let root_arcs = [
+            ArcElement::new(Pos2::ZERO, 0.0, 50.0, 0.0, PI),
+            ArcElement::new(Pos2::ZERO, 0.0, 50.0, PI, 2.0 * PI),
+        ];
+        let rings = sunburst_rings(&roots, &root_arcs, &[(50.0, 80.0)]);
+        assert_eq!(rings.len(), 1);
+
+        let ring = &rings[0];
+        assert_eq!(ring.len(), 2);
+        assert_eq!(ring[0].arc.start_angle, 0.0);
+        assert!((ring[0].arc.end_angle - PI / 4.0).abs() < 1e-5);
+        assert!((ring[1].arc.end_angle - PI).abs() < 1e-5);
+        assert_eq!(ring[1].label, "A › A2");
+        assert_eq!(ring[1].fraction, 0.75);
+
+        // Hit testing a child slice
+        let pos = Pos2::new(0.0, 60.0); // straight down, PI/2
+        assert_eq!(ring.iter().position(|s| s.arc.contains(pos)), Some(1));
+    }
+
+    #[test]
+    fn test_dataset_rings_follow_visibility() {
+        let datasets = [PieDataset::new("2023", [1.0, 1.0, 2.0])];
+        let colors = [Color32::RED];
+        let weights = [1.0, 0.0, 1.0];
+        let geometry = RingGeometry {
+            center: Pos2::ZERO,
+            start_angle: 0.0,
+            circumference: 2.0 * PI,
+            weights: &weights,
+            colors: &colors,
+            border_color: Color32::WHITE,
+            border_width: 0.0,
+            spacing: 0.0,
+            border_radius: 0.0,
+            outline: None,
+        };
+        let rings = dataset_rings(&datasets, &[(20.0, 40.0)], &["a".into(), "b".into(), "c".into()], &geometry);
+        let ring = &rings[0];
+        assert_eq!(ring.len(), 3);
+        // The hidden index takes no angle
+        assert_eq!(ring[1].arc.start_angle, ring[1].arc.end_angle);
+        assert!((ring[2].arc.end_angle - ring[2].arc.start_angle - 4.0 * PI / 3.0).abs() < 1e-5);
+        assert_eq!(ring[2].title.as_deref(), Some("2023"));
+        assert_eq!(ring[0].arc.fill_color, Color32::RED);
+
+        // A missing value takes no angle either
+        let datasets = [PieDataset::new("2024", [1.0, 1.0, f64::NAN])];
+        let weights = [1.0; 3];
+        let geometry = RingGeometry {
+            weights: &weights,
+            ..geometry
+        };
+        let ring = &dataset_rings(&datasets, &[(20.0, 40.0)], &[], &geometry)[0];
+        assert_eq!(ring[2].arc.start_angle, ring[2].arc.end_angle);
+        assert!((ring[1].arc.end_angle - 2.0 * PI).abs() < 1e-5);
+    }
+}
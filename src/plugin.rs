@@ -0,0 +1,212 @@
+use std::sync::Arc;
+
+use egui::{Context, Id, Painter, Pos2, Rect, Response};
+
+use crate::elements::{ArcElement, BarElement, PointElement};
+use crate::scale::LinearScale;
+use crate::theme::ChartTheme;
+use crate::tooltip::TooltipContent;
+
+/// Space between the widget edge and the chart area, adjustable in
+/// [`ChartPlugin::before_layout`] (e.g. to make room for a title)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChartLayout {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl ChartLayout {
+    /// Same padding on every side
+    pub fn uniform(padding: f32) -> Self {
+        Self {
+            left: padding,
+            right: padding,
+            top: padding,
+            bottom: padding,
+        }
+    }
+
+    /// Chart area inside the widget rect
+    pub fn chart_area(&self, rect: Rect) -> Rect {
+        Rect::from_min_max(
+            Pos2::new(rect.min.x + self.left, rect.min.y + self.top),
+            Pos2::new(rect.max.x - self.right, rect.max.y - self.bottom),
+        )
+    }
+}
+
+/// Geometry of the drawn data elements
+#[derive(Clone, Copy, Debug, Default)]
+pub enum ChartElements<'a> {
+    /// Not built yet (`before_draw`)
+    #[default]
+    None,
+    Bars(&'a [BarElement]),
+    /// Points drawn for each dataset of a line chart; only those on screen,
+    /// and fewer still when decimated
+    Points(&'a [Vec<PointElement>]),
+    Arcs(&'a [ArcElement]),
+}
+
+/// A tooltip about to be drawn
+#[derive(Clone, Debug)]
+pub struct ChartTooltip {
+    pub content: TooltipContent,
+    /// Point the tooltip is positioned against
+    pub anchor: Pos2,
+}
+
+/// What a plugin sees at each hook
+#[derive(Clone, Copy)]
+pub struct PluginContext<'a> {
+    pub painter: &'a Painter,
+    pub chart_id: Id,
+    /// The whole widget
+    pub rect: Rect,
+    /// Area the data is drawn in (inside axes and layout padding)
+    pub chart_area: Rect,
+    /// Scales of cartesian charts
+    pub x_scale: Option<&'a LinearScale>,
+    pub y_scale: Option<&'a LinearScale>,
+    pub elements: ChartElements<'a>,
+    pub theme: &'a ChartTheme,
+    pub response: &'a Response,
+    /// Hovered (dataset, index)
+    pub hovered: Option<(usize, usize)>,
+    /// Tooltip for the hovered element (in `after_draw`)
+    pub tooltip: Option<&'a ChartTooltip>,
+}
+
+/// Pointer events delivered to [`ChartPlugin::on_event`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChartEvent {
+    Hover { pos: Pos2 },
+    Click { pos: Pos2 },
+    DoubleClick { pos: Pos2 },
+}
+
+impl ChartEvent {
+    /// Events the response reports this frame
+    pub(crate) fn from_response(response: &Response) -> Vec<ChartEvent> {
+        let mut events = Vec::new();
+        if let Some(pos) = response.hover_pos() {
+            events.push(ChartEvent::Hover { pos });
+        }
+        if let Some(pos) = response.interact_pointer_pos() {
+            if response.clicked() {
+                events.push(ChartEvent::Click { pos });
+            }
+            if response.double_clicked() {
+                events.push(ChartEvent::DoubleClick { pos });
+            }
+        }
+        events
+    }
+}
+
+/// Hooks into a chart's drawing, mirroring Chart.js plugins
+///
+/// All hooks default to doing nothing. Plugins are shared between frames and
+/// charts, so keep state behind interior mutability or in egui memory.
+///
+/// ```rust,ignore
+/// struct Watermark;
+///
+/// impl ChartPlugin for Watermark {
+///     fn before_draw(&self, ctx: &PluginContext) {
+///         ctx.painter.text(ctx.chart_area.center(), Align2::CENTER_CENTER, "DRAFT",
+///             FontId::proportional(40.0), Color32::from_gray(235));
+///     }
+/// }
+///
+/// BarChart::new().data(data).plugin(Watermark).show(ui);
+/// ```
+pub trait ChartPlugin: Send + Sync {
+    /// Adjust the padding around the chart area
+    fn before_layout(&self, _layout: &mut ChartLayout) {}
+
+    /// After the background, before grid and data
+    fn before_draw(&self, _ctx: &PluginContext<'_>) {}
+
+    /// After the datasets, before axes and labels
+    fn after_datasets_draw(&self, _ctx: &PluginContext<'_>) {}
+
+    /// After everything; only the built-in tooltip is drawn later
+    fn after_draw(&self, _ctx: &PluginContext<'_>) {}
+
+    /// Pointer input over the chart
+    fn on_event(&self, _ctx: &PluginContext<'_>, _event: &ChartEvent) {}
+}
+
+/// Plugins registered for every chart
+#[derive(Clone, Default)]
+struct GlobalPlugins(Vec<Arc<dyn ChartPlugin>>);
+
+fn global_plugins_id() -> Id {
+    Id::new("egui_charts::global_plugins")
+}
+
+/// Register a plugin for every chart drawn with this context (Chart.js `Chart.register`)
+pub fn register_global_plugin(ctx: &Context, plugin: impl ChartPlugin + 'static) {
+    ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<GlobalPlugins>(global_plugins_id())
+            .0
+            .push(Arc::new(plugin));
+    });
+}
+
+/// Remove all globally registered plugins
+pub fn clear_global_plugins(ctx: &Context) {
+    ctx.data_mut(|d| d.remove::<GlobalPlugins>(global_plugins_id()));
+}
+
+/// Plugins to run for one chart: global ones first, then the chart's own
+pub(crate) fn chart_plugins(ctx: &Context, local: &[Arc<dyn ChartPlugin>]) -> Vec<Arc<dyn ChartPlugin>> {
+    let global = ctx.data(|d| d.get_temp::<GlobalPlugins>(global_plugins_id()).unwrap_or_default());
+    global.0.into_iter().chain(local.iter().cloned()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Padding(f32);
+
+    impl ChartPlugin for Padding {
+        fn before_layout(&self, layout: &mut ChartLayout) {
+            layout.top += self.0;
+        }
+    }
+
+    #[test]
+    fn test_global_then_local_order() {
+        let ctx = Context::default();
+        register_global_plugin(&ctx, Padding(1.0));
+        let plugins = chart_plugins(&ctx, &[Arc::new(Padding(10.0))]);
+        assert_eq!(plugins.len(), 2);
+
+        // Each plugin sees the layout left by the previous one
+        let mut layout = ChartLayout::uniform(0.0);
+        for plugin in &plugins {
+            plugin.before_layout(&mut layout);
+        }
+        assert_eq!(layout.top, 11.0);
+
+        clear_global_plugins(&ctx);
+        assert_eq!(chart_plugins(&ctx, &[]).len(), 0);
+    }
+
+    #[test]
+    fn test_chart_area() {
+        let layout = ChartLayout {
+            left: 45.0,
+            right: 15.0,
+            top: 15.0,
+            bottom: 30.0,
+        };
+        let area = layout.chart_area(Rect::from_min_max(Pos2::ZERO, Pos2::new(200.0, 100.0)));
+        assert_eq!(area, Rect::from_min_max(Pos2::new(45.0, 15.0), Pos2::new(185.0, 70.0)));
+    }
+}
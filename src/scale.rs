@@ -0,0 +1,105 @@
+use crate::helpers::math::nice_ticks;
+
+/// Linear mapping between data values and screen pixels along one axis
+///
+/// Mirrors Chart.js `LinearScale`: `min` maps to `pixel_min` and `max` to
+/// `pixel_max`. For a vertical axis `pixel_min` is the bottom of the chart
+/// area, so larger values go up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinearScale {
+    pub min: f64,
+    pub max: f64,
+    /// Pixel position of `min`
+    pub pixel_min: f32,
+    /// Pixel position of `max`
+    pub pixel_max: f32,
+}
+
+impl LinearScale {
+    pub fn new(min: f64, max: f64, pixel_min: f32, pixel_max: f32) -> Self {
+        Self {
+            min,
+            max,
+            pixel_min,
+            pixel_max,
+        }
+    }
+
+    /// Data range covered by the scale
+    pub fn range(&self) -> f64 {
+        self.max - self.min
+    }
+
+    /// Convert a data value to a pixel position
+    pub fn value_to_pixel(&self, value: f64) -> f32 {
+        let range = self.range();
+        if range.abs() < f64::EPSILON {
+            return self.pixel_min;
+        }
+        let t = (value - self.min) / range;
+        self.pixel_min + (self.pixel_max - self.pixel_min) * t as f32
+    }
+
+    /// Convert a pixel position back to a data value
+    pub fn pixel_to_value(&self, pixel: f32) -> f64 {
+        let span = self.pixel_max - self.pixel_min;
+        if span.abs() < f32::EPSILON {
+            return self.min;
+        }
+        let t = ((pixel - self.pixel_min) / span) as f64;
+        self.min + self.range() * t
+    }
+
+    /// Nice tick values inside the scale range
+    pub fn ticks(&self, max_ticks: usize) -> Vec<f64> {
+        let epsilon = self.range().abs() * 1e-9;
+        nice_ticks(self.min, self.max, max_ticks)
+            .into_iter()
+            .filter(|t| *t >= self.min - epsilon && *t <= self.max + epsilon)
+            .collect()
+    }
+}
+
+/// Value range for a vertical value axis the way bar and line charts lay it out:
+/// always includes zero and leaves 10% headroom above positive data
+pub(crate) fn value_axis_range(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (min, max) = values
+        .filter(|v| v.is_finite())
+        .fold((0.0_f64, 0.0_f64), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    let padded_max = if max > 0.0 { max * 1.1 } else { max };
+    (min, padded_max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_pixel_roundtrip() {
+        // Vertical axis: 0 at the bottom (300px), 100 at the top (100px)
+        let scale = LinearScale::new(0.0, 100.0, 300.0, 100.0);
+
+        assert!((scale.value_to_pixel(0.0) - 300.0).abs() < 0.001);
+        assert!((scale.value_to_pixel(100.0) - 100.0).abs() < 0.001);
+        assert!((scale.value_to_pixel(25.0) - 250.0).abs() < 0.001);
+        assert!((scale.pixel_to_value(250.0) - 25.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_ticks_inside_range() {
+        let scale = LinearScale::new(-20.0, 110.0, 300.0, 0.0);
+        let ticks = scale.ticks(5);
+        assert!(!ticks.is_empty());
+        assert!(ticks.iter().all(|t| *t >= -20.0 && *t <= 110.0));
+    }
+
+    #[test]
+    fn test_value_axis_range() {
+        let (min, max) = value_axis_range([10.0, 50.0].into_iter());
+        assert_eq!(min, 0.0);
+        assert!((max - 55.0).abs() < 1e-9);
+        assert_eq!(value_axis_range([-10.0, -5.0].into_iter()), (-10.0, 0.0));
+        // Non-finite values are ignored
+        assert_eq!(value_axis_range([f64::NAN, 10.0].into_iter()).0, 0.0);
+    }
+}
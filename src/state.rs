@@ -0,0 +1,187 @@
+use std::collections::BTreeSet;
+
+use egui::{Context, Id};
+
+use crate::link::LinkGroup;
+use crate::zoom::VisibleRange;
+
+/// User-controllable chart state, kept in egui memory under the chart's id
+///
+/// Use the same id passed to the chart's `.id()` to drive it from your own UI,
+/// e.g. checkboxes in a side panel. Changes animate on the chart's next frame.
+///
+/// ```rust,ignore
+/// let id = egui::Id::new("cpu");
+/// let mut state = ChartState::load(ui.ctx(), id).unwrap_or_default();
+/// let mut visible = state.is_visible(1);
+/// if ui.checkbox(&mut visible, "System").changed() {
+///     state.set_visible(1, visible);
+///     state.store(ui.ctx(), id);
+/// }
+/// LineChart::new().id(id).data(user).dataset(system).show(ui);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChartState {
+    hidden_datasets: BTreeSet<usize>,
+    hidden_data: BTreeSet<usize>,
+    x_range: Option<(f64, f64)>,
+    y_range: Option<(f64, f64)>,
+}
+
+impl ChartState {
+    /// Load the state of the chart with the given id
+    pub fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.data(|d| d.get_temp(id))
+    }
+
+    /// Store the state for the chart with the given id
+    pub fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|d| d.insert_temp(id, self));
+    }
+
+    /// Whether a dataset is shown
+    pub fn is_visible(&self, dataset: usize) -> bool {
+        !self.hidden_datasets.contains(&dataset)
+    }
+
+    /// Show or hide a whole dataset (a line, or all bars of a bar chart)
+    pub fn set_visible(&mut self, dataset: usize, visible: bool) {
+        if visible {
+            self.hidden_datasets.remove(&dataset);
+        } else {
+            self.hidden_datasets.insert(dataset);
+        }
+    }
+
+    /// Flip the visibility of a dataset
+    pub fn toggle_visible(&mut self, dataset: usize) {
+        self.set_visible(dataset, !self.is_visible(dataset));
+    }
+
+    /// Whether the data element at `index` is shown (pie segments, single bars)
+    pub fn is_data_visible(&self, index: usize) -> bool {
+        !self.hidden_data.contains(&index)
+    }
+
+    /// Show or hide a single data element (Chart.js `toggleDataVisibility`)
+    pub fn set_data_visible(&mut self, index: usize, visible: bool) {
+        if visible {
+            self.hidden_data.remove(&index);
+        } else {
+            self.hidden_data.insert(index);
+        }
+    }
+
+    /// Flip the visibility of a single data element
+    pub fn toggle_data_visibility(&mut self, index: usize) {
+        self.set_data_visible(index, !self.is_data_visible(index));
+    }
+
+    /// Zoomed x range, `None` when showing all data
+    pub fn x_range(&self) -> Option<(f64, f64)> {
+        self.x_range
+    }
+
+    /// Zoomed y range, `None` when fitting the data
+    pub fn y_range(&self) -> Option<(f64, f64)> {
+        self.y_range
+    }
+
+    /// Zoom the x axis to a range, or `None` to show all data
+    pub fn set_x_range(&mut self, range: Option<(f64, f64)>) {
+        self.x_range = range;
+    }
+
+    /// Zoom the y axis to a range, or `None` to fit the data
+    pub fn set_y_range(&mut self, range: Option<(f64, f64)>) {
+        self.y_range = range;
+    }
+
+    /// Whether either axis is zoomed or panned
+    pub fn is_zoomed(&self) -> bool {
+        self.x_range.is_some() || self.y_range.is_some()
+    }
+
+    /// Return to the unzoomed view
+    pub fn reset_zoom(&mut self) {
+        self.x_range = None;
+        self.y_range = None;
+    }
+
+    /// The zoomed range, falling back to `full` on unzoomed axes
+    pub(crate) fn visible_range(&self, full: VisibleRange) -> VisibleRange {
+        VisibleRange::new(self.x_range.unwrap_or(full.x()), self.y_range.unwrap_or(full.y()))
+    }
+
+    /// Copy the parts of `other` shared by a link group
+    pub(crate) fn copy_linked(&mut self, other: &ChartState, link: &LinkGroup) {
+        if link.link_x {
+            self.x_range = other.x_range;
+        }
+        if link.link_y {
+            self.y_range = other.y_range;
+        }
+        if link.link_visibility {
+            self.hidden_datasets = other.hidden_datasets.clone();
+            self.hidden_data = other.hidden_data.clone();
+        }
+    }
+
+    /// Animated visibility of a dataset: 1.0 = shown, 0.0 = hidden
+    pub(crate) fn dataset_amount(&self, ctx: &Context, id: Id, dataset: usize, duration: f32) -> f32 {
+        ctx.animate_bool_with_time(id.with(("dataset_visible", dataset)), self.is_visible(dataset), duration)
+    }
+
+    /// Animated visibility of a data element: 1.0 = shown, 0.0 = hidden
+    pub(crate) fn data_amount(&self, ctx: &Context, id: Id, index: usize, duration: f32) -> f32 {
+        ctx.animate_bool_with_time(id.with(("data_visible", index)), self.is_data_visible(index), duration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_visibility() {
+        let mut state = ChartState::default();
+        assert!(state.is_visible(0));
+
+        state.toggle_visible(0);
+        assert!(!state.is_visible(0));
+        state.set_visible(0, true);
+        assert!(state.is_visible(0));
+
+        state.toggle_data_visibility(3);
+        assert!(!state.is_data_visible(3));
+        assert!(state.is_data_visible(2));
+    }
+
+    #[test]
+    fn test_store_and_load() {
+        let ctx = Context::default();
+        let id = Id::new("chart");
+        assert!(ChartState::load(&ctx, id).is_none());
+
+        let mut state = ChartState::default();
+        state.set_visible(1, false);
+        state.clone().store(&ctx, id);
+
+        assert_eq!(ChartState::load(&ctx, id), Some(state));
+    }
+
+    #[test]
+    fn test_zoom_and_reset() {
+        let full = VisibleRange::new((0.0, 10.0), (0.0, 100.0));
+        let mut state = ChartState::default();
+        assert_eq!(state.visible_range(full), full);
+
+        state.set_x_range(Some((2.0, 4.0)));
+        assert!(state.is_zoomed());
+        assert_eq!(state.visible_range(full), VisibleRange::new((2.0, 4.0), (0.0, 100.0)));
+
+        state.reset_zoom();
+        assert!(!state.is_zoomed());
+        assert_eq!(state.visible_range(full), full);
+    }
+}
@@ -0,0 +1,359 @@
+use std::collections::VecDeque;
+
+use crate::data::ChartData;
+
+/// Longest and shortest time one scroll step takes, in seconds
+const MAX_SCROLL_SECS: f32 = 1.0;
+const MIN_SCROLL_SECS: f32 = 0.02;
+
+/// Live samples kept in a ring buffer, for charts that scroll as data arrives
+///
+/// Keep the stream in your app state, push samples as they come in and show
+/// it every frame. Old samples slide out once the buffer is full or older
+/// than the time window. The chart scrolls to new samples instead of
+/// replaying its grow animation.
+///
+/// As [`ChartData`], samples sit at their times on the x axis. Charts place
+/// samples of a time-window stream by time and span the window; streams
+/// capped only by capacity are shown one step per sample.
+///
+/// ```rust,ignore
+/// // Every 100ms
+/// stream.push(ui.input(|i| i.time), read_sensor());
+///
+/// LineChart::new().stream(&stream).show(ui);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DataStream {
+    /// `(time, value)` of each sample, oldest first
+    samples: VecDeque<(f64, f64)>,
+    capacity: Option<usize>,
+    window: Option<f64>,
+    /// Samples pushed since the stream was created or cleared
+    pushed: u64,
+    /// Times the stream was cleared
+    clears: u64,
+    /// Changes with every push and clear
+    version: u64,
+}
+
+impl DataStream {
+    /// Keep the latest `capacity` samples
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity: Some(capacity.max(1)),
+            window: None,
+            pushed: 0,
+            clears: 0,
+            version: 0,
+        }
+    }
+
+    /// Keep the samples of the last `secs` seconds, by the time they were pushed with
+    pub fn with_window(secs: f64) -> Self {
+        Self {
+            samples: VecDeque::new(),
+            capacity: None,
+            window: Some(secs),
+            pushed: 0,
+            clears: 0,
+            version: 0,
+        }
+    }
+
+    /// Also keep at most `capacity` samples
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity.max(1));
+        self
+    }
+
+    /// Add a sample taken at `time` seconds (on any clock, such as
+    /// `ui.input(|i| i.time)`), dropping those that no longer fit
+    pub fn push(&mut self, time: f64, value: f64) {
+        self.samples.push_back((time, value));
+        self.pushed += 1;
+        self.version += 1;
+
+        if let Some(capacity) = self.capacity {
+            while self.samples.len() > capacity {
+                self.samples.pop_front();
+            }
+        }
+        if let Some(window) = self.window {
+            while self.samples.front().is_some_and(|(t, _)| *t < time - window) {
+                self.samples.pop_front();
+            }
+        }
+    }
+
+    /// Remove all samples; charts showing the stream animate in again
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.pushed = 0;
+        self.clears += 1;
+        self.version += 1;
+    }
+
+    /// Values of the samples in the buffer, oldest first
+    pub fn values(&self) -> impl Iterator<Item = f64> + '_ {
+        self.samples.iter().map(|(_, v)| *v)
+    }
+
+    /// Times of the samples in the buffer, oldest first
+    pub fn times(&self) -> impl Iterator<Item = f64> + '_ {
+        self.samples.iter().map(|(t, _)| *t)
+    }
+
+    /// The newest `(time, value)`
+    pub fn latest(&self) -> Option<(f64, f64)> {
+        self.samples.back().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Samples pushed since the stream was created or cleared, including
+    /// those that slid out
+    pub fn pushed(&self) -> u64 {
+        self.pushed
+    }
+
+    /// Number of samples a full buffer holds, if capped
+    pub fn max_len(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Seconds of samples kept, if windowed
+    pub fn window(&self) -> Option<f64> {
+        self.window
+    }
+}
+
+impl ChartData for DataStream {
+    fn len(&self) -> usize {
+        self.samples.len()
+    }
+    fn y(&self, index: usize) -> f64 {
+        self.samples[index].1
+    }
+    fn x(&self, index: usize) -> f64 {
+        self.samples[index].0
+    }
+    fn version(&self) -> Option<u64> {
+        Some(self.version)
+    }
+}
+
+/// A stream placed one step per sample, for streams without a time window
+pub(crate) struct BySample<'a>(pub &'a DataStream);
+
+impl ChartData for BySample<'_> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+    fn y(&self, index: usize) -> f64 {
+        self.0.y(index)
+    }
+    fn version(&self) -> Option<u64> {
+        self.0.version()
+    }
+}
+
+/// What a chart shows of a [`DataStream`]
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct StreamView {
+    /// Samples pushed so far
+    pub pushed: u64,
+    /// Times the stream was cleared; a new clear starts the chart over
+    pub clears: u64,
+    /// Number of samples the x axis spans
+    pub span: Option<usize>,
+    /// Time the x axis spans, for streams placed by time
+    pub window: Option<f64>,
+    /// Time of the newest sample
+    pub latest: Option<f64>,
+}
+
+impl StreamView {
+    pub fn new(stream: &DataStream) -> Self {
+        Self {
+            pushed: stream.pushed(),
+            clears: stream.clears,
+            span: stream.max_len(),
+            window: stream.window,
+            latest: stream.latest().map(|(time, _)| time),
+        }
+    }
+
+    /// Position of the newest sample on the x axis: its time for streams
+    /// placed by time, otherwise its sequence number
+    pub fn position(&self) -> Option<f64> {
+        match self.window {
+            Some(_) => self.latest,
+            None => Some(self.pushed as f64),
+        }
+    }
+
+    /// Sequence number of the oldest of `len` samples in the buffer
+    pub fn first_sample(&self, len: usize) -> u64 {
+        self.pushed.saturating_sub(len as u64)
+    }
+}
+
+/// Scroll position of a streaming chart, kept in chart memory
+///
+/// Each new sample shifts the data to the left, by one step or by the time
+/// since the previous sample. The offset starts that far behind and catches
+/// up at a steady speed, taking as long as the time since the previous
+/// sample, so regular samples scroll continuously.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct StreamScroll {
+    /// Clears and position of the newest sample as of the last frame, `None`
+    /// before the first
+    seen: Option<(u64, Option<f64>)>,
+    /// Distance along x the drawing lags behind the data (zero or negative)
+    offset: f32,
+    /// Distance per second the offset catches up at
+    speed: f32,
+    /// Time of the last frame and of the last new sample
+    time: f64,
+    sample_time: f64,
+}
+
+impl StreamScroll {
+    /// Advance to the samples of `stream` at time `now`. Returns true when
+    /// the stream was cleared, and should animate in like new data.
+    pub fn update(&mut self, stream: StreamView, now: f64, animate: bool) -> bool {
+        let dt = (now - self.time).max(0.0) as f32;
+        self.time = now;
+        self.offset = (self.offset + self.speed * dt).min(0.0);
+
+        let position = stream.position();
+        let Some((clears, last)) = self.seen.replace((stream.clears, position)) else {
+            self.sample_time = now;
+            return false;
+        };
+        if stream.clears != clears {
+            self.offset = 0.0;
+            self.sample_time = now;
+            return true;
+        }
+        // The first sample after an empty time-window stream has nothing to scroll from
+        if let Some(step) = position.zip(last).map(|(position, last)| position - last).filter(|step| *step > 0.0) {
+            let interval = ((now - self.sample_time) as f32).clamp(MIN_SCROLL_SECS, MAX_SCROLL_SECS);
+            self.sample_time = now;
+            self.offset = if animate { self.offset - step as f32 } else { 0.0 };
+            self.speed = -self.offset / interval;
+        }
+        false
+    }
+
+    /// Distance along x the drawing lags behind the data
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    pub fn is_scrolling(&self) -> bool {
+        self.offset < 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capacity_and_window() {
+        let mut stream = DataStream::with_capacity(3);
+        for i in 0..5 {
+            stream.push(i as f64, i as f64 * 10.0);
+        }
+        assert_eq!(stream.values().collect::<Vec<_>>(), vec![20.0, 30.0, 40.0]);
+        assert_eq!(stream.pushed(), 5);
+        assert_eq!(StreamView::new(&stream).first_sample(stream.len()), 2);
+
+        let mut stream = DataStream::with_window(1.0);
+        for i in 0..10 {
+            stream.push(i as f64 * 0.25, i as f64);
+        }
+        // Samples from 1.25s to 2.25s
+        assert_eq!(stream.times().next(), Some(1.25));
+        assert_eq!(stream.latest(), Some((2.25, 9.0)));
+
+        stream.clear();
+        assert!(stream.is_empty());
+        assert_eq!(stream.pushed(), 0);
+    }
+
+    fn view(clears: u64, pushed: u64) -> StreamView {
+        StreamView { pushed, clears, span: None, window: None, latest: None }
+    }
+
+    #[test]
+    fn test_scroll_catches_up_by_next_sample() {
+        let mut scroll = StreamScroll::default();
+        // The samples already there when first shown don't scroll
+        assert!(!scroll.update(view(0, 10), 0.0, true));
+        assert!(!scroll.is_scrolling());
+
+        // A sample every 100ms
+        assert!(!scroll.update(view(0, 11), 0.1, true));
+        assert_eq!(scroll.offset(), -1.0);
+        scroll.update(view(0, 11), 0.15, true);
+        assert!((scroll.offset() + 0.5).abs() < 1e-4);
+        scroll.update(view(0, 11), 0.2, true);
+        assert!(scroll.offset().abs() < 1e-4);
+
+        // Without animation the chart jumps
+        scroll.update(view(0, 12), 0.3, false);
+        assert!(!scroll.is_scrolling());
+
+        // A cleared stream starts over, even with as many samples pushed again
+        assert!(scroll.update(view(1, 12), 0.4, true));
+        assert!(!scroll.is_scrolling());
+        assert!(!scroll.update(view(1, 13), 0.5, true));
+        assert!(scroll.is_scrolling());
+    }
+
+    #[test]
+    fn test_window_streams_scroll_by_time() {
+        let mut stream = DataStream::with_window(1.0);
+        let mut scroll = StreamScroll::default();
+        assert!(!scroll.update(StreamView::new(&stream), 0.0, true));
+
+        // Nothing to scroll from while empty
+        stream.push(5.0, 1.0);
+        scroll.update(StreamView::new(&stream), 0.1, true);
+        assert!(!scroll.is_scrolling());
+
+        stream.push(5.25, 2.0);
+        scroll.update(StreamView::new(&stream), 0.2, true);
+        assert_eq!(scroll.offset(), -0.25);
+
+        // Samples sit at their times, unless shown one step per sample
+        assert_eq!(stream.x(1), 5.25);
+        assert_eq!(BySample(&stream).x(1), 1.0);
+        assert_eq!(StreamView::new(&stream).position(), Some(5.25));
+    }
+
+    #[test]
+    fn test_clear_counts_restarts() {
+        let mut stream = DataStream::with_capacity(4);
+        stream.push(0.0, 1.0);
+        stream.push(1.0, 2.0);
+        let before = StreamView::new(&stream);
+        stream.clear();
+        for i in 0..3 {
+            stream.push(i as f64, 0.0);
+        }
+        let after = StreamView::new(&stream);
+        assert!(after.pushed > before.pushed);
+        assert_ne!(after.clears, before.clears);
+    }
+}
@@ -0,0 +1,56 @@
+use egui::{Align2, Color32, FontId, Pos2};
+
+use crate::plugin::{ChartLayout, ChartPlugin, PluginContext};
+
+/// Chart title drawn above the chart area, implemented as a plugin
+///
+/// `.title("Sales")` on any chart adds one; use [`Title::new`] with
+/// `.plugin()` for more control.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Title {
+    pub text: String,
+    pub font_size: f32,
+    /// Text color, defaults to the theme's text color
+    pub color: Option<Color32>,
+    /// Space above and below the title
+    pub padding: f32,
+}
+
+impl Title {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            font_size: 15.0,
+            color: None,
+            padding: 6.0,
+        }
+    }
+
+    /// Set font size
+    pub fn font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    /// Set text color
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+impl ChartPlugin for Title {
+    fn before_layout(&self, layout: &mut ChartLayout) {
+        layout.top += self.font_size + self.padding * 2.0;
+    }
+
+    fn before_draw(&self, ctx: &PluginContext<'_>) {
+        ctx.painter.text(
+            Pos2::new(ctx.rect.center().x, ctx.rect.min.y + self.padding),
+            Align2::CENTER_TOP,
+            &self.text,
+            FontId::proportional(self.font_size),
+            self.color.unwrap_or(ctx.theme.text_color),
+        );
+    }
+}
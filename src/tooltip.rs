@@ -1,5 +1,7 @@
 use egui::{Color32, CornerRadius, FontId, Painter, Pos2, Rect, Stroke, StrokeKind, Vec2};
 
+use crate::plugin::{ChartPlugin, PluginContext};
+
 /// Tooltip configuration
 #[derive(Clone, Debug)]
 pub struct TooltipConfig {
@@ -28,9 +30,17 @@ impl Default for TooltipConfig {
     }
 }
 
+/// Color indicator size
+const INDICATOR_SIZE: f32 = 10.0;
+/// Gap between color indicator and text
+const INDICATOR_MARGIN: f32 = 8.0;
+/// Gap between title and value row
+const TITLE_SPACING: f32 = 4.0;
+
 /// Tooltip content
 #[derive(Clone, Debug)]
 pub struct TooltipContent {
+    /// Optional heading drawn above the value row
     pub title: Option<String>,
     pub label: String,
     pub value: String,
@@ -72,24 +82,19 @@ pub fn draw_tooltip(
 
     // Calculate text layout
     let label_text = format!("{}: ", content.label);
-    let galley_label = painter.layout_no_wrap(label_text.clone(), font_id.clone(), config.text_color);
+    let galley_label = painter.layout_no_wrap(label_text, font_id.clone(), config.text_color);
     let galley_value = painter.layout_no_wrap(content.value.clone(), font_id.clone(), config.text_color);
+    let galley_title = content
+        .title
+        .as_ref()
+        .map(|title| painter.layout_no_wrap(title.clone(), font_id, config.text_color));
 
-    let text_width = galley_label.size().x + galley_value.size().x;
-    let text_height = galley_label.size().y.max(galley_value.size().y);
-
-    // Color indicator size
-    let indicator_size = 10.0;
-    let indicator_margin = 8.0;
+    let label_width = galley_label.size().x;
+    let row_height = galley_label.size().y.max(galley_value.size().y);
+    let title_height = galley_title.as_ref().map_or(0.0, |g| g.size().y + TITLE_SPACING);
 
     // Calculate background rect
-    let bg_rect = Rect::from_min_size(
-        position,
-        Vec2::new(
-            indicator_size + indicator_margin + text_width + config.padding.x * 2.0,
-            text_height + config.padding.y * 2.0,
-        ),
-    );
+    let bg_rect = Rect::from_min_size(position, measure_tooltip_size(painter, content, config));
 
     // Draw shadow (subtle)
     let shadow_offset = Vec2::new(2.0, 2.0);
@@ -113,30 +118,32 @@ pub fn draw_tooltip(
         );
     }
 
+    // Draw title above the value row
+    if let Some(galley_title) = galley_title {
+        painter.galley(
+            bg_rect.min + config.padding,
+            galley_title,
+            config.text_color,
+        );
+    }
+
     // Draw color indicator (small square)
+    let row_top = bg_rect.min.y + config.padding.y + title_height;
     let indicator_rect = Rect::from_min_size(
         Pos2::new(
             bg_rect.min.x + config.padding.x,
-            bg_rect.center().y - indicator_size / 2.0,
+            row_top + (row_height - INDICATOR_SIZE) / 2.0,
         ),
-        Vec2::splat(indicator_size),
+        Vec2::splat(INDICATOR_SIZE),
     );
     painter.rect_filled(indicator_rect, CornerRadius::same(2), content.color);
 
     // Draw text
-    let text_x = indicator_rect.max.x + indicator_margin;
-    let text_y = bg_rect.min.y + config.padding.y;
+    let text_x = indicator_rect.max.x + INDICATOR_MARGIN;
 
-    painter.galley(Pos2::new(text_x, text_y), galley_label, config.text_color);
+    painter.galley(Pos2::new(text_x, row_top), galley_label, config.text_color);
     painter.galley(
-        Pos2::new(
-            text_x
-                + painter
-                    .layout_no_wrap(label_text, font_id, config.text_color)
-                    .size()
-                    .x,
-            text_y,
-        ),
+        Pos2::new(text_x + label_width, row_top),
         galley_value,
         config.text_color,
     );
@@ -152,20 +159,37 @@ pub fn measure_tooltip_size(
 
     let label_text = format!("{}: ", content.label);
     let galley_label = painter.layout_no_wrap(label_text, font_id.clone(), config.text_color);
-    let galley_value = painter.layout_no_wrap(content.value.clone(), font_id, config.text_color);
+    let galley_value = painter.layout_no_wrap(content.value.clone(), font_id.clone(), config.text_color);
 
-    let text_width = galley_label.size().x + galley_value.size().x;
-    let text_height = galley_label.size().y.max(galley_value.size().y);
+    let mut text_width = INDICATOR_SIZE + INDICATOR_MARGIN + galley_label.size().x + galley_value.size().x;
+    let mut text_height = galley_label.size().y.max(galley_value.size().y);
 
-    let indicator_size = 10.0;
-    let indicator_margin = 8.0;
+    if let Some(title) = &content.title {
+        let galley_title = painter.layout_no_wrap(title.clone(), font_id, config.text_color);
+        text_width = text_width.max(galley_title.size().x);
+        text_height += galley_title.size().y + TITLE_SPACING;
+    }
 
     Vec2::new(
-        indicator_size + indicator_margin + text_width + config.padding.x * 2.0,
+        text_width + config.padding.x * 2.0,
         text_height + config.padding.y * 2.0,
     )
 }
 
+/// Built-in plugin drawing the hovered element's tooltip, after all other plugins
+pub(crate) struct TooltipPlugin;
+
+impl ChartPlugin for TooltipPlugin {
+    fn after_draw(&self, ctx: &PluginContext<'_>) {
+        if let Some(tooltip) = ctx.tooltip {
+            let config = &ctx.theme.tooltip;
+            let size = measure_tooltip_size(ctx.painter, &tooltip.content, config);
+            let position = calculate_tooltip_position(tooltip.anchor, size, ctx.rect);
+            draw_tooltip(ctx.painter, &tooltip.content, position, config);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,404 @@
+use egui::{Color32, Modifiers, Painter, Pos2, Rect, Response, Stroke, StrokeKind, Ui};
+
+use crate::scale::LinearScale;
+use crate::state::ChartState;
+
+/// Smallest drag-select box, in pixels, that counts as a zoom
+const MIN_SELECT_SIZE: f32 = 5.0;
+
+/// Which axes zooming and panning act on (Chart.js zoom plugin `mode`)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ZoomAxis {
+    X,
+    Y,
+    #[default]
+    XY,
+}
+
+impl ZoomAxis {
+    fn x(self) -> bool {
+        matches!(self, ZoomAxis::X | ZoomAxis::XY)
+    }
+
+    fn y(self) -> bool {
+        matches!(self, ZoomAxis::Y | ZoomAxis::XY)
+    }
+}
+
+/// Bounds for zooming and panning along one axis
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AxisLimits {
+    /// Lowest value that can be scrolled into view
+    pub min: Option<f64>,
+    /// Highest value that can be scrolled into view
+    pub max: Option<f64>,
+    /// Smallest range that can be zoomed into
+    pub min_range: Option<f64>,
+}
+
+impl AxisLimits {
+    /// Clamp a visible range to these limits, keeping its size where possible
+    pub fn clamp(&self, (mut lo, mut hi): (f64, f64)) -> (f64, f64) {
+        if let Some(min_range) = self.min_range {
+            if hi - lo < min_range {
+                let center = (lo + hi) / 2.0;
+                lo = center - min_range / 2.0;
+                hi = center + min_range / 2.0;
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if hi - lo >= max - min {
+                return (min, max);
+            }
+        }
+        if let Some(min) = self.min.filter(|min| lo < *min) {
+            hi += min - lo;
+            lo = min;
+        }
+        if let Some(max) = self.max.filter(|max| hi > *max) {
+            lo -= hi - max;
+            hi = max;
+        }
+        (lo, hi)
+    }
+}
+
+/// Zoom and pan behavior for cartesian charts
+///
+/// Disabled by default. [`ZoomConfig::new`] turns on wheel, pinch and drag-to-pan.
+///
+/// ```rust,ignore
+/// LineChart::new()
+///     .data(data)
+///     .zoom(ZoomConfig::new().axis(ZoomAxis::X).drag_select(true).pan_modifier(Modifiers::SHIFT))
+///     .show(ui);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZoomConfig {
+    /// Zoom with the mouse wheel
+    pub wheel: bool,
+    /// Zoom with pinch gestures (and ctrl + wheel)
+    pub pinch: bool,
+    /// Zoom to a box dragged over the chart
+    pub drag_select: bool,
+    /// Pan by dragging
+    pub pan: bool,
+    /// Modifier keys that must be held to pan; lets drag-select and pan share the mouse
+    pub pan_modifier: Modifiers,
+    /// Axes affected by zoom and pan
+    pub axis: ZoomAxis,
+    /// Zoom speed per scrolled point
+    pub speed: f32,
+    pub x_limits: AxisLimits,
+    pub y_limits: AxisLimits,
+}
+
+impl Default for ZoomConfig {
+    fn default() -> Self {
+        Self {
+            wheel: false,
+            pinch: false,
+            drag_select: false,
+            pan: false,
+            pan_modifier: Modifiers::NONE,
+            axis: ZoomAxis::XY,
+            speed: 0.002,
+            x_limits: AxisLimits::default(),
+            y_limits: AxisLimits::default(),
+        }
+    }
+}
+
+impl ZoomConfig {
+    /// Wheel and pinch zoom with drag-to-pan
+    pub fn new() -> Self {
+        Self {
+            wheel: true,
+            pinch: true,
+            pan: true,
+            ..Default::default()
+        }
+    }
+
+    /// Enable/disable mouse wheel zoom
+    pub fn wheel(mut self, enabled: bool) -> Self {
+        self.wheel = enabled;
+        self
+    }
+
+    /// Enable/disable pinch zoom
+    pub fn pinch(mut self, enabled: bool) -> Self {
+        self.pinch = enabled;
+        self
+    }
+
+    /// Enable/disable drag-to-select zoom
+    pub fn drag_select(mut self, enabled: bool) -> Self {
+        self.drag_select = enabled;
+        self
+    }
+
+    /// Enable/disable panning
+    pub fn pan(mut self, enabled: bool) -> Self {
+        self.pan = enabled;
+        self
+    }
+
+    /// Require modifier keys for panning; other drags fall through to drag-select
+    pub fn pan_modifier(mut self, modifiers: Modifiers) -> Self {
+        self.pan_modifier = modifiers;
+        self
+    }
+
+    /// Restrict zoom and pan to one axis
+    pub fn axis(mut self, axis: ZoomAxis) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    /// Set zoom speed per scrolled point
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Keep the x range within `min..=max`
+    pub fn x_limits(mut self, min: f64, max: f64) -> Self {
+        self.x_limits.min = Some(min);
+        self.x_limits.max = Some(max);
+        self
+    }
+
+    /// Keep the y range within `min..=max`
+    pub fn y_limits(mut self, min: f64, max: f64) -> Self {
+        self.y_limits.min = Some(min);
+        self.y_limits.max = Some(max);
+        self
+    }
+
+    /// Smallest ranges that can be zoomed into
+    pub fn min_range(mut self, x: f64, y: f64) -> Self {
+        self.x_limits.min_range = Some(x);
+        self.y_limits.min_range = Some(y);
+        self
+    }
+
+    /// Whether any zoom or pan input is enabled
+    pub fn enabled(&self) -> bool {
+        self.wheel || self.pinch || self.drag_select || self.pan
+    }
+
+    /// Whether a drag with these modifiers pans (otherwise it may drag-select)
+    fn pans_with(&self, modifiers: Modifiers) -> bool {
+        if !self.pan {
+            return false;
+        }
+        if self.pan_modifier.is_none() {
+            !self.drag_select
+        } else {
+            modifiers.contains(self.pan_modifier)
+        }
+    }
+}
+
+/// Data range currently shown on the x and y axes
+///
+/// For bar charts category `i` spans `i..i + 1` on the x axis;
+/// for line charts point `i` sits at `x = i`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VisibleRange {
+    pub x_min: f64,
+    pub x_max: f64,
+    pub y_min: f64,
+    pub y_max: f64,
+}
+
+impl VisibleRange {
+    pub fn new(x: (f64, f64), y: (f64, f64)) -> Self {
+        Self {
+            x_min: x.0,
+            x_max: x.1,
+            y_min: y.0,
+            y_max: y.1,
+        }
+    }
+
+    pub fn x(&self) -> (f64, f64) {
+        (self.x_min, self.x_max)
+    }
+
+    pub fn y(&self) -> (f64, f64) {
+        (self.y_min, self.y_max)
+    }
+}
+
+/// Scale `range` by `factor` (< 1 zooms in) keeping `anchor` fixed
+pub(crate) fn zoom_range((lo, hi): (f64, f64), anchor: f64, factor: f64) -> (f64, f64) {
+    (anchor - (anchor - lo) * factor, anchor + (hi - anchor) * factor)
+}
+
+/// Apply zoom and pan input to the chart's [`ChartState`]
+///
+/// `x_scale` and `y_scale` describe what is currently drawn. Returns the
+/// drag-select box being dragged, if any, so the chart can paint it.
+pub(crate) fn handle_zoom_input(
+    ui: &Ui,
+    response: &Response,
+    config: &ZoomConfig,
+    x_scale: &LinearScale,
+    y_scale: &LinearScale,
+    state: &mut ChartState,
+) -> Option<Rect> {
+    if !config.enabled() {
+        return None;
+    }
+
+    let chart_rect = Rect::from_x_y_ranges(
+        x_scale.pixel_min.min(x_scale.pixel_max)..=x_scale.pixel_min.max(x_scale.pixel_max),
+        y_scale.pixel_min.min(y_scale.pixel_max)..=y_scale.pixel_min.max(y_scale.pixel_max),
+    );
+    let mut x_range = (x_scale.min, x_scale.max);
+    let mut y_range = (y_scale.min, y_scale.max);
+    let mut changed = false;
+
+    // Wheel and pinch zoom around the pointer
+    if let Some(pos) = response.hover_pos().filter(|p| chart_rect.contains(*p)) {
+        let (scroll, pinch) = ui.input(|i| (i.smooth_scroll_delta.y, i.zoom_delta()));
+        let mut factor = 1.0;
+        if config.wheel && scroll != 0.0 {
+            factor *= (-(scroll * config.speed) as f64).exp();
+            ui.ctx().input_mut(|i| i.smooth_scroll_delta.y = 0.0);
+        }
+        if config.pinch && pinch != 1.0 {
+            factor /= pinch as f64;
+        }
+        if factor != 1.0 {
+            if config.axis.x() {
+                x_range = zoom_range(x_range, x_scale.pixel_to_value(pos.x), factor);
+            }
+            if config.axis.y() {
+                y_range = zoom_range(y_range, y_scale.pixel_to_value(pos.y), factor);
+            }
+            changed = true;
+        }
+    }
+
+    // Drag: pan, or remember where a drag-select box started
+    let select_id = response.id.with("zoom_select");
+    let modifiers = ui.input(|i| i.modifiers);
+    if response.drag_started() && config.drag_select && !config.pans_with(modifiers) {
+        if let Some(pos) = ui.input(|i| i.pointer.press_origin()) {
+            ui.ctx().data_mut(|d| d.insert_temp(select_id, pos));
+        }
+    }
+    let select_start: Option<Pos2> = ui.ctx().data(|d| d.get_temp(select_id));
+
+    let mut selection = None;
+    if let Some(start) = select_start {
+        let end = response.interact_pointer_pos().unwrap_or(start);
+        let mut rect = Rect::from_two_pos(start, end).intersect(chart_rect);
+        if !config.axis.x() {
+            rect = Rect::from_x_y_ranges(chart_rect.x_range(), rect.y_range());
+        }
+        if !config.axis.y() {
+            rect = Rect::from_x_y_ranges(rect.x_range(), chart_rect.y_range());
+        }
+
+        if response.drag_stopped() {
+            ui.ctx().data_mut(|d| d.remove::<Pos2>(select_id));
+            if config.axis.x() && rect.width() >= MIN_SELECT_SIZE {
+                x_range = ordered(x_scale.pixel_to_value(rect.min.x), x_scale.pixel_to_value(rect.max.x));
+                changed = true;
+            }
+            if config.axis.y() && rect.height() >= MIN_SELECT_SIZE {
+                y_range = ordered(y_scale.pixel_to_value(rect.min.y), y_scale.pixel_to_value(rect.max.y));
+                changed = true;
+            }
+        } else {
+            selection = Some(rect);
+        }
+    } else if response.dragged() && config.pans_with(modifiers) {
+        let delta = response.drag_delta();
+        if config.axis.x() && delta.x != 0.0 {
+            let shift = x_scale.pixel_to_value(x_scale.pixel_min) - x_scale.pixel_to_value(x_scale.pixel_min + delta.x);
+            x_range = (x_range.0 + shift, x_range.1 + shift);
+            changed = true;
+        }
+        if config.axis.y() && delta.y != 0.0 {
+            let shift = y_scale.pixel_to_value(y_scale.pixel_min) - y_scale.pixel_to_value(y_scale.pixel_min + delta.y);
+            y_range = (y_range.0 + shift, y_range.1 + shift);
+            changed = true;
+        }
+    }
+
+    if response.double_clicked() {
+        state.reset_zoom();
+    } else if changed {
+        if config.axis.x() {
+            state.set_x_range(Some(config.x_limits.clamp(x_range)));
+        }
+        if config.axis.y() {
+            state.set_y_range(Some(config.y_limits.clamp(y_range)));
+        }
+    }
+
+    selection
+}
+
+/// Paint the drag-select box
+pub(crate) fn draw_selection(painter: &Painter, rect: Rect, color: Color32) {
+    painter.rect(
+        rect,
+        0.0,
+        color.gamma_multiply(0.15),
+        Stroke::new(1.0, color),
+        StrokeKind::Inside,
+    );
+}
+
+fn ordered(a: f64, b: f64) -> (f64, f64) {
+    (a.min(b), a.max(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zoom_range_keeps_anchor() {
+        let (lo, hi) = zoom_range((0.0, 10.0), 2.0, 0.5);
+        assert!((lo - 1.0).abs() < 1e-9);
+        assert!((hi - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_axis_limits_clamp() {
+        let limits = AxisLimits {
+            min: Some(0.0),
+            max: Some(10.0),
+            min_range: Some(2.0),
+        };
+
+        // Panning past an edge keeps the range size
+        assert_eq!(limits.clamp((-3.0, 2.0)), (0.0, 5.0));
+        assert_eq!(limits.clamp((8.0, 12.0)), (6.0, 10.0));
+        // Zooming out past the limits shows everything
+        assert_eq!(limits.clamp((-5.0, 20.0)), (0.0, 10.0));
+        // Zooming in stops at the minimum range
+        assert_eq!(limits.clamp((4.5, 5.5)), (4.0, 6.0));
+        // No limits: unchanged
+        assert_eq!(AxisLimits::default().clamp((-5.0, 20.0)), (-5.0, 20.0));
+    }
+
+    #[test]
+    fn test_pan_modifier_separates_drag_modes() {
+        let config = ZoomConfig::new();
+        assert!(config.pans_with(Modifiers::NONE));
+
+        let config = ZoomConfig::new().drag_select(true).pan_modifier(Modifiers::SHIFT);
+        assert!(!config.pans_with(Modifiers::NONE));
+        assert!(config.pans_with(Modifiers::SHIFT));
+
+        assert!(!ZoomConfig::default().enabled());
+    }
+}